@@ -88,7 +88,7 @@ pub fn benchmark_data_loader(c: &mut Criterion) {
                     cmd_worker: None,
                     worker: None,
                 };
-                let loader = HttpDataLoader::new(rt, None, false);
+                let loader = HttpDataLoader::new(rt, None, false, None);
                 let loader = loader.to_data_loader(Batch::default().delay(1));
 
                 let request1 = reqwest::Request::new(
@@ -11,6 +11,8 @@ use tailcall_valid::{Valid, ValidateFrom, Validator};
 use url::Url;
 
 use crate::core::config::transformer::Preset;
+use crate::core::config::KeyValue;
+use crate::core::generator::{FromProtoOptions, OneofStrategy, ServiceGrouping};
 use crate::core::http::Method;
 
 #[derive(Deserialize, Serialize, Debug, Default, Setters)]
@@ -35,6 +37,10 @@ pub struct LLMConfig {
     pub model: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub secret: Option<String>,
+    /// Directory where the answers of the model are cached, so the identical
+    /// questions aren't asked again between the runs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_dir: Option<String>,
 }
 
 #[derive(Clone, Deserialize, Serialize, Debug, Default)]
@@ -45,6 +51,7 @@ pub struct PresetConfig {
     pub infer_type_names: Option<bool>,
     pub tree_shake: Option<bool>,
     pub unwrap_single_field_types: Option<bool>,
+    pub shared_args: Option<bool>,
 }
 #[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(transparent)]
@@ -89,12 +96,35 @@ pub enum Source<Status = UnResolved> {
         #[serde(skip_serializing_if = "Option::is_none")]
         #[serde(rename = "connectRPC")]
         connect_rpc: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        options: Option<ProtoOptions>,
     },
     Config {
         src: Location<Status>,
     },
 }
 
+/// Options that control how the config is generated from the proto file.
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct ProtoOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip_deprecated: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_grouping: Option<ServiceGrouping>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flatten_input_oneofs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oneof_strategy: Option<OneofStrategy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_method_prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_presence: Option<bool>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
@@ -148,6 +178,10 @@ impl ValidateFrom<PresetConfig> for Preset {
             preset = preset.tree_shake(tree_shake);
         }
 
+        if let Some(shared_args) = config.shared_args {
+            preset = preset.shared_args(shared_args);
+        }
+
         // TODO: The field names in trace should be inserted at compile time.
         Valid::succeed(preset)
             .and_then(|preset| {
@@ -159,6 +193,47 @@ impl ValidateFrom<PresetConfig> for Preset {
     }
 }
 
+impl From<ProtoOptions> for FromProtoOptions {
+    fn from(config: ProtoOptions) -> Self {
+        let mut options = FromProtoOptions::default();
+
+        if let Some(skip_deprecated) = config.skip_deprecated {
+            options = options.skip_deprecated(skip_deprecated);
+        }
+
+        if let Some(service_grouping) = config.service_grouping {
+            options = options.service_grouping(service_grouping);
+        }
+
+        if let Some(flatten_input_oneofs) = config.flatten_input_oneofs {
+            options = options.flatten_input_oneofs(flatten_input_oneofs);
+        }
+
+        if let Some(oneof_strategy) = config.oneof_strategy {
+            options = options.oneof_strategy(oneof_strategy);
+        }
+
+        if let Some(batch_method_prefix) = config.batch_method_prefix {
+            options = options.batch_method_prefix(batch_method_prefix);
+        }
+
+        if let Some(headers) = config.headers {
+            options = options.headers(
+                headers
+                    .into_iter()
+                    .map(|(key, value)| KeyValue { key, value })
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        if let Some(field_presence) = config.field_presence {
+            options = options.field_presence(field_presence);
+        }
+
+        options
+    }
+}
+
 impl<A> Location<A> {
     fn is_empty(&self) -> bool {
         self.0.is_empty()
@@ -217,7 +292,7 @@ impl Source<UnResolved> {
                     is_mutation,
                 })
             }
-            Source::Proto { src, url, proto_paths, connect_rpc } => {
+            Source::Proto { src, url, proto_paths, connect_rpc, options } => {
                 let resolved_path = src.into_resolved(parent_dir);
                 let resolved_proto_paths = proto_paths.map(|paths| {
                     paths
@@ -230,6 +305,7 @@ impl Source<UnResolved> {
                     url,
                     proto_paths: resolved_proto_paths,
                     connect_rpc,
+                    options,
                 })
             }
             Source::Config { src } => {
@@ -261,7 +337,12 @@ impl Config {
         let output = self.output.resolve(parent_dir)?;
         let llm = self.llm.map(|llm| {
             let secret = llm.secret;
-            LLMConfig { model: llm.model, secret }
+            let cache_dir = llm.cache_dir.map(|dir| {
+                Location::<UnResolved>(dir, PhantomData)
+                    .into_resolved(parent_dir)
+                    .0
+            });
+            LLMConfig { model: llm.model, secret, cache_dir }
         });
 
         Ok(Config {
@@ -335,6 +416,44 @@ mod tests {
         insta::assert_snapshot!(actual)
     }
 
+    #[test]
+    fn test_proto_options() {
+        let json = r#"
+            {"inputs": [{
+                "proto": {
+                    "src": "news.proto",
+                    "url": "http://localhost:50051",
+                    "options": {
+                        "skipDeprecated": true,
+                        "serviceGrouping": "perService",
+                        "oneofStrategy": "presence",
+                        "batchMethodPrefix": "Batch",
+                        "headers": {"authorization": "Bearer 123"}
+                    }
+                }
+            }],
+            "output": {"path": "./output.graphql"},
+            "schema": {}}
+        "#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        let options = match config.inputs.into_iter().next().unwrap().source {
+            Source::Proto { options, .. } => FromProtoOptions::from(options.unwrap()),
+            _ => panic!("Expected proto input"),
+        };
+
+        let expected = FromProtoOptions::default()
+            .skip_deprecated(true)
+            .service_grouping(ServiceGrouping::PerService)
+            .oneof_strategy(OneofStrategy::Presence)
+            .batch_method_prefix("Batch")
+            .headers(vec![KeyValue {
+                key: "authorization".to_string(),
+                value: "Bearer 123".to_string(),
+            }]);
+
+        assert_eq!(options, expected);
+    }
+
     #[test]
     fn should_fail_when_invalid_merge_type_threshold() {
         let config_preset = PresetConfig {
@@ -342,6 +461,7 @@ mod tests {
             infer_type_names: None,
             merge_type: Some(2.0),
             unwrap_single_field_types: None,
+            shared_args: None,
         };
 
         let transform_preset: Result<Preset, ValidationError<String>> =
@@ -356,6 +476,7 @@ mod tests {
             infer_type_names: Some(true),
             merge_type: Some(0.5),
             unwrap_single_field_types: None,
+            shared_args: None,
         };
         let transform_preset: Preset = config_preset.validate_into().to_result().unwrap();
         let expected_preset = Preset::new()
@@ -436,7 +557,7 @@ mod tests {
             }}
         "#;
         let expected_error =
-            "unknown field `mergeTypes`, expected one of `mergeType`, `inferTypeNames`, `treeShake`, `unwrapSingleFieldTypes` at line 3 column 28";
+            "unknown field `mergeTypes`, expected one of `mergeType`, `inferTypeNames`, `treeShake`, `unwrapSingleFieldTypes`, `sharedArgs` at line 3 column 28";
         assert_deserialization_error(json, expected_error);
     }
 
@@ -470,13 +591,17 @@ mod tests {
         let config = Config::default().llm(Some(LLMConfig {
             model: Some("gpt-3.5-turbo".to_string()),
             secret: Some(token.to_string()),
+            cache_dir: Some(".cache".to_string()),
         }));
-        let resolved_config = config.into_resolved("").unwrap();
+        let resolved_config = config
+            .into_resolved("/tmp/tailcall/generator.json")
+            .unwrap();
 
         let actual = resolved_config.llm;
         let expected = Some(LLMConfig {
             model: Some("gpt-3.5-turbo".to_string()),
             secret: Some(token.to_string()),
+            cache_dir: Some("/tmp/tailcall/.cache".to_string()),
         });
 
         assert_eq!(actual, expected);
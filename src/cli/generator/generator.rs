@@ -1,5 +1,5 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::anyhow;
 use http::header::{HeaderMap, HeaderName, HeaderValue};
@@ -9,7 +9,7 @@ use tailcall_valid::{ValidateInto, Validator};
 
 use super::config::{Config, LLMConfig, Resolved, Source};
 use super::source::ConfigSource;
-use crate::cli::llm::InferTypeName;
+use crate::cli::llm::{InferTypeName, WizardOptions};
 use crate::core::config::transformer::{Preset, RenameTypes};
 use crate::core::config::{self, ConfigModule, ConfigReaderContext};
 use crate::core::generator::{Generator as ConfigGenerator, Input};
@@ -135,7 +135,7 @@ impl Generator {
                         headers: headers.into_btree_map(),
                     });
                 }
-                Source::Proto { src, url, proto_paths, connect_rpc } => {
+                Source::Proto { src, url, proto_paths, connect_rpc, options } => {
                     let path = src.0;
                     let proto_paths =
                         proto_paths.map(|paths| paths.into_iter().map(|l| l.0).collect::<Vec<_>>());
@@ -143,7 +143,12 @@ impl Generator {
                     if let Some(relative_path_to_proto) = to_relative_path(output_dir, &path) {
                         metadata.path = relative_path_to_proto;
                     }
-                    input_samples.push(Input::Proto { metadata, url, connect_rpc });
+                    input_samples.push(Input::Proto {
+                        metadata,
+                        url,
+                        connect_rpc,
+                        options: options.map(Into::into).unwrap_or_default(),
+                    });
                 }
                 Source::Config { src } => {
                     let path = src.0;
@@ -180,8 +185,15 @@ impl Generator {
         let mut config = config_gen.mutation(mutation_type_name).generate(true)?;
 
         if infer_type_names {
-            if let Some(LLMConfig { model: Some(model), secret }) = llm {
-                let mut llm_gen = InferTypeName::new(model, secret.map(|s| s.to_string()));
+            if let Some(LLMConfig { model: Some(model), secret, cache_dir }) = llm {
+                let mut llm_gen = InferTypeName::new(
+                    model,
+                    secret.map(|s| s.to_string()),
+                    WizardOptions::default(),
+                );
+                if let Some(cache_dir) = cache_dir {
+                    llm_gen = llm_gen.cache_dir(PathBuf::from(cache_dir));
+                }
                 let suggested_names = llm_gen.generate(config.config()).await?;
                 let cfg = RenameTypes::new(suggested_names.iter())
                     .transform(config.config().to_owned())
@@ -1,17 +1,23 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 use genai::chat::{ChatMessage, ChatRequest, ChatResponse};
 use indexmap::{indexset, IndexSet};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-use super::{Error, Result, Wizard};
+use super::{Error, Result, Wizard, WizardOptions};
 use crate::core::config::Config;
 use crate::core::generator::PREFIX;
 use crate::core::Mustache;
 
 const BASE_TEMPLATE: &str = include_str!("prompts/infer_type_name.md");
 
+/// Maximum number of requests sent to the model for a single type.
+const MAX_ATTEMPTS: usize = 10;
+/// Maximum delay in seconds between the retries.
+const MAX_DELAY: u64 = 60;
+
 pub struct InferTypeName {
     wizard: Wizard<Question, Answer>,
 }
@@ -83,8 +89,21 @@ impl TryInto<ChatRequest> for Question {
 }
 
 impl InferTypeName {
-    pub fn new(model: String, secret: Option<String>) -> InferTypeName {
-        Self { wizard: Wizard::new(model, secret) }
+    pub fn new(model: String, secret: Option<String>, options: WizardOptions) -> InferTypeName {
+        Self { wizard: Wizard::new(model, secret, options) }
+    }
+
+    /// Caches the answers of the model in the directory, see
+    /// [`Wizard::cache_dir`].
+    pub fn cache_dir(mut self, dir: PathBuf) -> Self {
+        self.wizard = self.wizard.cache_dir(dir);
+        self
+    }
+
+    /// Doubles the delay between the retries up to [`MAX_DELAY`].
+    #[inline]
+    fn next_delay(delay: u64) -> u64 {
+        std::cmp::min(delay * 2, MAX_DELAY)
     }
 
     /// All generated type names starts with PREFIX
@@ -124,6 +143,7 @@ impl InferTypeName {
             };
 
             let mut delay = 3;
+            let mut attempt = 1;
             loop {
                 let answer = self.wizard.ask(question.clone()).await;
                 match answer {
@@ -150,8 +170,9 @@ impl InferTypeName {
                         // names: [names list]`
                         break;
                     }
+                    Err(e) if attempt >= MAX_ATTEMPTS => return Err(e),
                     Err(e) => {
-                        // TODO: log errors after certain number of retries.
+                        attempt += 1;
                         if let Error::GenAI(_) = e {
                             // TODO: retry only when it's required.
                             tracing::warn!(
@@ -160,7 +181,7 @@ impl InferTypeName {
                                 delay
                             );
                             tokio::time::sleep(tokio::time::Duration::from_secs(delay)).await;
-                            delay *= std::cmp::min(delay * 2, 60);
+                            delay = Self::next_delay(delay);
                         }
                     }
                 }
@@ -221,4 +242,14 @@ mod test {
         assert!(!InferTypeName::is_auto_generated("123T"));
         assert!(!InferTypeName::is_auto_generated("A1234"));
     }
+
+    #[test]
+    fn test_next_delay() {
+        let delays =
+            std::iter::successors(Some(3), |delay| Some(InferTypeName::next_delay(*delay)))
+                .take(7)
+                .collect::<Vec<_>>();
+
+        assert_eq!(delays, vec![3, 6, 12, 24, 48, 60, 60]);
+    }
 }
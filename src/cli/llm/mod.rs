@@ -5,4 +5,4 @@ use error::Result;
 pub use infer_type_name::InferTypeName;
 mod wizard;
 
-pub use wizard::Wizard;
+pub use wizard::{Wizard, WizardOptions};
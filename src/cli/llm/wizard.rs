@@ -1,21 +1,80 @@
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::Arc;
+
 use derive_setters::Setters;
 use genai::adapter::AdapterKind;
-use genai::chat::{ChatOptions, ChatRequest, ChatResponse};
+use genai::chat::{ChatOptions, ChatRequest, ChatResponse, MessageContent};
 use genai::resolver::AuthResolver;
 use genai::Client;
+use serde::Serialize;
+use tailcall_hasher::TailcallHasher;
 
 use super::Result;
 
+/// Parameters of the model used to answer the questions. The unset ones are
+/// left to the defaults of the provider.
+#[derive(Setters, Clone, Debug, PartialEq)]
+pub struct WizardOptions {
+    pub temperature: f64,
+    #[setters(strip_option)]
+    pub max_tokens: Option<u32>,
+    #[setters(strip_option)]
+    pub top_p: Option<f64>,
+}
+
+impl Default for WizardOptions {
+    /// Low temperature keeps the generation reproducible between the runs.
+    fn default() -> Self {
+        Self { temperature: 0.0, max_tokens: None, top_p: None }
+    }
+}
+
+impl From<WizardOptions> for ChatOptions {
+    fn from(options: WizardOptions) -> Self {
+        let mut chat_options = ChatOptions::default()
+            .with_json_mode(true)
+            .with_temperature(options.temperature);
+        if let Some(max_tokens) = options.max_tokens {
+            chat_options = chat_options.with_max_tokens(max_tokens);
+        }
+        if let Some(top_p) = options.top_p {
+            chat_options = chat_options.with_top_p(top_p);
+        }
+        chat_options
+    }
+}
+
+/// Answers the questions asked by the [`Wizard`].
+#[async_trait::async_trait]
+pub trait Responder: Send + Sync {
+    async fn respond(&self, model: &str, request: ChatRequest) -> Result<ChatResponse>;
+}
+
+#[async_trait::async_trait]
+impl Responder for Client {
+    async fn respond(&self, model: &str, request: ChatRequest) -> Result<ChatResponse> {
+        Ok(self.exec_chat(model, request, None).await?)
+    }
+}
+
 #[derive(Setters, Clone)]
 pub struct Wizard<Q, A> {
-    client: Client,
+    responder: Arc<dyn Responder>,
     model: String,
+    /// Directory where the answers are cached by the question, so that
+    /// identical questions don't query the model again.
+    #[setters(strip_option)]
+    cache_dir: Option<PathBuf>,
+    /// Ignores the cached answers and always queries the model. The answers
+    /// are still written to the cache.
+    bypass_cache: bool,
     _q: std::marker::PhantomData<Q>,
     _a: std::marker::PhantomData<A>,
 }
 
 impl<Q, A> Wizard<Q, A> {
-    pub fn new(model: String, secret: Option<String>) -> Self {
+    pub fn new(model: String, secret: Option<String>, options: WizardOptions) -> Self {
         let mut config = genai::adapter::AdapterConfig::default();
         if let Some(key) = secret {
             config = config.with_auth_resolver(AuthResolver::from_key_value(key));
@@ -23,16 +82,16 @@ impl<Q, A> Wizard<Q, A> {
 
         let adapter = AdapterKind::from_model(model.as_str()).unwrap_or(AdapterKind::Ollama);
 
-        let chat_options = ChatOptions::default()
-            .with_json_mode(true)
-            .with_temperature(0.0);
-
         Self {
-            client: Client::builder()
-                .with_chat_options(chat_options)
-                .insert_adapter_config(adapter, config)
-                .build(),
+            responder: Arc::new(
+                Client::builder()
+                    .with_chat_options(options.into())
+                    .insert_adapter_config(adapter, config)
+                    .build(),
+            ),
             model,
+            cache_dir: None,
+            bypass_cache: false,
             _q: Default::default(),
             _a: Default::default(),
         }
@@ -40,13 +99,191 @@ impl<Q, A> Wizard<Q, A> {
 
     pub async fn ask(&self, q: Q) -> Result<A>
     where
-        Q: TryInto<ChatRequest, Error = super::Error>,
+        Q: Serialize + TryInto<ChatRequest, Error = super::Error>,
         A: TryFrom<ChatResponse, Error = super::Error>,
     {
+        let cache_path = self.cache_path(&q)?;
+
+        if let Some(path) = cache_path.as_ref().filter(|_| !self.bypass_cache) {
+            if let Ok(text) = tokio::fs::read_to_string(path).await {
+                let response = ChatResponse {
+                    content: Some(MessageContent::Text(text)),
+                    ..Default::default()
+                };
+                // an unreadable answer is asked again and overwritten
+                if let Ok(answer) = A::try_from(response) {
+                    return Ok(answer);
+                }
+            }
+        }
+
         let response = self
-            .client
-            .exec_chat(self.model.as_str(), q.try_into()?, None)
+            .responder
+            .respond(self.model.as_str(), q.try_into()?)
             .await?;
-        A::try_from(response)
+
+        let text = response
+            .content
+            .as_ref()
+            .and_then(|c| c.text_as_str())
+            .map(|text| text.to_owned());
+        let answer = A::try_from(response)?;
+
+        // only the answers that could be read are cached
+        if let Some((path, text)) = cache_path.zip(text) {
+            if let Some(dir) = path.parent() {
+                tokio::fs::create_dir_all(dir).await?;
+            }
+            tokio::fs::write(path, text).await?;
+        }
+
+        Ok(answer)
+    }
+
+    /// Path of the cached answer, addressed by the hash of the question and
+    /// the model.
+    fn cache_path(&self, q: &Q) -> Result<Option<PathBuf>>
+    where
+        Q: Serialize,
+    {
+        let Some(dir) = self.cache_dir.as_ref() else {
+            return Ok(None);
+        };
+
+        let mut hasher = TailcallHasher::default();
+        self.model.hash(&mut hasher);
+        serde_json::to_string(q)?.hash(&mut hasher);
+
+        Ok(Some(dir.join(format!("{:016x}.json", hasher.finish()))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use genai::chat::{ChatOptions, ChatRequest, ChatResponse, MessageContent};
+    use serde::Serialize;
+
+    use super::{Responder, Wizard, WizardOptions};
+    use crate::cli::llm::{Error, Result};
+
+    #[derive(Serialize)]
+    struct Question(String);
+
+    impl TryInto<genai::chat::ChatRequest> for Question {
+        type Error = Error;
+
+        fn try_into(self) -> Result<genai::chat::ChatRequest> {
+            Ok(genai::chat::ChatRequest::new(vec![
+                genai::chat::ChatMessage::user(self.0),
+            ]))
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Answer(String);
+
+    impl TryFrom<ChatResponse> for Answer {
+        type Error = Error;
+
+        fn try_from(response: ChatResponse) -> Result<Self> {
+            let content = response.content.ok_or(Error::EmptyResponse)?;
+            let text = content.text_as_str().ok_or(Error::EmptyResponse)?;
+            Ok(Answer(serde_json::from_str(text)?))
+        }
+    }
+
+    #[test]
+    fn test_chat_options() {
+        let options: ChatOptions = WizardOptions::default()
+            .temperature(0.2)
+            .max_tokens(256)
+            .into();
+
+        assert_eq!(options.temperature, Some(0.2));
+        assert_eq!(options.max_tokens, Some(256));
+        assert_eq!(options.top_p, None);
+        assert_eq!(options.json_mode, Some(true));
+    }
+
+    #[test]
+    fn test_default_chat_options() {
+        let options: ChatOptions = WizardOptions::default().into();
+
+        assert_eq!(options.temperature, Some(0.0));
+        assert_eq!(options.max_tokens, None);
+        assert_eq!(options.top_p, None);
+    }
+
+    /// Answers every question with the same text and counts the questions.
+    #[derive(Default)]
+    struct StubResponder {
+        text: String,
+        hits: AtomicUsize,
+    }
+
+    impl StubResponder {
+        fn new(text: &str) -> Self {
+            Self { text: text.to_string(), ..Default::default() }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Responder for StubResponder {
+        async fn respond(&self, _: &str, _: ChatRequest) -> Result<ChatResponse> {
+            self.hits.fetch_add(1, Ordering::SeqCst);
+            Ok(ChatResponse {
+                content: Some(MessageContent::Text(self.text.clone())),
+                ..Default::default()
+            })
+        }
+    }
+
+    fn wizard(responder: Arc<StubResponder>, dir: &Path) -> Wizard<Question, Answer> {
+        Wizard::new("stub-model".to_string(), None, WizardOptions::default())
+            .responder(responder)
+            .cache_dir(dir.to_path_buf())
+    }
+
+    #[tokio::test]
+    async fn test_cached_answer() {
+        let dir = tempfile::tempdir().unwrap();
+        let responder = Arc::new(StubResponder::new(r#""Cached""#));
+        let wizard = wizard(responder.clone(), dir.path());
+
+        let question = || Question("What is the name?".to_string());
+        let answer = wizard.ask(question()).await.unwrap();
+        assert_eq!(answer, Answer("Cached".to_string()));
+
+        // the identical question is answered by the cache
+        let answer = wizard.ask(question()).await.unwrap();
+        assert_eq!(answer, Answer("Cached".to_string()));
+        assert_eq!(responder.hits.load(Ordering::SeqCst), 1);
+
+        let other = Question("What is the age?".to_string());
+        wizard.ask(other).await.unwrap();
+        assert_eq!(responder.hits.load(Ordering::SeqCst), 2);
+
+        // the bypass always asks the model
+        let wizard = wizard.bypass_cache(true);
+        wizard.ask(question()).await.unwrap();
+        assert_eq!(responder.hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_answer_not_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let responder = Arc::new(StubResponder::new("not json"));
+        let wizard = wizard(responder.clone(), dir.path());
+
+        let question = || Question("What is the name?".to_string());
+        assert!(wizard.ask(question()).await.is_err());
+        assert!(wizard.ask(question()).await.is_err());
+
+        assert_eq!(responder.hits.load(Ordering::SeqCst), 2);
+        assert!(!wizard.cache_path(&question()).unwrap().unwrap().exists());
     }
 }
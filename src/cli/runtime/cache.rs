@@ -0,0 +1,102 @@
+use std::num::NonZeroU64;
+
+use async_graphql_value::ConstValue;
+use redis::aio::ConnectionManager;
+use redis::{AsyncCommands, Cmd};
+use tokio::sync::OnceCell;
+
+use crate::core::ir::model::IoId;
+use crate::core::{cache, Cache};
+
+/// Cache backed by Redis, so that replicas of the server can share the
+/// results of cached IO.
+pub struct RedisCache {
+    client: redis::Client,
+    connection: OnceCell<ConnectionManager>,
+}
+
+impl RedisCache {
+    pub fn init(url: &str) -> Result<Self, cache::Error> {
+        let client = redis::Client::open(url).map_err(|e| cache::Error::Kv(e.to_string()))?;
+        Ok(Self { client, connection: OnceCell::new() })
+    }
+
+    async fn connection(&self) -> Result<ConnectionManager, cache::Error> {
+        self.connection
+            .get_or_try_init(|| ConnectionManager::new(self.client.clone()))
+            .await
+            .cloned()
+            .map_err(|e| cache::Error::Kv(e.to_string()))
+    }
+
+    fn key(key: &IoId) -> String {
+        format!("tailcall:{}", key.as_u64())
+    }
+
+    /// `ttl` is in milliseconds, same as `@cache(maxAge)`, hence `PSETEX`
+    /// instead of `SETEX`.
+    fn set_cmd(key: &IoId, value: String, ttl: NonZeroU64) -> Cmd {
+        Cmd::pset_ex(Self::key(key), value, ttl.get())
+    }
+}
+
+#[async_trait::async_trait]
+impl Cache for RedisCache {
+    type Key = IoId;
+    type Value = ConstValue;
+
+    async fn set<'a>(
+        &'a self,
+        key: IoId,
+        value: ConstValue,
+        ttl: NonZeroU64,
+    ) -> Result<(), cache::Error> {
+        let value = serde_json::to_string(&value)?;
+        let mut connection = self.connection().await?;
+        Self::set_cmd(&key, value, ttl)
+            .query_async::<_, ()>(&mut connection)
+            .await
+            .map_err(|e| cache::Error::Kv(e.to_string()))
+    }
+
+    async fn get<'a>(&'a self, key: &'a IoId) -> Result<Option<Self::Value>, cache::Error> {
+        let value: Option<String> = self
+            .connection()
+            .await?
+            .get(Self::key(key))
+            .await
+            .map_err(|e| cache::Error::Kv(e.to_string()))?;
+
+        Ok(value
+            .map(|value| serde_json::from_str(&value))
+            .transpose()?)
+    }
+
+    fn hit_rate(&self) -> Option<f64> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use super::RedisCache;
+    use crate::core::ir::model::IoId;
+
+    #[test]
+    fn test_set_ttl_is_in_milliseconds() {
+        let cmd = RedisCache::set_cmd(
+            &IoId::new(1),
+            "\"value\"".to_string(),
+            NonZeroU64::new(3000).unwrap(),
+        );
+        let expected = redis::cmd("PSETEX")
+            .arg("tailcall:1")
+            .arg(3000u64)
+            .arg("\"value\"")
+            .get_packed_command();
+
+        assert_eq!(cmd.get_packed_command(), expected);
+    }
+}
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use anyhow::Result;
+use futures_util::StreamExt;
 use http_cache_reqwest::{Cache, CacheMode, HttpCache, HttpCacheOptions};
 use hyper::body::Bytes;
 use once_cell::sync::Lazy;
@@ -20,6 +21,11 @@ use super::HttpIO;
 use crate::core::blueprint::telemetry::Telemetry;
 use crate::core::blueprint::Upstream;
 use crate::core::http::Response;
+use crate::core::ByteStream;
+
+/// Upper bound of the decompressed body of the upstream response, guards
+/// against decompression bombs.
+const MAX_DECOMPRESSED_BYTES: usize = 100 * 1024 * 1024;
 
 static HTTP_CLIENT_REQUEST_COUNT: Lazy<Counter<u64>> = Lazy::new(|| {
     let meter = opentelemetry::global::meter("http_request");
@@ -126,24 +132,9 @@ impl NativeHttp {
             enable_telemetry: telemetry.export.is_some(),
         }
     }
-}
 
-#[async_trait::async_trait]
-impl HttpIO for NativeHttp {
-    #[allow(clippy::blocks_in_conditions)]
-    // because of the issue with tracing and clippy - https://github.com/rust-lang/rust-clippy/issues/12281
-    #[tracing::instrument(
-        skip_all,
-        err,
-        fields(
-            otel.name = "upstream_request",
-            otel.kind = ?SpanKind::Client,
-            url.full = %request.url(),
-            http.request.method = %request.method(),
-            network.protocol.version = ?request.version()
-        )
-    )]
-    async fn execute(&self, mut request: reqwest::Request) -> Result<Response<Bytes>> {
+    /// Sends the request and fails if the response status is an error.
+    async fn send(&self, mut request: reqwest::Request) -> Result<reqwest::Response> {
         if self.http2_only {
             *request.version_mut() = reqwest::Version::HTTP_2;
         }
@@ -176,15 +167,76 @@ impl HttpIO for NativeHttp {
             tracing::Span::current().set_attribute(status_code.key, status_code.value);
         }
 
-        Ok(Response::from_reqwest(
-            response?
-                .error_for_status()
-                .map_err(|err| err.without_url())?,
+        Ok(response?
+            .error_for_status()
+            .map_err(|err| err.without_url())?)
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpIO for NativeHttp {
+    #[allow(clippy::blocks_in_conditions)]
+    // because of the issue with tracing and clippy - https://github.com/rust-lang/rust-clippy/issues/12281
+    #[tracing::instrument(
+        skip_all,
+        err,
+        fields(
+            otel.name = "upstream_request",
+            otel.kind = ?SpanKind::Client,
+            url.full = %request.url(),
+            http.request.method = %request.method(),
+            network.protocol.version = ?request.version()
+        )
+    )]
+    async fn execute(&self, request: reqwest::Request) -> Result<Response<Bytes>> {
+        // reqwest is built without the decoders, so the body is decompressed here
+        Response::from_reqwest(self.send(request).await?)
+            .await?
+            .decompress(MAX_DECOMPRESSED_BYTES)
+    }
+
+    #[allow(clippy::blocks_in_conditions)]
+    #[tracing::instrument(
+        skip_all,
+        err,
+        fields(
+            otel.name = "upstream_request",
+            otel.kind = ?SpanKind::Client,
+            url.full = %request.url(),
+            http.request.method = %request.method(),
+            network.protocol.version = ?request.version()
         )
-        .await?)
+    )]
+    async fn execute_stream(&self, request: reqwest::Request) -> Result<ByteStream> {
+        let response = self.send(request).await?;
+
+        // the body is passed on as it's received, so unlike `execute` it can't be
+        // decompressed within the limit
+        if let Some(encoding) = response.headers().get(reqwest::header::CONTENT_ENCODING) {
+            let encoding = encoding.to_str()?.trim();
+            if !encoding.is_empty() && !encoding.eq_ignore_ascii_case("identity") {
+                anyhow::bail!("Streaming a {} encoded response is not supported", encoding);
+            }
+        }
+
+        Ok(body_stream(response))
     }
 }
 
+/// Streams the body of the response chunk by chunk as it's received.
+pub fn body_stream(response: reqwest::Response) -> ByteStream {
+    futures_util::stream::unfold(Some(response), |response| async move {
+        let mut response = response?;
+        match response.chunk().await {
+            Ok(Some(chunk)) => Some((Ok(chunk), Some(response))),
+            Ok(None) => None,
+            // stop the stream after the first error
+            Err(err) => Some((Err(err.into()), None)),
+        }
+    })
+    .boxed()
+}
+
 #[cfg(test)]
 mod tests {
     use reqwest::Method;
@@ -282,4 +334,52 @@ mod tests {
         let resp = make_request(&url1, &native_http).await;
         assert_eq!(resp.headers.get("x-cache-lookup").unwrap(), "MISS");
     }
+
+    #[tokio::test]
+    async fn test_native_http_execute_stream() {
+        let server = start_mock_server();
+        let body = "x".repeat(1024 * 1024);
+
+        server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/large");
+            then.status(200).body(&body);
+        });
+
+        let native_http = NativeHttp::init(&Default::default(), &Default::default());
+        let request_url = format!("http://localhost:{}/large", server.port());
+        let request = reqwest::Request::new(Method::GET, request_url.parse().unwrap());
+        let mut stream = native_http.execute_stream(request).await.unwrap();
+
+        let mut chunks = 0;
+        let mut received = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            chunks += 1;
+            received.extend_from_slice(&chunk.unwrap());
+        }
+
+        assert!(chunks > 1);
+        assert_eq!(received, body.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_native_http_execute_stream_compressed() {
+        let server = start_mock_server();
+
+        server.mock(|when, then| {
+            when.method(httpmock::Method::GET).path("/compressed");
+            then.status(200)
+                .header("Content-Encoding", "gzip")
+                .body([0x1f, 0x8b, 0x08, 0x00]);
+        });
+
+        let native_http = NativeHttp::init(&Default::default(), &Default::default());
+        let request_url = format!("http://localhost:{}/compressed", server.port());
+        let request = reqwest::Request::new(Method::GET, request_url.parse().unwrap());
+        let error = native_http.execute_stream(request).await.err().unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            "Streaming a gzip encoded response is not supported"
+        );
+    }
 }
@@ -1,3 +1,4 @@
+mod cache;
 mod env;
 mod file;
 mod http;
@@ -6,14 +7,17 @@ use std::fs;
 use std::hash::Hash;
 use std::sync::Arc;
 
-pub use http::NativeHttp;
+use async_graphql_value::ConstValue;
+pub use cache::RedisCache;
+pub use http::{body_stream, NativeHttp};
 use inquire::{Confirm, Select};
 
 use crate::core::blueprint::Blueprint;
 use crate::core::cache::InMemoryCache;
+use crate::core::ir::model::IoId;
 use crate::core::runtime::TargetRuntime;
 use crate::core::worker::{Command, Event};
-use crate::core::{blueprint, EnvIO, FileIO, HttpIO, WorkerIO};
+use crate::core::{blueprint, Cache, EnvIO, FileIO, HttpIO, WorkerIO};
 
 // Provides access to env in native rust environment
 fn init_env() -> Arc<dyn EnvIO> {
@@ -69,6 +73,19 @@ fn init_in_memory_cache<K: Hash + Eq, V: Clone>() -> InMemoryCache<K, V> {
     InMemoryCache::default()
 }
 
+// Uses redis when `@server(redisUrl)` is set, so that the cache is shared
+// between replicas, and falls back to an in-memory cache otherwise
+fn init_cache(blueprint: &Blueprint) -> Arc<dyn Cache<Key = IoId, Value = ConstValue>> {
+    if let Some(url) = &blueprint.server.redis_url {
+        match RedisCache::init(url) {
+            Ok(cache) => return Arc::new(cache),
+            Err(e) => tracing::warn!("Falling back to in-memory cache: {}", e),
+        }
+    }
+
+    Arc::new(init_in_memory_cache())
+}
+
 pub fn init(blueprint: &Blueprint) -> TargetRuntime {
     #[cfg(not(feature = "js"))]
     tracing::warn!("JS capabilities are disabled in this build");
@@ -78,7 +95,7 @@ pub fn init(blueprint: &Blueprint) -> TargetRuntime {
         http2_only: init_http2_only(blueprint),
         env: init_env(),
         file: init_file(),
-        cache: Arc::new(init_in_memory_cache()),
+        cache: init_cache(blueprint),
         extensions: Arc::new(vec![]),
         cmd_worker: init_http_worker_io(blueprint.server.script.clone()),
         worker: init_resolver_worker_io(blueprint.server.script.clone()),
@@ -1,11 +1,12 @@
 use std::sync::Arc;
 
+use hyper::server::conn::AddrStream;
 use hyper::service::{make_service_fn, service_fn};
 use tokio::sync::oneshot;
 
+use super::rate_limit::handle_rate_limited_request;
 use super::server_config::ServerConfig;
 use crate::core::async_graphql_hyper::{GraphQLBatchRequest, GraphQLRequest};
-use crate::core::http::handle_request;
 use crate::core::Errata;
 
 pub async fn start_http_1(
@@ -13,20 +14,22 @@ pub async fn start_http_1(
     server_up_sender: Option<oneshot::Sender<()>>,
 ) -> anyhow::Result<()> {
     let addr = sc.addr();
-    let make_svc_single_req = make_service_fn(|_conn| {
+    let make_svc_single_req = make_service_fn(|conn: &AddrStream| {
         let state = Arc::clone(&sc);
+        let client = conn.remote_addr().ip();
         async move {
             Ok::<_, anyhow::Error>(service_fn(move |req| {
-                handle_request::<GraphQLRequest>(req, state.app_ctx.clone())
+                handle_rate_limited_request::<GraphQLRequest>(req, state.clone(), client)
             }))
         }
     });
 
-    let make_svc_batch_req = make_service_fn(|_conn| {
+    let make_svc_batch_req = make_service_fn(|conn: &AddrStream| {
         let state = Arc::clone(&sc);
+        let client = conn.remote_addr().ip();
         async move {
             Ok::<_, anyhow::Error>(service_fn(move |req| {
-                handle_request::<GraphQLBatchRequest>(req, state.app_ctx.clone())
+                handle_rate_limited_request::<GraphQLBatchRequest>(req, state.clone(), client)
             }))
         }
     });
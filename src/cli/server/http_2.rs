@@ -1,50 +1,94 @@
 #![allow(clippy::too_many_arguments)]
+use std::net::{IpAddr, Ipv4Addr};
 use std::sync::Arc;
 
 use hyper::server::conn::AddrIncoming;
 use hyper::service::{make_service_fn, service_fn};
 use hyper::Server;
+use hyper_rustls::acceptor::TlsStream;
 use hyper_rustls::TlsAcceptor;
 use rustls_pki_types::CertificateDer;
 use tokio::sync::oneshot;
+use tokio_rustls::rustls;
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::RootCertStore;
 
+use super::rate_limit::handle_rate_limited_request;
 use super::server_config::ServerConfig;
 use crate::core::async_graphql_hyper::{GraphQLBatchRequest, GraphQLRequest};
 use crate::core::config::PrivateKey;
-use crate::core::http::handle_request;
 use crate::core::Errata;
 
+/// Builds the TLS config that requires clients to present a certificate
+/// signed by one of the given CAs.
+fn to_mtls_config(
+    cert: Vec<CertificateDer<'static>>,
+    key: PrivateKey,
+    client_ca: Vec<CertificateDer<'static>>,
+) -> anyhow::Result<rustls::ServerConfig> {
+    let mut roots = RootCertStore::empty();
+    for ca in client_ca {
+        roots.add(ca)?;
+    }
+
+    let verifier = WebPkiClientVerifier::builder(Arc::new(roots)).build()?;
+
+    Ok(rustls::ServerConfig::builder()
+        .with_client_cert_verifier(verifier)
+        .with_single_cert(cert, key.into_inner())?)
+}
+
 pub async fn start_http_2(
     sc: Arc<ServerConfig>,
     cert: Vec<CertificateDer<'static>>,
     key: PrivateKey,
+    client_ca: Option<Vec<CertificateDer<'static>>>,
+    alpn_fallback: bool,
     server_up_sender: Option<oneshot::Sender<()>>,
 ) -> anyhow::Result<()> {
     let addr = sc.addr();
     let incoming = AddrIncoming::bind(&addr)?;
-    let acceptor = TlsAcceptor::builder()
-        .with_single_cert(cert, key.into_inner())?
-        .with_http2_alpn()
-        .with_incoming(incoming);
-    let make_svc_single_req = make_service_fn(|_conn| {
+    let acceptor = match client_ca {
+        Some(client_ca) => {
+            TlsAcceptor::builder().with_tls_config(to_mtls_config(cert, key, client_ca)?)
+        }
+        None => TlsAcceptor::builder().with_single_cert(cert, key.into_inner())?,
+    };
+    // with the fallback, clients negotiating `http/1.1` are served on the same
+    // port
+    let acceptor = if alpn_fallback {
+        acceptor.with_all_versions_alpn()
+    } else {
+        acceptor.with_http2_alpn()
+    }
+    .with_incoming(incoming);
+    let make_svc_single_req = make_service_fn(|conn: &TlsStream| {
         let state = Arc::clone(&sc);
+        let client = conn
+            .io()
+            .map(|io| io.remote_addr().ip())
+            .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
         async move {
             Ok::<_, anyhow::Error>(service_fn(move |req| {
-                handle_request::<GraphQLRequest>(req, state.app_ctx.clone())
+                handle_rate_limited_request::<GraphQLRequest>(req, state.clone(), client)
             }))
         }
     });
 
-    let make_svc_batch_req = make_service_fn(|_conn| {
+    let make_svc_batch_req = make_service_fn(|conn: &TlsStream| {
         let state = Arc::clone(&sc);
+        let client = conn
+            .io()
+            .map(|io| io.remote_addr().ip())
+            .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
         async move {
             Ok::<_, anyhow::Error>(service_fn(move |req| {
-                handle_request::<GraphQLBatchRequest>(req, state.app_ctx.clone())
+                handle_rate_limited_request::<GraphQLBatchRequest>(req, state.clone(), client)
             }))
         }
     });
 
-    let builder = Server::builder(acceptor).http2_only(true);
+    let builder = Server::builder(acceptor).http2_only(!alpn_fallback);
 
     super::log_launch(sc.as_ref());
 
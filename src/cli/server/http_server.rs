@@ -39,8 +39,16 @@ impl Server {
         init_opentelemetry(blueprint.telemetry.clone(), &server_config.app_ctx.runtime)?;
 
         match blueprint.server.http.clone() {
-            Http::HTTP2 { cert, key } => {
-                start_http_2(server_config, cert, key, self.server_up_sender).await
+            Http::HTTP2 { cert, key, client_ca, alpn_fallback } => {
+                start_http_2(
+                    server_config,
+                    cert,
+                    key,
+                    client_ca,
+                    alpn_fallback,
+                    self.server_up_sender,
+                )
+                .await
             }
             Http::HTTP1 => start_http_1(server_config, self.server_up_sender).await,
         }
@@ -2,6 +2,7 @@ pub mod http_1;
 pub mod http_2;
 pub mod http_server;
 pub mod playground;
+pub mod rate_limit;
 pub mod server_config;
 
 pub use http_server::Server;
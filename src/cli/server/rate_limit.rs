@@ -0,0 +1,115 @@
+use std::net::IpAddr;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use hyper::{Body, Request, Response, StatusCode};
+use lru::LruCache;
+use serde::de::DeserializeOwned;
+
+use super::server_config::ServerConfig;
+use crate::core::async_graphql_hyper::GraphQLRequestLike;
+use crate::core::blueprint::RateLimit;
+use crate::core::http::handle_request;
+
+/// Maximum number of clients whose buckets are tracked. Once exceeded, the
+/// least recently seen clients start over with a full bucket.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Enforces the `rateLimit` of `@server` with a token bucket per client IP.
+/// A bucket holds up to `requests + burst` tokens and is refilled with
+/// `requests` tokens per window.
+pub struct RateLimiter {
+    rate_limit: RateLimit,
+    buckets: Mutex<LruCache<IpAddr, Bucket>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(rate_limit: RateLimit) -> Self {
+        Self {
+            rate_limit,
+            buckets: Mutex::new(LruCache::new(
+                NonZeroUsize::new(MAX_TRACKED_CLIENTS).unwrap(),
+            )),
+        }
+    }
+
+    /// Takes a token from the bucket of the client and returns `false` if the
+    /// client has exceeded the rate limit.
+    pub fn check(&self, client: IpAddr) -> bool {
+        self.check_at(client, Instant::now())
+    }
+
+    fn check_at(&self, client: IpAddr, now: Instant) -> bool {
+        let capacity = (self.rate_limit.requests + self.rate_limit.burst) as f64;
+        let refill_rate = self.rate_limit.requests as f64 / self.rate_limit.window.as_secs_f64();
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket =
+            buckets.get_or_insert_mut(client, || Bucket { tokens: capacity, updated_at: now });
+
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * refill_rate).min(capacity);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Handles the request unless the client has exceeded the rate limit, in
+/// which case `429 Too Many Requests` is returned.
+pub async fn handle_rate_limited_request<T: DeserializeOwned + GraphQLRequestLike>(
+    req: Request<Body>,
+    sc: Arc<ServerConfig>,
+    client: IpAddr,
+) -> anyhow::Result<Response<Body>> {
+    if let Some(rate_limiter) = &sc.rate_limiter {
+        if !rate_limiter.check(client) {
+            return Ok(Response::builder()
+                .status(StatusCode::TOO_MANY_REQUESTS)
+                .body(Body::empty())?);
+        }
+    }
+
+    handle_request::<T>(req, sc.app_ctx.clone()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_rate_limit() {
+        let rate_limiter =
+            RateLimiter::new(RateLimit { requests: 2, window: Duration::from_secs(1), burst: 1 });
+        let client = IpAddr::from([127, 0, 0, 1]);
+        let other = IpAddr::from([127, 0, 0, 2]);
+        let now = Instant::now();
+
+        // the burst is allowed on top of the requests
+        assert!(rate_limiter.check_at(client, now));
+        assert!(rate_limiter.check_at(client, now));
+        assert!(rate_limiter.check_at(client, now));
+        assert!(!rate_limiter.check_at(client, now));
+
+        // other clients have their own bucket
+        assert!(rate_limiter.check_at(other, now));
+
+        // half of the window refills a single token
+        let now = now + Duration::from_millis(500);
+        assert!(rate_limiter.check_at(client, now));
+        assert!(!rate_limiter.check_at(client, now));
+    }
+}
@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use async_graphql_extension_apollo_tracing::ApolloTracing;
 
+use super::rate_limit::RateLimiter;
 use crate::cli::runtime::init;
 use crate::core::app_context::AppContext;
 use crate::core::blueprint::telemetry::TelemetryExporter;
@@ -13,6 +14,7 @@ use crate::core::schema_extension::SchemaExtension;
 pub struct ServerConfig {
     pub blueprint: Blueprint,
     pub app_ctx: Arc<AppContext>,
+    pub rate_limiter: Option<Arc<RateLimiter>>,
 }
 
 impl ServerConfig {
@@ -39,7 +41,13 @@ impl ServerConfig {
         let endpoints = endpoints.into_checked(&blueprint, rt.clone()).await?;
         let app_context = Arc::new(AppContext::new(blueprint.clone(), rt, endpoints));
 
-        Ok(Self { app_ctx: app_context, blueprint })
+        let rate_limiter = blueprint
+            .server
+            .rate_limit
+            .clone()
+            .map(|rate_limit| Arc::new(RateLimiter::new(rate_limit)));
+
+        Ok(Self { app_ctx: app_context, blueprint, rate_limiter })
     }
 
     pub fn addr(&self) -> SocketAddr {
@@ -48,7 +56,7 @@ impl ServerConfig {
 
     pub fn http_version(&self) -> String {
         match self.blueprint.server.http {
-            Http::HTTP2 { cert: _, key: _ } => "HTTP/2".to_string(),
+            Http::HTTP2 { .. } => "HTTP/2".to_string(),
             _ => "HTTP/1.1".to_string(),
         }
     }
@@ -1,18 +1,18 @@
 use std::sync::Arc;
 
 use async_graphql::dynamic::{self, DynamicRequest};
-use async_graphql_value::ConstValue;
 use dashmap::DashMap;
 
 use super::jit::AnyResponse;
 use crate::core::async_graphql_hyper::OperationId;
+use crate::core::auth::jwt::jwks_refresh::RefreshableJwks;
 use crate::core::blueprint::{Blueprint, Definition, SchemaModifiers};
 use crate::core::data_loader::{DataLoader, DedupeResult};
 use crate::core::graphql::GraphqlDataLoader;
 use crate::core::grpc;
 use crate::core::grpc::data_loader::GrpcDataLoader;
 use crate::core::http::{DataLoaderRequest, HttpDataLoader};
-use crate::core::ir::model::{DataLoaderId, IoId, IO, IR};
+use crate::core::ir::model::{DataLoaderId, IoId, IoResult, IO, IR};
 use crate::core::ir::Error;
 use crate::core::jit::{OPHash, OperationPlan};
 use crate::core::rest::{Checked, EndpointSet};
@@ -26,10 +26,11 @@ pub struct AppContext {
     pub gql_data_loaders: Arc<Vec<DataLoader<DataLoaderRequest, GraphqlDataLoader>>>,
     pub grpc_data_loaders: Arc<Vec<DataLoader<grpc::DataLoaderRequest, GrpcDataLoader>>>,
     pub endpoints: EndpointSet<Checked>,
-    pub dedupe_handler: Arc<DedupeResult<IoId, ConstValue, Error>>,
+    pub dedupe_handler: Arc<DedupeResult<IoId, IoResult, Error>>,
     pub dedupe_operation_handler: DedupeResult<OperationId, AnyResponse<Vec<u8>>, Error>,
     pub operation_plans: DashMap<OPHash, OperationPlan<async_graphql_value::Value>>,
     pub const_execution_cache: DashMap<OPHash, AnyResponse<Vec<u8>>>,
+    pub jwks: Arc<DashMap<String, Arc<RefreshableJwks>>>,
 }
 
 impl AppContext {
@@ -50,7 +51,14 @@ impl AppContext {
                         expr.modify(&mut |expr| match expr {
                             IR::IO(io) => match io {
                                 IO::Http {
-                                    req_template, group_by, is_list, dedupe, hook, ..
+                                    req_template,
+                                    group_by,
+                                    is_list,
+                                    dedupe,
+                                    hook,
+                                    response_transform,
+                                    capture_headers,
+                                    ..
                                 } => {
                                     let is_list = *is_list;
                                     let dedupe = *dedupe;
@@ -58,6 +66,7 @@ impl AppContext {
                                         runtime.clone(),
                                         group_by.clone(),
                                         is_list,
+                                        response_transform.clone(),
                                     )
                                     .to_data_loader(upstream_batch.clone().unwrap_or_default());
 
@@ -68,6 +77,8 @@ impl AppContext {
                                         hook: hook.clone(),
                                         is_list,
                                         dedupe,
+                                        response_transform: response_transform.clone(),
+                                        capture_headers: capture_headers.clone(),
                                     }));
 
                                     http_data_loaders.push(data_loader);
@@ -119,8 +130,8 @@ impl AppContext {
 
                                     result
                                 }
-                                IO::Js { name: method } => {
-                                    Some(IR::IO(IO::Js { name: method.clone() }))
+                                IO::Js { name: method, dedupe } => {
+                                    Some(IR::IO(IO::Js { name: method.clone(), dedupe: *dedupe }))
                                 }
                             },
                             _ => None,
@@ -146,6 +157,7 @@ impl AppContext {
             dedupe_operation_handler: DedupeResult::new(false),
             operation_plans: DashMap::new(),
             const_execution_cache: DashMap::default(),
+            jwks: Arc::new(DashMap::default()),
         }
     }
 
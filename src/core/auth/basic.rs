@@ -1,3 +1,4 @@
+use anyhow::{bail, Context};
 use headers::authorization::Basic;
 use headers::{Authorization, HeaderMapExt};
 use htpasswd_verify::Htpasswd;
@@ -41,6 +42,54 @@ impl BasicVerifier {
     }
 }
 
+/// Prefixes of the bcrypt hashes, followed by the two digit cost.
+const BCRYPT_PREFIXES: [&str; 3] = ["$2a$", "$2b$", "$2y$"];
+
+/// Checks if the hash is a DES crypt hash, i.e. 13 characters of the crypt
+/// alphabet without any prefix.
+fn is_des_crypt(hash: &str) -> bool {
+    hash.len() == 13
+        && hash
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || c == b'.' || c == b'/')
+}
+
+/// Checks that every entry of the htpasswd file is hashed with a scheme the
+/// verifier supports, i.e. bcrypt, apr1, SHA-1 or DES crypt. Entries with
+/// plaintext or unknown hashes are rejected, since they would never match at
+/// auth time.
+pub fn validate_htpasswd(htpasswd: &str) -> anyhow::Result<()> {
+    for (index, line) in htpasswd.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line_number = index + 1;
+        let (username, hash) = line.split_once(':').with_context(|| {
+            format!("Invalid htpasswd entry on line {line_number}: expected `username:hash`")
+        })?;
+
+        if let Some(prefix) = BCRYPT_PREFIXES.iter().find(|p| hash.starts_with(*p)) {
+            let cost = hash[prefix.len()..]
+                .split_once('$')
+                .and_then(|(cost, _)| cost.parse::<u32>().ok());
+
+            if !cost.is_some_and(|cost| (4..=31).contains(&cost)) {
+                bail!(
+                    "Invalid bcrypt cost for user '{username}' on line {line_number}: expected a cost between 4 and 31"
+                );
+            }
+        } else if !hash.starts_with("$apr1$") && !hash.starts_with("{SHA}") && !is_des_crypt(hash) {
+            bail!(
+                "Unsupported password hash for user '{username}' on line {line_number}: only bcrypt, apr1, SHA and crypt hashes are supported"
+            );
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 pub mod tests {
     use http::header::HeaderValue;
@@ -127,6 +176,51 @@ testuser3:{SHA}Y2fEjdGT1W6nsLqtJbGUVeUp9e4=
         assert_eq!(validation, Verification::fail(Error::Invalid));
     }
 
+    #[test]
+    fn validate_htpasswd_supported_schemes() {
+        assert!(validate_htpasswd(HTPASSWD_TEST).is_ok());
+    }
+
+    #[test]
+    fn validate_htpasswd_bcrypt() {
+        let htpasswd = "testuser2:$2y$10$wJ/mZDURcAOBIrswCAKFsO0Nk7BpHmWl/XuhF7lNm3gBAFH3ofsuu";
+        assert!(validate_htpasswd(htpasswd).is_ok());
+
+        let htpasswd = "testuser2:$2y$99$wJ/mZDURcAOBIrswCAKFsO0Nk7BpHmWl/XuhF7lNm3gBAFH3ofsuu";
+        let actual = validate_htpasswd(htpasswd).unwrap_err().to_string();
+        assert_eq!(
+            actual,
+            "Invalid bcrypt cost for user 'testuser2' on line 1: expected a cost between 4 and 31"
+        );
+    }
+
+    #[test]
+    fn validate_htpasswd_plaintext() {
+        let htpasswd = "# users\ntestuser1:password123";
+        let actual = validate_htpasswd(htpasswd).unwrap_err().to_string();
+        assert_eq!(
+            actual,
+            "Unsupported password hash for user 'testuser1' on line 2: only bcrypt, apr1, SHA and crypt hashes are supported"
+        );
+    }
+
+    #[test]
+    fn validate_htpasswd_crypt() {
+        let htpasswd = "testuser4:ab3z4hnHA5WdU";
+        assert!(validate_htpasswd(htpasswd).is_ok());
+    }
+
+    #[tokio::test]
+    async fn verify_correct_password_crypt() {
+        // testuser4:abc123
+        let provider =
+            BasicVerifier::new(blueprint::Basic { htpasswd: "testuser4:ab3z4hnHA5WdU".to_owned() });
+        let validation = provider
+            .verify(&create_basic_auth_request("testuser4", "abc123"))
+            .await;
+        assert_eq!(validation, Verification::succeed());
+    }
+
     // Helper function for setting up the provider
     fn setup_provider() -> BasicVerifier {
         BasicVerifier::new(blueprint::Basic { htpasswd: HTPASSWD_TEST.to_owned() })
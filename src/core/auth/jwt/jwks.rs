@@ -8,7 +8,7 @@ use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
 use super::jwt_verify::JwtClaim;
 use crate::core::auth::error::Error;
 
-#[derive(Setters)]
+#[derive(Clone, Setters)]
 pub struct Jwks {
     pub set: JwkSet,
     pub optional_kid: bool,
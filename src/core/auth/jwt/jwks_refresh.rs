@@ -0,0 +1,290 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use url::Url;
+use web_time::Instant;
+
+use crate::core::data_loader::DedupeResult;
+use crate::core::runtime::TargetRuntime;
+
+/// Fetches the latest published version of a JWKS.
+#[async_trait::async_trait]
+pub trait JwksFetcher: Send + Sync {
+    async fn fetch(&self) -> Result<JwkSet>;
+}
+
+/// Fetches the JWKS from the HTTP endpoint of the identity provider.
+pub struct HttpJwksFetcher {
+    url: Url,
+    runtime: TargetRuntime,
+}
+
+impl HttpJwksFetcher {
+    pub fn new(url: Url, runtime: TargetRuntime) -> Self {
+        Self { url, runtime }
+    }
+}
+
+#[async_trait::async_trait]
+impl JwksFetcher for HttpJwksFetcher {
+    async fn fetch(&self) -> Result<JwkSet> {
+        let request = reqwest::Request::new(reqwest::Method::GET, self.url.clone());
+        let response = self.runtime.http.execute(request).await?;
+
+        Ok(serde_json::from_slice(&response.body)?)
+    }
+}
+
+/// JWKS that is fetched again periodically, so keys rotated by the identity
+/// provider are picked up without a restart. Keys are merged by `kid`: new
+/// keys are resolvable right after the refresh, while keys that are no longer
+/// published are kept for the grace period so the tokens signed before the
+/// rotation stay valid.
+pub struct RefreshableJwks {
+    fetcher: Arc<dyn JwksFetcher>,
+    refresh_interval: Duration,
+    grace_period: Duration,
+    state: Mutex<State>,
+    /// Shares a stale refresh with the concurrent requests, so only one fetch
+    /// runs at a time.
+    pending: DedupeResult<(), (), String>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Known keys along with the time they were last published.
+    keys: Vec<(Jwk, Instant)>,
+    /// Time of the last refresh, whether it succeeded or not, so a failing
+    /// identity provider is retried once per refresh interval.
+    attempted_at: Option<Instant>,
+}
+
+/// Checks if both keys refer to the same `kid`. Keys without a `kid` can only
+/// be matched by their content.
+fn is_same_key(left: &Jwk, right: &Jwk) -> bool {
+    match (&left.common.key_id, &right.common.key_id) {
+        (Some(left), Some(right)) => left == right,
+        _ => left == right,
+    }
+}
+
+impl RefreshableJwks {
+    pub fn new(
+        fetcher: Arc<dyn JwksFetcher>,
+        refresh_interval: Duration,
+        grace_period: Duration,
+    ) -> Self {
+        Self {
+            fetcher,
+            refresh_interval,
+            grace_period,
+            state: Mutex::new(State::default()),
+            pending: DedupeResult::new(false),
+        }
+    }
+
+    /// Starts with the already fetched keys, so the next refresh happens
+    /// only after the refresh interval.
+    pub fn with_keys(mut self, set: JwkSet) -> Self {
+        let now = Instant::now();
+        self.state = Mutex::new(State {
+            keys: set.keys.into_iter().map(|key| (key, now)).collect(),
+            attempted_at: Some(now),
+        });
+
+        self
+    }
+
+    /// Fetches the JWKS, merges it into the known keys by `kid` and drops the
+    /// keys that haven't been published within the grace period.
+    pub async fn refresh(&self) -> Result<()> {
+        let set = self.fetcher.fetch().await;
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        state.attempted_at = Some(now);
+        let set = set?;
+
+        for jwk in set.keys {
+            match state
+                .keys
+                .iter_mut()
+                .find(|(key, _)| is_same_key(key, &jwk))
+            {
+                Some(entry) => *entry = (jwk, now),
+                None => state.keys.push((jwk, now)),
+            }
+        }
+
+        let grace_period = self.grace_period;
+        state
+            .keys
+            .retain(|(_, published_at)| now.duration_since(*published_at) <= grace_period);
+
+        Ok(())
+    }
+
+    fn is_stale(&self) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .attempted_at
+            .map_or(true, |attempted_at| {
+                attempted_at.elapsed() >= self.refresh_interval
+            })
+    }
+
+    /// Refreshes the JWKS if it has never been fetched or the refresh interval
+    /// has elapsed since the last attempt. Concurrent calls wait for the same
+    /// refresh.
+    pub async fn refresh_if_stale(&self) -> Result<()> {
+        if !self.is_stale() {
+            return Ok(());
+        }
+
+        self.pending
+            .dedupe(&(), || async {
+                // a refresh that completed in the meantime makes this one redundant
+                if self.is_stale() {
+                    self.refresh().await.map_err(|e| e.to_string())
+                } else {
+                    Ok(())
+                }
+            })
+            .await
+            .map_err(|e| anyhow!(e))
+    }
+
+    /// Finds the key with the specified `kid` among the known keys.
+    pub fn find(&self, kid: &str) -> Option<Jwk> {
+        self.state
+            .lock()
+            .unwrap()
+            .keys
+            .iter()
+            .find(|(key, _)| key.common.key_id.as_deref() == Some(kid))
+            .map(|(key, _)| key.clone())
+    }
+
+    /// Returns all the known keys, e.g. to decode the tokens with
+    /// [Jwks](super::jwks::Jwks).
+    pub fn jwks(&self) -> JwkSet {
+        let state = self.state.lock().unwrap();
+
+        JwkSet {
+            keys: state.keys.iter().map(|(key, _)| key.clone()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tokio::time::sleep;
+
+    use super::*;
+    use crate::core::auth::jwt::jwt_verify::tests::JWK_SET;
+
+    const OLD_KID: &str = "I48qMJp566SSKQogYXYtHBo9q6ZcEKHixNPeNoxV1c8";
+    const NEW_KID: &str = "rotated";
+
+    /// Returns the sets in order and keeps returning the last one.
+    struct StubFetcher(Mutex<VecDeque<JwkSet>>);
+
+    #[async_trait::async_trait]
+    impl JwksFetcher for StubFetcher {
+        async fn fetch(&self) -> Result<JwkSet> {
+            let mut sets = self.0.lock().unwrap();
+            let set = if sets.len() > 1 {
+                sets.pop_front()
+            } else {
+                sets.front().cloned()
+            };
+
+            set.ok_or_else(|| anyhow::anyhow!("No JWKS"))
+        }
+    }
+
+    /// Counts the fetches, which fail after a delay.
+    #[derive(Default)]
+    struct FailingFetcher(AtomicUsize);
+
+    #[async_trait::async_trait]
+    impl JwksFetcher for FailingFetcher {
+        async fn fetch(&self) -> Result<JwkSet> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            sleep(Duration::from_millis(10)).await;
+
+            Err(anyhow!("Identity provider is down"))
+        }
+    }
+
+    fn rotated_set() -> JwkSet {
+        let mut jwk = JWK_SET.keys[0].clone();
+        jwk.common.key_id = Some(NEW_KID.to_string());
+
+        JwkSet { keys: vec![jwk] }
+    }
+
+    #[tokio::test]
+    async fn test_refresh_rotated_keys() {
+        let fetcher = StubFetcher(Mutex::new(VecDeque::from([JWK_SET.clone(), rotated_set()])));
+        let jwks = RefreshableJwks::new(
+            Arc::new(fetcher),
+            Duration::from_secs(60),
+            Duration::from_millis(50),
+        );
+
+        assert!(jwks.find(OLD_KID).is_none());
+
+        jwks.refresh_if_stale().await.unwrap();
+        assert!(jwks.find(OLD_KID).is_some());
+        assert!(jwks.find(NEW_KID).is_none());
+
+        // the refresh interval hasn't elapsed yet
+        jwks.refresh_if_stale().await.unwrap();
+        assert!(jwks.find(NEW_KID).is_none());
+
+        // the new key is resolvable right after the refresh and the old one is
+        // kept within the grace period
+        jwks.refresh().await.unwrap();
+        assert!(jwks.find(NEW_KID).is_some());
+        assert!(jwks.find(OLD_KID).is_some());
+        assert_eq!(jwks.jwks().keys.len(), JWK_SET.keys.len() + 1);
+
+        sleep(Duration::from_millis(60)).await;
+
+        // the old key is dropped once the grace period expires
+        jwks.refresh().await.unwrap();
+        assert!(jwks.find(NEW_KID).is_some());
+        assert!(jwks.find(OLD_KID).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_failure_is_not_retried_per_request() {
+        let fetcher = Arc::new(FailingFetcher::default());
+        let jwks = RefreshableJwks::new(
+            fetcher.clone(),
+            Duration::from_millis(50),
+            Duration::from_secs(60),
+        );
+
+        // concurrent requests share the same fetch
+        let (first, second) = tokio::join!(jwks.refresh_if_stale(), jwks.refresh_if_stale());
+        assert!(first.is_err());
+        assert!(second.is_err());
+        assert_eq!(fetcher.0.load(Ordering::SeqCst), 1);
+
+        // the failed attempt isn't retried before the refresh interval elapses
+        jwks.refresh_if_stale().await.unwrap();
+        assert_eq!(fetcher.0.load(Ordering::SeqCst), 1);
+
+        sleep(Duration::from_millis(60)).await;
+
+        assert!(jwks.refresh_if_stale().await.is_err());
+        assert_eq!(fetcher.0.load(Ordering::SeqCst), 2);
+    }
+}
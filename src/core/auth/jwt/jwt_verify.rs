@@ -1,8 +1,12 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
 use headers::authorization::Bearer;
 use headers::{Authorization, HeaderMapExt};
 use serde::Deserialize;
 
 use super::jwks::Jwks;
+use super::jwks_refresh::{HttpJwksFetcher, RefreshableJwks};
 use crate::core::auth::error::Error;
 use crate::core::auth::verification::Verification;
 use crate::core::auth::verify::Verify;
@@ -46,9 +50,37 @@ impl JwtVerifier {
         Ok(value.map(|token| token.token().to_owned()))
     }
 
-    async fn validate_token(&self, token: &str) -> Verification {
+    /// Returns the keys to decode the tokens with. The refreshable JWKS is
+    /// fetched again once the refresh interval elapses and keeps the
+    /// previously known keys if that fails.
+    async fn decoder(&self, request: &RequestContext) -> Cow<'_, Jwks> {
+        let Some(refresh) = &self.options.refresh else {
+            return Cow::Borrowed(&self.decoder);
+        };
+
+        let jwks = request
+            .jwks
+            .entry(refresh.url.to_string())
+            .or_insert_with(|| {
+                let fetcher = HttpJwksFetcher::new(refresh.url.clone(), request.runtime.clone());
+                let jwks =
+                    RefreshableJwks::new(Arc::new(fetcher), refresh.interval, refresh.grace_period)
+                        .with_keys(self.options.jwks.clone());
+
+                Arc::new(jwks)
+            })
+            .clone();
+
+        if let Err(err) = jwks.refresh_if_stale().await {
+            tracing::warn!("Failed to refresh the JWKS from {}: {}", refresh.url, err);
+        }
+
+        Cow::Owned(Jwks { set: jwks.jwks(), optional_kid: self.options.optional_kid })
+    }
+
+    async fn validate_token(&self, decoder: &Jwks, token: &str) -> Verification {
         Verification::from_result(
-            self.decoder.decode(token),
+            decoder.decode(token),
             |claims| self.validate_claims(&claims),
             |err| Verification::fail(Error::Parse(err.to_string())),
         )
@@ -74,7 +106,8 @@ impl Verify for JwtVerifier {
             return Verification::fail(Error::Missing);
         };
 
-        self.validate_token(&token).await
+        let decoder = self.decoder(request).await;
+        self.validate_token(&decoder, &token).await
     }
 }
 
@@ -114,11 +147,13 @@ pub fn validate_aud(options: &blueprint::Jwt, claims: &JwtClaim) -> bool {
 #[cfg(test)]
 pub mod tests {
     use std::collections::HashSet;
+    use std::time::Duration;
 
     use jsonwebtoken::jwk::JwkSet;
     use once_cell::sync::Lazy;
 
     use super::*;
+    use crate::core::config::JwksRefresh;
 
     // tokens are valid for 10 years. If it is expired, update it =)
     // to parse the token and see its content use https://jwt.io
@@ -159,6 +194,7 @@ pub mod tests {
                 audiences: Default::default(),
                 optional_kid: false,
                 jwks: JWK_SET.clone(),
+                refresh: None,
             }
         }
     }
@@ -278,6 +314,37 @@ pub mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn validate_token_refreshed_jwks() {
+        let refresh = JwksRefresh {
+            url: "http://localhost/jwks.json".parse().unwrap(),
+            interval: Duration::from_secs(60),
+            grace_period: Duration::from_secs(60),
+        };
+        let jwt_options = blueprint::Jwt {
+            jwks: JwkSet { keys: vec![] },
+            refresh: Some(refresh.clone()),
+            ..blueprint::Jwt::test_value()
+        };
+        let jwt_provider = JwtVerifier::new(jwt_options);
+
+        // the key isn't known yet
+        let request = create_jwt_auth_request(JWT_VALID_TOKEN_WITH_KID);
+        let error = jwt_provider.verify(&request).await;
+
+        assert!(matches!(error, Verification::Fail(Error::Parse(_))));
+
+        // the key was published by a refresh shared across the requests
+        let fetcher = HttpJwksFetcher::new(refresh.url.clone(), request.runtime.clone());
+        let jwks = RefreshableJwks::new(Arc::new(fetcher), refresh.interval, refresh.grace_period)
+            .with_keys(JWK_SET.clone());
+        request.jwks.insert(refresh.url.to_string(), Arc::new(jwks));
+
+        let valid = jwt_provider.verify(&request).await;
+
+        assert_eq!(valid, Verification::succeed());
+    }
+
     mod aud {
         use std::collections::HashSet;
 
@@ -1,2 +1,3 @@
 pub mod jwks;
+pub mod jwks_refresh;
 pub mod jwt_verify;
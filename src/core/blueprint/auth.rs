@@ -3,7 +3,7 @@ use std::fmt::Debug;
 
 use jsonwebtoken::jwk::JwkSet;
 
-use crate::core::config::{ConfigModule, Content};
+use crate::core::config::{ConfigModule, Content, JwksRefresh, JwksSource};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Basic {
@@ -16,6 +16,8 @@ pub struct Jwt {
     pub audiences: HashSet<String>,
     pub optional_kid: bool,
     pub jwks: JwkSet,
+    /// Options to fetch the JWKS again, so the rotated keys are picked up.
+    pub refresh: Option<JwksRefresh>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -33,15 +35,16 @@ impl From<Content<String>> for Content<Provider> {
     }
 }
 
-impl From<Content<JwkSet>> for Content<Provider> {
-    fn from(content: Content<JwkSet>) -> Self {
+impl From<Content<JwksSource>> for Content<Provider> {
+    fn from(content: Content<JwksSource>) -> Self {
         Content {
             id: content.id,
             content: Provider::Jwt(Jwt {
-                jwks: content.content,
+                jwks: content.content.set,
                 issuer: None,
                 audiences: HashSet::new(),
                 optional_kid: false,
+                refresh: content.content.refresh,
             }),
         }
     }
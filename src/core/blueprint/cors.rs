@@ -150,9 +150,10 @@ fn ensure_usable_cors_rules(
 
         let allowing_all_origins = layer.allow_origins.iter().any(is_wildcard);
 
+        // browsers reject the credentialed responses that allow any origin
         if allowing_all_origins {
             return Err(ValidationError::new(
-                BlueprintError::InvalidCORSConfiguration("Access-Control-Allow-Origin".to_string()),
+                BlueprintError::CorsWildcardWithCredentials,
             ));
         }
 
@@ -167,12 +168,37 @@ fn ensure_usable_cors_rules(
     Ok(())
 }
 
+/// Checks that the names are valid header names, allowing the `*` wildcard.
+/// Empty names are accepted, so the header can be sent without a value.
+fn validate_header_names(
+    names: &[String],
+) -> Result<(), ValidationError<crate::core::blueprint::BlueprintError>> {
+    match names
+        .iter()
+        .filter(|name| !name.is_empty() && *name != "*")
+        .find(|name| HeaderName::from_bytes(name.as_bytes()).is_err())
+    {
+        Some(name) => Err(ValidationError::new(BlueprintError::InvalidCorsHeaderName(
+            name.clone(),
+        ))),
+        None => Ok(()),
+    }
+}
+
 impl TryFrom<config::cors::Cors> for Cors {
     type Error = ValidationError<crate::core::blueprint::BlueprintError>;
 
     fn try_from(
         value: config::cors::Cors,
     ) -> Result<Self, ValidationError<crate::core::blueprint::BlueprintError>> {
+        validate_header_names(&value.allow_headers).map_err(|e| e.trace("allowHeaders"))?;
+        validate_header_names(&value.expose_headers).map_err(|e| e.trace("exposeHeaders"))?;
+        if let Some(max_age) = value.max_age.filter(|max_age| *max_age < 0) {
+            return Err(
+                ValidationError::new(BlueprintError::NegativeCorsMaxAge(max_age)).trace("maxAge"),
+            );
+        }
+
         let cors = Cors {
             allow_credentials: value.allow_credentials.unwrap_or_default(),
             allow_headers: (!value.allow_headers.is_empty()).then_some(
@@ -241,6 +267,65 @@ mod tests {
     use http::header::HeaderValue;
 
     use super::*;
+    use crate::core::http::Method;
+
+    #[test]
+    fn test_negative_max_age() {
+        let cors = config::cors::Cors { max_age: Some(-1), ..Default::default() };
+
+        let actual = Cors::try_from(cors).unwrap_err();
+        let expected = ValidationError::new(BlueprintError::NegativeCorsMaxAge(-1)).trace("maxAge");
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_invalid_expose_header() {
+        let cors = config::cors::Cors {
+            expose_headers: vec!["x-request-id".to_string(), "x bogus".to_string()],
+            ..Default::default()
+        };
+
+        let actual = Cors::try_from(cors).unwrap_err();
+        let expected =
+            ValidationError::new(BlueprintError::InvalidCorsHeaderName("x bogus".to_string()))
+                .trace("exposeHeaders");
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_wildcard_origin_with_credentials() {
+        let cors = config::cors::Cors {
+            allow_credentials: Some(true),
+            allow_origins: vec!["*".to_string()],
+            allow_methods: vec![Method::POST],
+            ..Default::default()
+        };
+
+        let actual = Cors::try_from(cors).unwrap_err();
+        let expected = ValidationError::new(BlueprintError::CorsWildcardWithCredentials);
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_explicit_origin_with_credentials() {
+        let cors = config::cors::Cors {
+            allow_credentials: Some(true),
+            allow_origins: vec!["https://example.com".to_string()],
+            allow_methods: vec![Method::POST],
+            ..Default::default()
+        };
+
+        let cors = Cors::try_from(cors).unwrap();
+
+        assert_eq!(
+            cors.allow_origins,
+            vec![HeaderValue::from_static("https://example.com")]
+        );
+        assert!(cors.allow_credentials_to_header().is_some());
+    }
 
     #[test]
     fn test_allow_origin_to_header() {
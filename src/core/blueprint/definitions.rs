@@ -10,7 +10,7 @@ use union_resolver::update_union_resolver;
 use crate::core::blueprint::*;
 use crate::core::config::{Config, Enum, Field, GraphQLOperationType, Protected, Union};
 use crate::core::directive::DirectiveCodec;
-use crate::core::ir::model::{Cache, IR};
+use crate::core::ir::model::{Cache, IO, IR};
 use crate::core::try_fold::TryFold;
 use crate::core::{config, scalar, Type};
 
@@ -54,7 +54,7 @@ pub fn to_input_object_type_definition(
             })
             .collect(),
         description: definition.description,
-        directives: Vec::new(),
+        directives: definition.directives,
     }))
 }
 
@@ -250,7 +250,7 @@ fn to_enum_type_definition((name, eu): (&String, &Enum)) -> Definition {
             .variants
             .iter()
             .map(|variant| EnumValueDefinition {
-                description: None,
+                description: variant.doc.clone(),
                 name: variant.name.clone(),
                 directives: vec![],
                 alias: variant.alias.clone().unwrap_or_default().options,
@@ -300,6 +300,7 @@ fn update_args<'a>() -> TryFold<
                 directives: to_directives(&field.directives),
                 resolver: None,
                 default_value: field.default_value.clone(),
+                deprecation: field.deprecation.clone(),
             })
         },
     )
@@ -372,15 +373,49 @@ pub fn update_cache_resolvers<'a>() -> TryFold<
 > {
     TryFold::<(&ConfigModule, &Field, &config::Type, &str), FieldDefinition, BlueprintError>::new(
         move |(_config, field, typ, _name), mut b_field| {
-            if let Some(config::Cache { max_age }) = field.cache.as_ref().or(typ.cache.as_ref()) {
-                b_field.map_expr(|expression| Cache::wrap(*max_age, expression))
-            }
-
-            Valid::succeed(b_field)
+            let Some(config::Cache { max_age, key_args }) =
+                field.cache.as_ref().or(typ.cache.as_ref())
+            else {
+                return Valid::succeed(b_field);
+            };
+
+            validate_key_args(field, b_field.resolver.as_ref(), key_args).map(|_| {
+                b_field.map_expr(|expression| Cache::wrap(*max_age, key_args.clone(), expression));
+                b_field
+            })
         },
     )
 }
 
+/// The key of the cache is formed with the `keyArgs` only, so a misspelled
+/// argument would make all the callers share the same cached value.
+fn validate_key_args(
+    field: &Field,
+    resolver: Option<&IR>,
+    key_args: &[String],
+) -> Valid<(), BlueprintError> {
+    let Some(resolver) = resolver.filter(|_| !key_args.is_empty()) else {
+        return Valid::succeed(());
+    };
+
+    let mut is_http = true;
+    resolver
+        .clone()
+        .modify_io(&mut |io| is_http &= matches!(io, IO::Http { .. }));
+    if !is_http {
+        return Valid::fail(BlueprintError::KeyArgsOnlyForHttp);
+    }
+
+    Valid::from_iter(key_args.iter(), |key_arg| {
+        if field.args.contains_key(key_arg) {
+            Valid::succeed(())
+        } else {
+            Valid::fail(BlueprintError::KeyArgNotFound(key_arg.clone()))
+        }
+    })
+    .unit()
+}
+
 fn validate_field_type_exist(config: &Config, field: &Field) -> Valid<(), BlueprintError> {
     let field_type = field.type_of.name();
     if !scalar::Scalar::is_predefined(field_type) && !config.contains(field_type) {
@@ -546,7 +581,7 @@ pub fn to_field_definition(
         .and(update_resolver(operation_type, object_name))
         .and(update_modify().trace(config::Modify::trace_name().as_str()))
         .and(fix_dangling_resolvers())
-        .and(update_cache_resolvers())
+        .and(update_cache_resolvers().trace(config::Cache::trace_name().as_str()))
         .and(update_protected(object_name).trace(Protected::trace_name().as_str()))
         .and(update_enum_alias())
         .and(update_union_resolver())
@@ -21,7 +21,8 @@ impl<A: Default> Default for DynamicValue<A> {
 impl<A> DynamicValue<A> {
     /// This function is used to prepend a string to every Mustache Expression.
     /// This is useful when we want to hide a Mustache data argument from the
-    /// user and make the use of Tailcall easier
+    /// user and make the use of Tailcall easier. Expressions that read the
+    /// upstream `response.headers` are left untouched.
     pub fn prepend(self, name: &str) -> Self {
         match self {
             DynamicValue::Value(value) => DynamicValue::Value(value),
@@ -33,7 +34,9 @@ impl<A> DynamicValue<A> {
                     if let Some(crate::core::mustache::Segment::Expression(vec)) =
                         segments.get_mut(0)
                     {
-                        vec.insert(0, name.to_string());
+                        if !vec.starts_with(&["response".to_string(), "headers".to_string()]) {
+                            vec.insert(0, name.to_string());
+                        }
                     }
                     DynamicValue::Mustache(mustache)
                 }
@@ -158,5 +161,11 @@ mod test {
         let value: DynamicValue<ConstValue> = DynamicValue::Value(ConstValue::Null).prepend("args");
         let expected: DynamicValue<ConstValue> = DynamicValue::Value(ConstValue::Null);
         assert_eq!(value, expected);
+
+        let value: DynamicValue<ConstValue> =
+            DynamicValue::Mustache(Mustache::parse("{{.response.headers.etag}}")).prepend("args");
+        let expected: DynamicValue<ConstValue> =
+            DynamicValue::Mustache(Mustache::parse("{{.response.headers.etag}}"));
+        assert_eq!(value, expected);
     }
 }
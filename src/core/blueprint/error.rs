@@ -100,6 +100,12 @@ pub enum BlueprintError {
     #[error("Link src cannot be empty")]
     LinkSrcCannotBeEmpty,
 
+    #[error("Refresh interval is only supported by the Jwks links with a URL src")]
+    LinkRefreshRequiresJwksUrl,
+
+    #[error("Refresh interval must be greater than 0")]
+    LinkRefreshIntervalIsZero,
+
     #[error("Duplicated id: {0}")]
     Duplicated(String),
 
@@ -136,6 +142,9 @@ pub enum BlueprintError {
     #[error("unknown template directive '{0}'")]
     UnknownTemplateDirective(String),
 
+    #[error("response header '{0}' is not captured")]
+    ResponseHeaderNotCaptured(String),
+
     #[error("Query root is missing")]
     QueryRootIsMissing,
 
@@ -148,12 +157,33 @@ pub enum BlueprintError {
     #[error("Mutation type is not defined")]
     MutationTypeNotDefined,
 
+    #[error("Subscription type is not defined")]
+    SubscriptionTypeNotDefined,
+
     #[error("Certificate is required for HTTP2")]
     CertificateIsRequiredForHTTP2,
 
     #[error("Key is required for HTTP2")]
     KeyIsRequiredForHTTP2,
 
+    #[error("Invalid client CA certificate: {0}")]
+    InvalidClientCA(String),
+
+    #[error("Shutdown timeout must be non-negative. Got: {0}")]
+    NegativeShutdownTimeout(i64),
+
+    #[error("Rate limit window must be positive. Got: {0}")]
+    InvalidRateLimitWindow(u64),
+
+    #[error("Rate limit requests must be positive. Got: {0}")]
+    InvalidRateLimitRequests(u64),
+
+    #[error("Max request body size must be positive. Got: {0}")]
+    InvalidMaxRequestBodyBytes(usize),
+
+    #[error("Response header '{0}' is specified more than once")]
+    DuplicateResponseHeader(String),
+
     #[error("Experimental headers must start with 'x-' or 'X-'. Got: '{0}'")]
     ExperimentalHeaderInvalidFormat(String),
 
@@ -163,6 +193,21 @@ pub enum BlueprintError {
     #[error("Invalid CORS configuration: Cannot combine `Access-Control-Allow-Credentials: true` with `{0}: *`")]
     InvalidCORSConfiguration(String),
 
+    #[error("Invalid CORS configuration: Cannot combine `Access-Control-Allow-Credentials: true` with `Access-Control-Allow-Origin: *`")]
+    CorsWildcardWithCredentials,
+
+    #[error("CORS max age must be non-negative. Got: {0}")]
+    NegativeCorsMaxAge(i64),
+
+    #[error("Invalid CORS header name: '{0}'")]
+    InvalidCorsHeaderName(String),
+
+    #[error("keyArgs contains '{0}', which is not an argument of the field")]
+    KeyArgNotFound(String),
+
+    #[error("keyArgs is only supported for the fields resolved with @http")]
+    KeyArgsOnlyForHttp,
+
     #[error("{0}")]
     Cause(String),
 
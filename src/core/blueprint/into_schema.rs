@@ -1,11 +1,15 @@
 use std::sync::Arc;
 
-use async_graphql::dynamic::{self, FieldFuture, FieldValue, SchemaBuilder, TypeRef};
+use async_graphql::dynamic::{
+    self, FieldFuture, FieldValue, SchemaBuilder, SubscriptionFieldFuture, TypeRef,
+};
 use async_graphql_value::ConstValue;
 use futures_util::TryFutureExt;
 use tracing::Instrument;
 
-use crate::core::blueprint::{Blueprint, Definition};
+use super::directive::Directive;
+use crate::core::blueprint::{Blueprint, Definition, ObjectTypeDefinition};
+use crate::core::config::ONE_OF_DIRECTIVE;
 use crate::core::http::RequestContext;
 use crate::core::ir::{EvalContext, ResolverContext, TypedValue};
 use crate::core::jit::graphql_error::ErrorExtensions;
@@ -34,7 +38,28 @@ fn set_default_value(
     }
 }
 
-fn to_field_value(value: async_graphql::Value) -> FieldValue<'static> {
+/// Converts the custom directive declared in the config to the directive
+/// invocation of the dynamic schema, so it shows up in the exported SDL.
+/// Arguments are sorted by name to keep the SDL stable.
+fn to_directive(directive: &Directive) -> dynamic::Directive {
+    let mut arguments = directive.arguments.iter().collect::<Vec<_>>();
+    arguments.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    arguments
+        .into_iter()
+        .fold(
+            dynamic::Directive::new(directive.name.clone()),
+            |dyn_directive, (name, value)| match ConstValue::from_json(value.clone()) {
+                Ok(value) => dyn_directive.argument(name.clone(), value),
+                Err(err) => {
+                    tracing::warn!("conversion from serde_json::Value to ConstValue failed for directive argument with error {err:?}");
+                    dyn_directive
+                }
+            },
+        )
+}
+
+fn to_field_value<'a>(value: async_graphql::Value) -> FieldValue<'a> {
     match value {
         ConstValue::List(vec) => FieldValue::list(vec.into_iter().map(to_field_value)),
         value => {
@@ -118,11 +143,20 @@ fn to_type(def: &Definition) -> dynamic::Type {
                 if let Some(description) = &field.description {
                     dyn_schema_field = dyn_schema_field.description(description);
                 }
+                if let Some(reason) = &field.deprecation {
+                    dyn_schema_field = dyn_schema_field.deprecation(Some(reason));
+                }
+                for directive in field.directives.iter() {
+                    dyn_schema_field = dyn_schema_field.directive(to_directive(directive));
+                }
                 for arg in field.args.iter() {
-                    dyn_schema_field = dyn_schema_field.argument(set_default_value(
-                        dynamic::InputValue::new(arg.name.clone(), TypeRef::from(&arg.of_type)),
-                        arg.default_value.clone(),
-                    ));
+                    let mut input_value =
+                        dynamic::InputValue::new(arg.name.clone(), TypeRef::from(&arg.of_type));
+                    if let Some(description) = &arg.description {
+                        input_value = input_value.description(description);
+                    }
+                    dyn_schema_field = dyn_schema_field
+                        .argument(set_default_value(input_value, arg.default_value.clone()));
                 }
                 object = object.field(dyn_schema_field);
             }
@@ -132,6 +166,9 @@ fn to_type(def: &Definition) -> dynamic::Type {
             for interface in def.implements.iter() {
                 object = object.implement(interface.clone());
             }
+            for directive in def.directives.iter() {
+                object = object.directive(to_directive(directive));
+            }
 
             dynamic::Type::Object(object)
         }
@@ -160,6 +197,13 @@ fn to_type(def: &Definition) -> dynamic::Type {
             if let Some(description) = &def.description {
                 input_object = input_object.description(description);
             }
+            if def
+                .directives
+                .iter()
+                .any(|directive| directive.name == ONE_OF_DIRECTIVE)
+            {
+                input_object = input_object.oneof();
+            }
 
             dynamic::Type::InputObject(input_object)
         }
@@ -168,6 +212,9 @@ fn to_type(def: &Definition) -> dynamic::Type {
             if let Some(description) = &def.description {
                 scalar = scalar.description(description);
             }
+            if let Some(url) = def.scalar.specified_by_url() {
+                scalar = scalar.specified_by_url(url);
+            }
             let name = def.scalar.clone();
             scalar = scalar.validator(move |v| name.validate(v));
             dynamic::Type::Scalar(scalar)
@@ -175,7 +222,11 @@ fn to_type(def: &Definition) -> dynamic::Type {
         Definition::Enum(def) => {
             let mut enum_type = dynamic::Enum::new(def.name.clone());
             for value in def.enum_values.iter() {
-                enum_type = enum_type.item(dynamic::EnumItem::new(value.name.clone()));
+                let mut item = dynamic::EnumItem::new(value.name.clone());
+                if let Some(description) = &value.description {
+                    item = item.description(description);
+                }
+                enum_type = enum_type.item(item);
             }
             if let Some(desc) = def.description.clone() {
                 enum_type = enum_type.description(desc);
@@ -192,16 +243,78 @@ fn to_type(def: &Definition) -> dynamic::Type {
     }
 }
 
+/// Converts the subscription root type. Every field emits the value resolved
+/// by its IR as a single item of the stream.
+fn to_subscription(def: &ObjectTypeDefinition) -> dynamic::Subscription {
+    let mut subscription = dynamic::Subscription::new(def.name.clone());
+
+    for field in def.fields.iter() {
+        let resolver = field.resolver.clone();
+        let mut subscription_field = dynamic::SubscriptionField::new(
+            field.name.clone(),
+            TypeRef::from(&field.of_type),
+            move |ctx| {
+                let resolver = resolver.clone();
+
+                SubscriptionFieldFuture::new(async move {
+                    let req_ctx = ctx.ctx.data::<Arc<RequestContext>>()?;
+                    let value = match resolver {
+                        Some(expr) => {
+                            let ctx: ResolverContext = ctx.into();
+                            let ctx = &mut EvalContext::new(req_ctx, &ctx);
+
+                            expr.eval(ctx).await.map_err(|err| err.extend())?
+                        }
+                        None => ConstValue::Null,
+                    };
+
+                    Ok(futures_util::stream::once(async move {
+                        Ok(to_field_value(value))
+                    }))
+                })
+            },
+        );
+        if let Some(description) = &field.description {
+            subscription_field = subscription_field.description(description);
+        }
+        if let Some(reason) = &field.deprecation {
+            subscription_field = subscription_field.deprecation(Some(reason));
+        }
+        for arg in field.args.iter() {
+            let mut input_value =
+                dynamic::InputValue::new(arg.name.clone(), TypeRef::from(&arg.of_type));
+            if let Some(description) = &arg.description {
+                input_value = input_value.description(description);
+            }
+            subscription_field = subscription_field
+                .argument(set_default_value(input_value, arg.default_value.clone()));
+        }
+        subscription = subscription.field(subscription_field);
+    }
+    if let Some(description) = &def.description {
+        subscription = subscription.description(description);
+    }
+
+    subscription
+}
+
 impl From<&Blueprint> for SchemaBuilder {
     fn from(blueprint: &Blueprint) -> Self {
         let query = blueprint.query();
         let mutation = blueprint.mutation();
-        let mut schema = dynamic::Schema::build(query.as_str(), mutation.as_deref(), None);
+        let subscription = blueprint.subscription();
+        let mut schema =
+            dynamic::Schema::build(query.as_str(), mutation.as_deref(), subscription.as_deref());
 
         schema = inject_custom_scalars(schema, blueprint);
 
         for def in blueprint.definitions.iter() {
-            schema = schema.register(to_type(def));
+            schema = match def {
+                Definition::Object(def) if subscription.as_ref() == Some(&def.name) => {
+                    schema.register(to_subscription(def))
+                }
+                def => schema.register(to_type(def)),
+            };
         }
 
         schema
@@ -212,8 +325,12 @@ fn inject_custom_scalars(mut schema: SchemaBuilder, blueprint: &Blueprint) -> Sc
     fn inject_scalar(schema: SchemaBuilder, type_name: &str) -> SchemaBuilder {
         if let Some(scalar) = Scalar::find(type_name) {
             let scalar = scalar.clone();
+            let mut dyn_scalar = dynamic::Scalar::new(scalar.name());
+            if let Some(url) = scalar.specified_by_url() {
+                dyn_scalar = dyn_scalar.specified_by_url(url);
+            }
             schema.register(dynamic::Type::Scalar(
-                dynamic::Scalar::new(scalar.name()).validator(move |val| scalar.validate(val)),
+                dyn_scalar.validator(move |val| scalar.validate(val)),
             ))
         } else {
             schema
@@ -245,3 +362,265 @@ fn inject_custom_scalars(mut schema: SchemaBuilder, blueprint: &Blueprint) -> Sc
     }
     schema
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tailcall_valid::Validator;
+
+    use crate::core::blueprint::Blueprint;
+    use crate::core::config::{Config, ConfigModule};
+    use crate::core::http::RequestContext;
+
+    #[test]
+    fn test_schema_with_subscription() {
+        let config = Config::from_sdl(
+            r#"
+            schema @server @upstream {
+                query: Query
+                subscription: Subscription
+            }
+
+            type Query {
+                hello: String @expr(body: "world")
+            }
+
+            type Subscription {
+                price: Int @expr(body: 42)
+            }
+            "#,
+        )
+        .to_result()
+        .unwrap();
+        let blueprint = Blueprint::try_from(&ConfigModule::from(config)).unwrap();
+
+        assert_eq!(blueprint.subscription().as_deref(), Some("Subscription"));
+
+        let sdl = blueprint.to_schema().sdl();
+        assert!(sdl.contains("type Subscription {"));
+        assert!(sdl.contains("price: Int"));
+    }
+
+    #[test]
+    fn test_schema_with_custom_directives() {
+        let config = Config::from_sdl(
+            r#"
+            schema @server @upstream {
+                query: Query
+            }
+
+            type Query {
+                user: User @expr(body: {id: 1})
+            }
+
+            type User @tag(name: "public") {
+                id: Int @tag(name: "internal")
+                name: String @visibility(scope: "admin") @expr(body: "John")
+            }
+            "#,
+        )
+        .to_result()
+        .unwrap();
+        let blueprint = Blueprint::try_from(&ConfigModule::from(config)).unwrap();
+
+        let sdl = blueprint.to_schema().sdl();
+        assert!(sdl.contains(r#"type User @tag(name: "public") {"#));
+        assert!(sdl.contains(r#"id: Int @tag(name: "internal")"#));
+        assert!(sdl.contains(r#"name: String @visibility(scope: "admin")"#));
+    }
+
+    #[test]
+    fn test_schema_with_deprecated_field() {
+        let config = Config::from_sdl(
+            r#"
+            schema @server @upstream {
+                query: Query
+            }
+
+            type Query {
+                hello: String @expr(body: "world") @deprecated(reason: "use greeting")
+                greeting: String @expr(body: "world")
+            }
+            "#,
+        )
+        .to_result()
+        .unwrap();
+        let blueprint = Blueprint::try_from(&ConfigModule::from(config)).unwrap();
+
+        let sdl = blueprint.to_schema().sdl();
+        assert!(sdl.contains(r#"hello: String @deprecated(reason: "use greeting")"#));
+        assert!(sdl.contains("greeting: String\n"));
+    }
+
+    #[tokio::test]
+    async fn test_schema_with_enum_descriptions() {
+        let config = Config::from_sdl(
+            r#"
+            schema @server @upstream {
+                query: Query
+            }
+
+            type Query {
+                status: Status @expr(body: "ACTIVE")
+            }
+
+            "Status of the account"
+            enum Status {
+                "Account can be used"
+                ACTIVE
+                INACTIVE
+            }
+            "#,
+        )
+        .to_result()
+        .unwrap();
+        let blueprint = Blueprint::try_from(&ConfigModule::from(config)).unwrap();
+
+        let response = blueprint
+            .to_schema()
+            .execute(
+                r#"{ __type(name: "Status") { description enumValues { name description } } }"#,
+            )
+            .await;
+
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({
+                "__type": {
+                    "description": "Status of the account",
+                    "enumValues": [
+                        { "name": "ACTIVE", "description": "Account can be used" },
+                        { "name": "INACTIVE", "description": null }
+                    ]
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_schema_with_input_field_description() {
+        let config = Config::from_sdl(
+            r#"
+            schema @server @upstream {
+                query: Query
+            }
+
+            type Query {
+                users(filter: Filter): [Int] @expr(body: [1])
+            }
+
+            input Filter {
+                "Maximum number of users"
+                limit: Int = 10
+            }
+            "#,
+        )
+        .to_result()
+        .unwrap();
+        let blueprint = Blueprint::try_from(&ConfigModule::from(config)).unwrap();
+
+        let response = blueprint
+            .to_schema()
+            .execute(
+                r#"{ __type(name: "Filter") { inputFields { name description defaultValue } } }"#,
+            )
+            .await;
+
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({
+                "__type": {
+                    "inputFields": [
+                        { "name": "limit", "description": "Maximum number of users", "defaultValue": "10" }
+                    ]
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_schema_with_validated_scalar() {
+        let config = Config::from_sdl(
+            r#"
+            schema @server @upstream {
+                query: Query
+            }
+
+            type Query {
+                user(filter: Filter): Email @expr(body: "{{.args.filter.email}}")
+            }
+
+            input Filter {
+                email: Email
+            }
+            "#,
+        )
+        .to_result()
+        .unwrap();
+        let blueprint = Blueprint::try_from(&ConfigModule::from(config)).unwrap();
+        let schema = blueprint.to_schema();
+        let execute = |query: &str| {
+            let request =
+                async_graphql::Request::new(query).data(Arc::new(RequestContext::default()));
+            schema.execute(request)
+        };
+
+        let response = execute(r#"{ user(filter: { email: "not an email" }) }"#).await;
+        assert!(!response.errors.is_empty());
+
+        let response = execute(r#"{ user(filter: { email: "john@example.com" }) }"#).await;
+        assert!(response.errors.is_empty());
+
+        let response = schema
+            .execute(r#"{ __type(name: "Email") { specifiedByURL } }"#)
+            .await;
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({
+                "__type": {
+                    "specifiedByURL": "https://html.spec.whatwg.org/multipage/input.html#valid-e-mail-address"
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_schema_with_argument_description() {
+        let config = Config::from_sdl(
+            r#"
+            schema @server @upstream {
+                query: Query
+            }
+
+            type Query {
+                users(
+                    "Maximum number of users"
+                    limit: Int = 10
+                ): [Int] @expr(body: [1])
+            }
+            "#,
+        )
+        .to_result()
+        .unwrap();
+        let blueprint = Blueprint::try_from(&ConfigModule::from(config)).unwrap();
+
+        let response = blueprint
+            .to_schema()
+            .execute(r#"{ __type(name: "Query") { fields { args { name description defaultValue } } } }"#)
+            .await;
+
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({
+                "__type": {
+                    "fields": [{
+                        "args": [
+                            { "name": "limit", "description": "Maximum number of users", "defaultValue": "10" }
+                        ]
+                    }]
+                }
+            })
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use tailcall_valid::{Valid, ValidationError, Validator};
+use url::Url;
 
 use super::BlueprintError;
 use crate::core::config::{Link, LinkType};
@@ -27,6 +28,13 @@ impl TryFrom<Vec<Link>> for Links {
                     }
                     Valid::succeed(link)
                 })
+                .and_then(|link| match link.refresh_interval {
+                    Some(_) if link.type_of != LinkType::Jwks || Url::parse(&link.src).is_err() => {
+                        Valid::fail(BlueprintError::LinkRefreshRequiresJwksUrl)
+                    }
+                    Some(0) => Valid::fail(BlueprintError::LinkRefreshIntervalIsZero),
+                    _ => Valid::succeed(link),
+                })
                 .trace(&pos.to_string())
         })
         .and_then(|links| {
@@ -4,7 +4,7 @@ use super::{BlueprintError, FieldDefinition};
 use crate::core::config::{self, Config};
 use crate::core::directive::DirectiveCodec;
 use crate::core::ir::model::{IO, IR};
-use crate::core::scalar;
+use crate::core::{scalar, Mustache};
 
 struct MustachePartsValidator<'a> {
     type_of: &'a config::Type,
@@ -93,6 +93,17 @@ impl<'a> MustachePartsValidator<'a> {
                 // "headers" and "env" refers to values known at runtime, which
                 // we can't validate here
             }
+            "response" => {
+                // only the headers of the upstream responses are captured
+                if tail != "headers" || parts.len() < 3 {
+                    return Valid::fail(BlueprintError::UnknownTemplateDirective(parts.join(".")));
+                }
+
+                let name = parts[2].to_lowercase();
+                if !config.captures_response_header(&name) {
+                    return Valid::fail(BlueprintError::ResponseHeaderNotCaptured(name));
+                }
+            }
             _ => {
                 return Valid::fail(BlueprintError::UnknownTemplateDirective(head.to_string()));
             }
@@ -103,7 +114,7 @@ impl<'a> MustachePartsValidator<'a> {
 
     fn validate_resolver(&self, resolver: &IR) -> Valid<(), BlueprintError> {
         match resolver {
-            IR::Merge(resolvers) => {
+            IR::Merge(resolvers) | IR::Parallel(resolvers) => {
                 Valid::from_iter(resolvers, |resolver| self.validate_resolver(resolver)).unit()
             }
             IR::IO(IO::Http { req_template, .. }) => {
@@ -196,6 +207,26 @@ impl FieldDefinition {
     }
 }
 
+impl FieldDefinition {
+    /// Validates the expressions of a mustache template used by the resolver
+    /// of the field. Templates of the request path are validated more strictly
+    /// than the ones of the query, headers or body, see `is_query`.
+    pub fn validate_template(
+        &self,
+        type_of: &config::Type,
+        config: &Config,
+        mustache: &Mustache,
+        is_query: bool,
+    ) -> Valid<(), BlueprintError> {
+        let parts_validator = MustachePartsValidator::new(type_of, config, self);
+
+        Valid::from_iter(mustache.expression_segments(), |parts| {
+            parts_validator.validate(parts, is_query)
+        })
+        .unit()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use tailcall_valid::Validator;
@@ -236,6 +267,7 @@ mod test {
             directives: vec![],
             description: None,
             default_value: None,
+            deprecation: None,
         };
 
         (config, fld)
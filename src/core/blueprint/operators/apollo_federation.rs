@@ -9,7 +9,7 @@ use crate::core::blueprint::{Blueprint, BlueprintError, Definition, TryFoldConfi
 use crate::core::config::{
     ApolloFederation, ConfigModule, EntityResolver, Field, GraphQLOperationType, Resolver,
 };
-use crate::core::ir::model::IR;
+use crate::core::ir::model::{EntityKey, IR};
 use crate::core::Type;
 
 pub struct CompileEntityResolver<'a> {
@@ -19,45 +19,54 @@ pub struct CompileEntityResolver<'a> {
 
 pub fn compile_entity_resolver(inputs: CompileEntityResolver<'_>) -> Valid<IR, BlueprintError> {
     let CompileEntityResolver { config_module, entity_resolver } = inputs;
-    let mut resolver_by_type = HashMap::new();
-
-    Valid::from_iter(
-        entity_resolver.resolver_by_type.iter(),
-        |(type_name, resolver)| {
-            // Fake field that is required for validation in some cases
-            // TODO: should be a proper way to run the validation both
-            // on types and fields
-            let field = &Field { type_of: Type::from(type_name.clone()), ..Default::default() };
-
-            // TODO: make this code reusable in other operators like call
-            let ir = match resolver {
-                Resolver::ApolloFederation(federation) => match federation {
-                    ApolloFederation::EntityResolver(entity_resolver) => {
-                        compile_entity_resolver(CompileEntityResolver { entity_resolver, ..inputs })
-                    }
-                    ApolloFederation::Service => {
-                        Valid::fail(BlueprintError::ApolloFederationResolversNoPartOfEntityResolver)
-                    }
-                },
-                resolver => {
-                    let inputs = CompileResolver {
-                        config_module,
-                        field,
-                        operation_type: &GraphQLOperationType::Query,
-                        object_name: type_name,
-                    };
-
-                    compile_resolver(&inputs, resolver).and_then(|resolver| {
-                        Valid::from_option(resolver, BlueprintError::NoResolverFoundInSchema)
-                    })
+    let mut resolver_by_type: HashMap<String, HashMap<EntityKey, IR>> = HashMap::new();
+
+    let resolvers = entity_resolver
+        .resolver_by_type
+        .iter()
+        .flat_map(|(type_name, resolvers)| {
+            resolvers
+                .iter()
+                .map(move |(fields, resolver)| (type_name, fields, resolver))
+        });
+
+    Valid::from_iter(resolvers, |(type_name, fields, resolver)| {
+        // Fake field that is required for validation in some cases
+        // TODO: should be a proper way to run the validation both
+        // on types and fields
+        let field = &Field { type_of: Type::from(type_name.clone()), ..Default::default() };
+
+        // TODO: make this code reusable in other operators like call
+        let ir = match resolver {
+            Resolver::ApolloFederation(federation) => match federation {
+                ApolloFederation::EntityResolver(entity_resolver) => {
+                    compile_entity_resolver(CompileEntityResolver { entity_resolver, ..inputs })
                 }
-            };
+                ApolloFederation::Service => {
+                    Valid::fail(BlueprintError::ApolloFederationResolversNoPartOfEntityResolver)
+                }
+            },
+            resolver => {
+                let inputs = CompileResolver {
+                    config_module,
+                    field,
+                    operation_type: &GraphQLOperationType::Query,
+                    object_name: type_name,
+                };
+
+                compile_resolver(&inputs, resolver).and_then(|resolver| {
+                    Valid::from_option(resolver, BlueprintError::NoResolverFoundInSchema)
+                })
+            }
+        };
 
-            ir.map(|ir| {
-                resolver_by_type.insert(type_name.to_owned(), ir);
-            })
-        },
-    )
+        ir.map(|ir| {
+            resolver_by_type
+                .entry(type_name.clone())
+                .or_default()
+                .insert(EntityKey::new(fields), ir);
+        })
+    })
     .map_to(IR::Entity(resolver_by_type))
 }
 
@@ -1,6 +1,7 @@
 use async_graphql_value::ConstValue;
 use tailcall_valid::{Valid, Validator};
 
+use super::http::collect_response_headers;
 use crate::core::blueprint::*;
 use crate::core::config;
 use crate::core::config::Expr;
@@ -34,10 +35,20 @@ pub fn compile_expr(inputs: CompileExpr) -> Valid<IR, BlueprintError> {
     let value = &inputs.expr.body;
     let validate = inputs.validate;
 
-    match DynamicValue::try_from(&value.clone()) {
+    let mut response_headers = vec![];
+    collect_response_headers(value, &mut response_headers);
+
+    Valid::from_iter(response_headers, |name| {
+        if config_module.captures_response_header(&name) {
+            Valid::succeed(())
+        } else {
+            Valid::fail(BlueprintError::ResponseHeaderNotCaptured(name))
+        }
+    })
+    .and_then(|_| match DynamicValue::try_from(&value.clone()) {
         Ok(data) => Valid::succeed(data),
         Err(err) => Valid::fail(BlueprintError::Error(err)),
-    }
+    })
     .and_then(|value| {
         if !value.is_const() {
             // TODO: Add validation for const with Mustache here
@@ -74,6 +74,10 @@ fn validate_schema(
     };
 
     let output_type = match JsonSchema::try_from(output_type) {
+        // every message sent by a server streaming method is an item of the list
+        Ok(output_type) if operation.is_server_streaming() => {
+            Valid::succeed(JsonSchema::Arr(Box::new(output_type)))
+        }
         Ok(output_type) => Valid::succeed(output_type),
         Err(e) => Valid::from_validation_err(BlueprintError::from_validation_string(e)),
     };
@@ -225,6 +229,7 @@ pub fn compile_grpc(inputs: CompileGrpc) -> Valid<IR, BlueprintError> {
                 operation,
                 body,
                 operation_type: operation_type.clone(),
+                page_token: None,
             };
             let on_response = grpc.on_response_body.clone();
             let hook = WorkerHooks::try_new(None, on_response).ok();
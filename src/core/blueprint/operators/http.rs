@@ -1,3 +1,4 @@
+use http::header::HeaderName;
 use tailcall_valid::{Valid, Validator};
 use template_validation::validate_argument;
 
@@ -7,6 +8,7 @@ use crate::core::config::Field;
 use crate::core::endpoint::Endpoint;
 use crate::core::http::{Method, RequestTemplate};
 use crate::core::ir::model::{IO, IR};
+use crate::core::ir::JqExpr;
 use crate::core::worker_hooks::WorkerHooks;
 use crate::core::{config, helpers, Mustache};
 
@@ -85,7 +87,11 @@ pub fn compile_http(
                 Valid::succeed(request_template)
             }
         })
-        .map(|req_template| {
+        .zip(compile_response_transform(
+            http.response_transform.as_deref(),
+        ))
+        .zip(compile_capture_headers(http))
+        .map(|((req_template, response_transform), capture_headers)| {
             // marge http and upstream on_request
             let on_request = http
                 .on_request
@@ -113,6 +119,8 @@ pub fn compile_http(
                     is_list,
                     dedupe,
                     hook,
+                    response_transform,
+                    capture_headers,
                 })
             } else {
                 IR::IO(IO::Http {
@@ -122,6 +130,8 @@ pub fn compile_http(
                     is_list,
                     dedupe,
                     hook,
+                    response_transform,
+                    capture_headers,
                 })
             };
             (io, &http.select)
@@ -129,6 +139,69 @@ pub fn compile_http(
         .and_then(apply_select)
 }
 
+fn compile_response_transform(expr: Option<&str>) -> Valid<Option<JqExpr>, BlueprintError> {
+    match expr.map(JqExpr::parse).transpose() {
+        Ok(response_transform) => Valid::succeed(response_transform),
+        Err(e) => Valid::fail(BlueprintError::Error(e)).trace("responseTransform"),
+    }
+}
+
+/// Parses the names of the captured response headers and checks that the
+/// headers read by the `select` are captured.
+fn compile_capture_headers(http: &config::Http) -> Valid<Vec<HeaderName>, BlueprintError> {
+    Valid::from_iter(
+        http.capture_headers.iter(),
+        |name| match HeaderName::from_bytes(name.as_bytes()) {
+            Ok(name) => Valid::succeed(name),
+            Err(e) => Valid::fail(BlueprintError::InvalidHeaderName(e)),
+        },
+    )
+    .trace("captureHeaders")
+    .and_then(|capture_headers| {
+        let mut selected = vec![];
+        if let Some(select) = &http.select {
+            collect_response_headers(select, &mut selected);
+        }
+
+        Valid::from_iter(selected, |name| {
+            if capture_headers.iter().any(|header| header.as_str() == name) {
+                Valid::succeed(())
+            } else {
+                Valid::fail(BlueprintError::ResponseHeaderNotCaptured(name))
+            }
+        })
+        .trace("select")
+        .map_to(capture_headers)
+    })
+}
+
+/// Collects the lowercased names of the response headers read as
+/// `{{.response.headers.<name>}}` in the JSON value.
+pub(super) fn collect_response_headers(json: &serde_json::Value, names: &mut Vec<String>) {
+    match json {
+        serde_json::Value::Array(arr) => {
+            for v in arr {
+                collect_response_headers(v, names)
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            for (_, v) in obj {
+                collect_response_headers(v, names)
+            }
+        }
+        serde_json::Value::String(s) => {
+            for parts in Mustache::parse(s).expression_segments() {
+                if let [head, kind, name, ..] = parts.as_slice() {
+                    if head == "response" && kind == "headers" {
+                        names.push(name.to_lowercase());
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Count the number of dynamic expressions in the JSON value.
 fn count_dynamic_paths(json: &serde_json::Value) -> usize {
     let mut count = 0;
@@ -156,6 +229,7 @@ fn count_dynamic_paths(json: &serde_json::Value) -> usize {
 #[cfg(test)]
 mod test {
     use serde_json::json;
+    use tailcall_valid::ValidationError;
 
     use super::*;
 
@@ -181,4 +255,30 @@ mod test {
         let keys = count_dynamic_paths(&json);
         assert_eq!(keys, 1);
     }
+
+    #[test]
+    fn test_capture_headers() {
+        let http = config::Http {
+            capture_headers: vec!["ETag".to_string()],
+            select: Some(json!({"etag": "{{.response.headers.ETag}}"})),
+            ..Default::default()
+        };
+        let actual = compile_capture_headers(&http).to_result().unwrap();
+        assert_eq!(actual, vec![HeaderName::from_static("etag")]);
+    }
+
+    #[test]
+    fn test_select_header_not_captured() {
+        let http = config::Http {
+            capture_headers: vec!["link".to_string()],
+            select: Some(json!({"etag": "{{.response.headers.etag}}"})),
+            ..Default::default()
+        };
+        let actual = compile_capture_headers(&http).to_result().unwrap_err();
+        let expected = ValidationError::new(BlueprintError::ResponseHeaderNotCaptured(
+            "etag".to_string(),
+        ))
+        .trace("select");
+        assert_eq!(actual, expected);
+    }
 }
@@ -11,6 +11,7 @@ pub struct CompileJs<'a> {
 
 pub fn compile_js(inputs: CompileJs) -> Valid<IR, BlueprintError> {
     let name = &inputs.js.name;
+    let dedupe = inputs.js.dedupe.unwrap_or_default();
     Valid::from_option(inputs.script.as_ref(), BlueprintError::ScriptIsRequired)
-        .map(|_| IR::IO(IO::Js { name: name.to_string() }))
+        .map(|_| IR::IO(IO::Js { name: name.to_string(), dedupe }))
 }
@@ -1,10 +1,10 @@
 use std::collections::{BTreeMap, BTreeSet, HashSet};
-use std::net::{AddrParseError, IpAddr};
+use std::net::{AddrParseError, IpAddr, Ipv4Addr, Ipv6Addr};
 use std::str::FromStr;
 use std::time::Duration;
 
 use derive_setters::Setters;
-use http::header::{HeaderMap, HeaderName, HeaderValue};
+use http::header::{HeaderMap, HeaderName, HeaderValue, SET_COOKIE};
 use rustls_pki_types::CertificateDer;
 use tailcall_valid::{Valid, ValidationError, Validator};
 
@@ -23,6 +23,8 @@ pub struct Server {
     pub enable_batch_requests: bool,
     pub enable_showcase: bool,
     pub global_response_timeout: i64,
+    /// Time given to the in-flight requests to complete on shutdown
+    pub shutdown_timeout: Option<Duration>,
     pub worker: usize,
     pub port: u16,
     pub hostname: IpAddr,
@@ -34,6 +36,20 @@ pub struct Server {
     pub cors: Option<Cors>,
     pub experimental_headers: HashSet<HeaderName>,
     pub routes: Routes,
+    pub rate_limit: Option<RateLimit>,
+    /// Maximum size of the request body in bytes
+    pub max_request_body_bytes: Option<usize>,
+    /// URL of the Redis used to cache the results of the `@cache` fields
+    pub redis_url: Option<String>,
+}
+
+/// Maximum number of requests a single client can make within a window.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u64,
+    pub window: Duration,
+    /// Requests allowed on top of `requests` in a short burst
+    pub burst: u64,
 }
 
 /// Mimic of mini_v8::Script that's wasm compatible
@@ -49,6 +65,10 @@ pub enum Http {
     HTTP2 {
         cert: Vec<CertificateDer<'static>>,
         key: PrivateKey,
+        /// CA certificates used to verify the client certificates
+        client_ca: Option<Vec<CertificateDer<'static>>>,
+        /// Advertises `http/1.1` along with `h2` in ALPN
+        alpn_fallback: bool,
     },
 }
 
@@ -78,6 +98,14 @@ impl Server {
     pub fn get_experimental_headers(&self) -> HashSet<HeaderName> {
         self.experimental_headers.clone()
     }
+
+    pub fn get_shutdown_timeout(&self) -> Option<Duration> {
+        self.shutdown_timeout
+    }
+
+    pub fn get_max_request_body_bytes(&self) -> Option<usize> {
+        self.max_request_body_bytes
+    }
 }
 
 impl TryFrom<crate::core::config::ConfigModule> for Server {
@@ -88,25 +116,26 @@ impl TryFrom<crate::core::config::ConfigModule> for Server {
 
         let http_server = match config_server.clone().get_version() {
             HttpVersion::HTTP2 => {
-                if config_module.extensions().cert.is_empty() {
-                    return Valid::fail(BlueprintError::CertificateIsRequiredForHTTP2).to_result();
-                }
-
-                let cert = config_module.extensions().cert.clone();
-
-                let key = config_module
-                    .extensions()
-                    .keys
-                    .first()
-                    .ok_or_else(|| ValidationError::new(BlueprintError::KeyIsRequiredForHTTP2))?
-                    .clone();
+                let (cert, key) = to_tls(
+                    &config_module,
+                    BlueprintError::CertificateIsRequiredForHTTP2,
+                    BlueprintError::KeyIsRequiredForHTTP2,
+                )?;
+                let client_ca = to_client_ca(&config_module)?;
 
-                Valid::succeed(Http::HTTP2 { cert, key })
+                Valid::succeed(Http::HTTP2 {
+                    cert,
+                    key,
+                    client_ca,
+                    alpn_fallback: config_server.get_alpn_fallback(),
+                })
             }
             _ => Valid::succeed(Http::HTTP1),
         };
 
-        validate_hostname((config_server).get_hostname().to_lowercase())
+        let prefer_ipv6 = config_server.get_prefer_ipv6();
+
+        validate_hostname((config_server).get_hostname().to_lowercase(), prefer_ipv6)
             .fuse(http_server)
             .fuse(handle_response_headers(
                 (config_server).get_response_headers(),
@@ -114,6 +143,7 @@ impl TryFrom<crate::core::config::ConfigModule> for Server {
             .fuse(to_script(&config_module))
             .fuse(handle_experimental_headers(
                 (config_server).get_experimental_headers(),
+                config_server.get_experimental_prefix(),
             ))
             .fuse(validate_cors(
                 config_server
@@ -121,8 +151,23 @@ impl TryFrom<crate::core::config::ConfigModule> for Server {
                     .as_ref()
                     .and_then(|headers| headers.get_cors()),
             ))
+            .fuse(
+                validate_shutdown_timeout(config_server.shutdown_timeout)
+                    .zip(validate_rate_limit(config_server.rate_limit.clone()))
+                    .zip(validate_max_request_body_bytes(
+                        config_server.max_request_body_bytes,
+                    )),
+            )
             .map(
-                |(hostname, http, response_headers, script, experimental_headers, cors)| Server {
+                |(
+                    hostname,
+                    http,
+                    response_headers,
+                    script,
+                    experimental_headers,
+                    cors,
+                    ((shutdown_timeout, rate_limit), max_request_body_bytes),
+                )| Server {
                     enable_apollo_tracing: (config_server).enable_apollo_tracing(),
                     enable_cache_control_header: (config_server).enable_cache_control(),
                     enable_set_cookie_header: (config_server).enable_set_cookies(),
@@ -133,6 +178,7 @@ impl TryFrom<crate::core::config::ConfigModule> for Server {
                     enable_showcase: (config_server).enable_showcase(),
                     experimental_headers,
                     global_response_timeout: (config_server).get_global_response_timeout(),
+                    shutdown_timeout,
                     http,
                     worker: (config_server).get_workers(),
                     port: (config_server).get_port(),
@@ -143,12 +189,89 @@ impl TryFrom<crate::core::config::ConfigModule> for Server {
                     script,
                     cors,
                     routes: config_server.get_routes(),
+                    rate_limit,
+                    max_request_body_bytes,
+                    redis_url: config_server.redis_url.clone(),
                 },
             )
             .to_result()
     }
 }
 
+/// Resolves the certificate and the key required by the HTTP versions that
+/// are served over TLS.
+fn to_tls(
+    config_module: &ConfigModule,
+    cert_error: BlueprintError,
+    key_error: BlueprintError,
+) -> Result<(Vec<CertificateDer<'static>>, PrivateKey), ValidationError<BlueprintError>> {
+    if config_module.extensions().cert.is_empty() {
+        return Valid::fail(cert_error).to_result();
+    }
+
+    let cert = config_module.extensions().cert.clone();
+
+    let key = config_module
+        .extensions()
+        .keys
+        .first()
+        .ok_or_else(|| ValidationError::new(key_error))?
+        .clone();
+
+    Ok((cert, key))
+}
+
+/// Parses the CA bundle used to verify the client certificates, if any.
+fn to_client_ca(
+    config_module: &ConfigModule,
+) -> Result<Option<Vec<CertificateDer<'static>>>, ValidationError<BlueprintError>> {
+    let Some(client_ca) = config_module.extensions().client_ca.as_ref() else {
+        return Ok(None);
+    };
+
+    let certs = rustls_pemfile::certs(&mut client_ca.as_bytes())
+        .map_err(|e| ValidationError::new(BlueprintError::InvalidClientCA(e.to_string())))?;
+
+    if certs.is_empty() {
+        return Valid::fail(BlueprintError::InvalidClientCA(
+            "no certificates found".to_string(),
+        ))
+        .to_result();
+    }
+
+    let certs = certs
+        .into_iter()
+        .map(CertificateDer::from)
+        .collect::<Vec<_>>();
+    validate_client_ca(&certs)?;
+
+    Ok(Some(certs))
+}
+
+/// Adds the CA certificates to a root store the same way the server does, so
+/// the malformed ones are reported before the server starts.
+#[cfg(feature = "cli")]
+fn validate_client_ca(
+    certs: &[CertificateDer<'static>],
+) -> Result<(), ValidationError<BlueprintError>> {
+    let mut roots = rustls::RootCertStore::empty();
+    for cert in certs {
+        roots
+            .add(cert.clone())
+            .map_err(|e| ValidationError::new(BlueprintError::InvalidClientCA(e.to_string())))?;
+    }
+
+    Ok(())
+}
+
+/// The client certificates are only verified by the CLI server.
+#[cfg(not(feature = "cli"))]
+fn validate_client_ca(
+    _certs: &[CertificateDer<'static>],
+) -> Result<(), ValidationError<BlueprintError>> {
+    Ok(())
+}
+
 fn to_script(
     config_module: &crate::core::config::ConfigModule,
 ) -> Valid<Option<Script>, BlueprintError> {
@@ -176,10 +299,73 @@ fn validate_cors(cors: Option<config::cors::Cors>) -> Valid<Option<Cors>, Bluepr
         .trace("schema")
 }
 
-fn validate_hostname(hostname: String) -> Valid<IpAddr, BlueprintError> {
+fn validate_shutdown_timeout(timeout: Option<i64>) -> Valid<Option<Duration>, BlueprintError> {
+    match timeout {
+        Some(timeout) if timeout < 0 => {
+            Valid::fail(BlueprintError::NegativeShutdownTimeout(timeout))
+                .trace("shutdownTimeout")
+                .trace("@server")
+                .trace("schema")
+        }
+        timeout => Valid::succeed(timeout.map(|timeout| Duration::from_millis(timeout as u64))),
+    }
+}
+
+fn validate_rate_limit(
+    rate_limit: Option<config::RateLimit>,
+) -> Valid<Option<RateLimit>, BlueprintError> {
+    let Some(rate_limit) = rate_limit else {
+        return Valid::succeed(None);
+    };
+
+    Valid::<(), BlueprintError>::fail(BlueprintError::InvalidRateLimitWindow(rate_limit.window))
+        .when(|| rate_limit.window == 0)
+        .trace("window")
+        .and(
+            Valid::<(), BlueprintError>::fail(BlueprintError::InvalidRateLimitRequests(
+                rate_limit.requests,
+            ))
+            .when(|| rate_limit.requests == 0)
+            .trace("requests"),
+        )
+        .map(|_| {
+            Some(RateLimit {
+                requests: rate_limit.requests,
+                window: Duration::from_millis(rate_limit.window),
+                burst: rate_limit.burst.unwrap_or_default(),
+            })
+        })
+        .trace("rateLimit")
+        .trace("@server")
+        .trace("schema")
+}
+
+fn validate_max_request_body_bytes(
+    max_request_body_bytes: Option<usize>,
+) -> Valid<Option<usize>, BlueprintError> {
+    match max_request_body_bytes {
+        Some(0) => Valid::fail(BlueprintError::InvalidMaxRequestBodyBytes(0))
+            .trace("maxRequestBodyBytes")
+            .trace("@server")
+            .trace("schema"),
+        max_request_body_bytes => Valid::succeed(max_request_body_bytes),
+    }
+}
+
+fn validate_hostname(hostname: String, prefer_ipv6: bool) -> Valid<IpAddr, BlueprintError> {
     if hostname == "localhost" {
-        Valid::succeed(IpAddr::from([127, 0, 0, 1]))
+        if prefer_ipv6 {
+            Valid::succeed(IpAddr::V6(Ipv6Addr::LOCALHOST))
+        } else {
+            Valid::succeed(IpAddr::V4(Ipv4Addr::LOCALHOST))
+        }
     } else {
+        // IPv6 literals could be written in brackets, as in URLs
+        let hostname = hostname
+            .strip_prefix('[')
+            .and_then(|hostname| hostname.strip_suffix(']'))
+            .unwrap_or(&hostname);
+
         Valid::from(
             hostname.parse().map_err(|e: AddrParseError| {
                 ValidationError::new(BlueprintError::ParsingFailed(e))
@@ -194,8 +380,15 @@ fn validate_hostname(hostname: String) -> Valid<IpAddr, BlueprintError> {
 fn handle_response_headers(
     resp_headers: Vec<(String, String)>,
 ) -> Valid<HeaderMap, BlueprintError> {
+    let mut names = HashSet::new();
+
     Valid::from_iter(resp_headers.iter(), |(k, v)| {
         let name = match HeaderName::from_bytes(k.as_bytes()) {
+            // only headers that can't be combined into a single value are
+            // allowed to be repeated
+            Ok(name) if !names.insert(name.clone()) && name != SET_COOKIE => {
+                Valid::fail(BlueprintError::DuplicateResponseHeader(name.to_string()))
+            }
             Ok(name) => Valid::succeed(name),
             Err(e) => Valid::fail(BlueprintError::InvalidHeaderName(e)),
         };
@@ -216,9 +409,10 @@ fn handle_response_headers(
 
 fn handle_experimental_headers(
     headers: BTreeSet<String>,
+    require_prefix: bool,
 ) -> Valid<HashSet<HeaderName>, BlueprintError> {
     Valid::from_iter(headers.iter(), |h| {
-        if !h.to_lowercase().starts_with("x-") {
+        if require_prefix && !h.to_lowercase().starts_with("x-") {
             Valid::fail(BlueprintError::ExperimentalHeaderInvalidFormat(h.clone()))
         } else {
             match HeaderName::from_str(h) {
@@ -236,11 +430,250 @@ fn handle_experimental_headers(
 
 #[cfg(test)]
 mod tests {
-    use crate::core::config::ConfigModule;
+    use std::collections::{BTreeSet, HashSet};
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use std::time::Duration;
+
+    use http::header::HeaderName;
+    use rustls_pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+    use tailcall_valid::{ValidationError, Validator};
+
+    use super::{handle_experimental_headers, handle_response_headers, validate_hostname, Http};
+    use crate::core::blueprint::BlueprintError;
+    use crate::core::config::{
+        Config, ConfigModule, Extensions, HttpVersion, PrivateKey, RateLimit,
+    };
 
     #[test]
     fn test_try_from_default() {
         let actual = super::Server::try_from(ConfigModule::default());
         assert!(actual.is_ok())
     }
+
+    #[test]
+    fn test_malformed_client_ca() {
+        let mut config = Config::default();
+        config.server.version = Some(HttpVersion::HTTP2);
+
+        let extensions = Extensions {
+            cert: vec![CertificateDer::from(vec![0])],
+            keys: vec![PrivateKey::from(PrivateKeyDer::Pkcs8(
+                PrivatePkcs8KeyDer::from(vec![0]),
+            ))],
+            client_ca: Some("not a certificate".to_string()),
+            ..Default::default()
+        };
+        let config_module = ConfigModule::from(config).set_extensions(extensions);
+
+        let actual = super::Server::try_from(config_module).unwrap_err();
+        let expected = ValidationError::new(BlueprintError::InvalidClientCA(
+            "no certificates found".to_string(),
+        ));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "cli")]
+    fn test_malformed_client_ca_der() {
+        let mut config = Config::default();
+        config.server.version = Some(HttpVersion::HTTP2);
+
+        let extensions = Extensions {
+            cert: vec![CertificateDer::from(vec![0])],
+            keys: vec![PrivateKey::from(PrivateKeyDer::Pkcs8(
+                PrivatePkcs8KeyDer::from(vec![0]),
+            ))],
+            // valid PEM wrapping a malformed DER certificate
+            client_ca: Some(
+                "-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----\n".to_string(),
+            ),
+            ..Default::default()
+        };
+        let config_module = ConfigModule::from(config).set_extensions(extensions);
+
+        let actual = super::Server::try_from(config_module).unwrap_err();
+        let error = rustls::Error::InvalidCertificate(rustls::CertificateError::BadEncoding);
+        let expected = ValidationError::new(BlueprintError::InvalidClientCA(error.to_string()));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_alpn_fallback() {
+        let to_alpn_fallback = |alpn_fallback: Option<bool>| {
+            let mut config = Config::default();
+            config.server.version = Some(HttpVersion::HTTP2);
+            config.server.alpn_fallback = alpn_fallback;
+
+            let extensions = Extensions {
+                cert: vec![CertificateDer::from(vec![0])],
+                keys: vec![PrivateKey::from(PrivateKeyDer::Pkcs8(
+                    PrivatePkcs8KeyDer::from(vec![0]),
+                ))],
+                ..Default::default()
+            };
+            let config_module = ConfigModule::from(config).set_extensions(extensions);
+
+            match super::Server::try_from(config_module).unwrap().http {
+                Http::HTTP2 { alpn_fallback, .. } => alpn_fallback,
+                _ => panic!("Expected HTTP2"),
+            }
+        };
+
+        assert!(to_alpn_fallback(None));
+        assert!(to_alpn_fallback(Some(true)));
+        assert!(!to_alpn_fallback(Some(false)));
+    }
+
+    #[test]
+    fn test_duplicate_response_headers() {
+        let headers = vec![
+            ("Cache-Control".to_string(), "max-age=60".to_string()),
+            ("cache-control".to_string(), "no-cache".to_string()),
+        ];
+        let actual = handle_response_headers(headers).to_result();
+        assert!(actual.is_err_and(|e| e
+            .to_string()
+            .contains("Response header 'cache-control' is specified more than once")));
+
+        let headers = vec![
+            ("Set-Cookie".to_string(), "a=1".to_string()),
+            ("Set-Cookie".to_string(), "b=2".to_string()),
+        ];
+        let actual = handle_response_headers(headers).to_result().unwrap();
+        assert_eq!(actual.get_all("set-cookie").iter().count(), 2);
+    }
+
+    #[test]
+    fn test_experimental_headers_with_prefix() {
+        let headers = BTreeSet::from(["x-trace-id".to_string()]);
+        let actual = handle_experimental_headers(headers, true).to_result();
+        assert_eq!(
+            actual,
+            Ok(HashSet::from([HeaderName::from_static("x-trace-id")]))
+        );
+
+        let headers = BTreeSet::from(["my-trace-id".to_string()]);
+        let actual = handle_experimental_headers(headers, true).to_result();
+        assert!(actual.is_err_and(|e| e
+            .to_string()
+            .contains("Experimental headers must start with 'x-' or 'X-'. Got: 'my-trace-id'")));
+    }
+
+    #[test]
+    fn test_experimental_headers_without_prefix() {
+        let headers = BTreeSet::from(["My-Trace-Id".to_string()]);
+        let actual = handle_experimental_headers(headers, false).to_result();
+        assert_eq!(
+            actual,
+            Ok(HashSet::from([HeaderName::from_static("my-trace-id")]))
+        );
+
+        // names are validated regardless of the prefix
+        for require_prefix in [true, false] {
+            let headers = BTreeSet::from(["x-trace id".to_string()]);
+            let actual = handle_experimental_headers(headers, require_prefix).to_result();
+            assert!(actual.is_err());
+        }
+    }
+
+    #[test]
+    fn test_shutdown_timeout() {
+        let server = super::Server::try_from(ConfigModule::default()).unwrap();
+        assert_eq!(server.get_shutdown_timeout(), None);
+
+        let mut config = Config::default();
+        config.server.shutdown_timeout = Some(5000);
+
+        let server = super::Server::try_from(ConfigModule::from(config)).unwrap();
+        assert_eq!(server.get_shutdown_timeout(), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_negative_shutdown_timeout() {
+        let mut config = Config::default();
+        config.server.shutdown_timeout = Some(-1);
+
+        let actual = super::Server::try_from(ConfigModule::from(config));
+        assert!(actual.is_err_and(|e| e
+            .to_string()
+            .contains("Shutdown timeout must be non-negative. Got: -1")));
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let mut config = Config::default();
+        config.server.rate_limit = Some(RateLimit { requests: 100, window: 1000, burst: Some(10) });
+
+        let server = super::Server::try_from(ConfigModule::from(config)).unwrap();
+        assert_eq!(
+            server.rate_limit,
+            Some(super::RateLimit { requests: 100, window: Duration::from_secs(1), burst: 10 })
+        );
+    }
+
+    #[test]
+    fn test_zero_rate_limit_window() {
+        let mut config = Config::default();
+        config.server.rate_limit = Some(RateLimit { requests: 100, window: 0, burst: None });
+
+        let actual = super::Server::try_from(ConfigModule::from(config)).unwrap_err();
+        let expected = ValidationError::new(BlueprintError::InvalidRateLimitWindow(0))
+            .trace("window")
+            .trace("rateLimit")
+            .trace("@server")
+            .trace("schema");
+
+        assert_eq!(actual, expected);
+        assert!(actual
+            .to_string()
+            .contains("Rate limit window must be positive. Got: 0"));
+    }
+
+    #[test]
+    fn test_max_request_body_bytes() {
+        let server = super::Server::try_from(ConfigModule::default()).unwrap();
+        assert_eq!(server.get_max_request_body_bytes(), None);
+
+        let mut config = Config::default();
+        config.server.max_request_body_bytes = Some(1024);
+
+        let server = super::Server::try_from(ConfigModule::from(config)).unwrap();
+        assert_eq!(server.get_max_request_body_bytes(), Some(1024));
+    }
+
+    #[test]
+    fn test_zero_max_request_body_bytes() {
+        let mut config = Config::default();
+        config.server.max_request_body_bytes = Some(0);
+
+        let actual = super::Server::try_from(ConfigModule::from(config));
+        assert!(actual.is_err_and(|e| e
+            .to_string()
+            .contains("Max request body size must be positive. Got: 0")));
+    }
+
+    #[test]
+    fn test_validate_hostname() {
+        let ipv6 = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+        let actual = validate_hostname("::1".to_string(), false).to_result();
+        assert_eq!(actual, Ok(ipv6));
+
+        let actual = validate_hostname("[::1]".to_string(), false).to_result();
+        assert_eq!(actual, Ok(ipv6));
+
+        let actual = validate_hostname("0.0.0.0".to_string(), false).to_result();
+        assert_eq!(actual, Ok(IpAddr::V4(Ipv4Addr::UNSPECIFIED)));
+    }
+
+    #[test]
+    fn test_validate_localhost() {
+        let actual = validate_hostname("localhost".to_string(), false).to_result();
+        assert_eq!(actual, Ok(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+
+        let actual = validate_hostname("localhost".to_string(), true).to_result();
+        assert_eq!(actual, Ok(IpAddr::V6(Ipv6Addr::LOCALHOST)));
+    }
 }
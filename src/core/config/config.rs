@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::{self, Display};
 
 use anyhow::{anyhow, Result};
@@ -17,7 +17,7 @@ use super::directive::Directive;
 use super::from_document::from_document;
 use super::{
     AddField, Alias, Cache, Call, Discriminate, Expr, GraphQL, Grpc, Http, Link, Modify, Omit,
-    Protected, ResolverSet, Server, Telemetry, Upstream, JS,
+    Protected, Resolver, ResolverSet, Server, Telemetry, Upstream, JS,
 };
 use crate::core::config::npo::QueryPath;
 use crate::core::config::source::Source;
@@ -192,6 +192,10 @@ pub struct Field {
     /// Publicly visible documentation for the field.
     pub doc: Option<String>,
 
+    ///
+    /// Marks the field as deprecated with the provided reason.
+    pub deprecation: Option<String>,
+
     ///
     /// Allows modifying existing fields.
     pub modify: Option<Modify>,
@@ -271,6 +275,16 @@ impl Field {
     }
 }
 
+/// Name of the built-in GraphQL directive that marks a field as deprecated.
+pub const DEPRECATED_DIRECTIVE: &str = "deprecated";
+
+/// Reason used by GraphQL when `@deprecated` is applied without one.
+pub const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+/// Name of the directive that requires exactly one field of an input type to
+/// be provided.
+pub const ONE_OF_DIRECTIVE: &str = "oneOf";
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Inline {
     pub path: Vec<String>,
@@ -298,11 +312,40 @@ pub struct Enum {
 }
 
 /// Definition of GraphQL value
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, MergeRight)]
+#[derive(Clone, Debug, MergeRight)]
 pub struct Variant {
     pub name: String,
     // directive: alias
     pub alias: Option<Alias>,
+    pub doc: Option<String>,
+}
+
+// The doc doesn't take part in the comparison, so variants that differ only in
+// their description aren't kept twice in the enum.
+impl Variant {
+    fn key(&self) -> (&String, &Option<Alias>) {
+        (&self.name, &self.alias)
+    }
+}
+
+impl PartialEq for Variant {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Variant {}
+
+impl PartialOrd for Variant {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Variant {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
@@ -391,6 +434,27 @@ impl Config {
         self.enums.get(name)
     }
 
+    /// Checks if an `@http` of the config captures the response header.
+    pub fn captures_response_header(&self, name: &str) -> bool {
+        self.types
+            .values()
+            .flat_map(|type_of| {
+                type_of.resolvers.0.iter().chain(
+                    type_of
+                        .fields
+                        .values()
+                        .flat_map(|field| field.resolvers.0.iter()),
+                )
+            })
+            .any(|resolver| match resolver {
+                Resolver::Http(http) => http
+                    .capture_headers
+                    .iter()
+                    .any(|header| header.eq_ignore_ascii_case(name)),
+                _ => false,
+            })
+    }
+
     /// Renders current config to graphQL string
     pub fn to_sdl(&self) -> String {
         crate::core::document::print(self.into())
@@ -475,10 +539,48 @@ impl Config {
             .filter(|(_, arg)| !self.is_scalar(arg.type_of.name()))
             .map(|(_, arg)| arg.type_of.name())
             .fold(HashSet::new(), |types, type_of| {
-                self.find_connections(type_of, types)
+                // types that were already reached from other arguments
+                // don't need to be traversed again
+                if types.contains(type_of) {
+                    types
+                } else {
+                    self.find_connections(type_of, types)
+                }
             })
     }
 
+    /// Returns the groups of types that reference each other recursively,
+    /// e.g. `User.posts: [Post]` and `Post.author: User`. Types in a group are
+    /// sorted and a type that references itself forms a group of its own.
+    pub fn detect_cycles(&self) -> Vec<Vec<String>> {
+        let mut detector = CycleDetector::default();
+
+        for name in self.types.keys().chain(self.unions.keys()) {
+            if !detector.index.contains_key(name.as_str()) {
+                detector.visit(self, name);
+            }
+        }
+
+        detector.cycles
+    }
+
+    /// Types directly referenced by the fields of the type or the members of
+    /// the union.
+    fn referenced_types<'a>(&'a self, type_of: &'a str) -> Vec<&'a str> {
+        if let Some(union_) = self.find_union(type_of) {
+            union_.types.iter().map(String::as_str).collect()
+        } else if let Some(type_) = self.find_type(type_of) {
+            type_
+                .fields
+                .values()
+                .map(|field| field.type_of.name().as_str())
+                .filter(|name| self.types.contains_key(*name) || self.unions.contains_key(*name))
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+
     /// finds the all types which are present in union.
     pub fn union_types(&self) -> HashSet<String> {
         self.unions
@@ -569,22 +671,31 @@ impl Config {
             .flat_map(|(_, field)| field.args.iter())
             .collect::<Vec<_>>()
     }
-    /// Removes all types that are passed in the set
+    /// Removes all types, unions and enums that are passed in the set
     pub fn remove_types(mut self, types: HashSet<String>) -> Self {
         for unused_type in types {
             self.types.remove(&unused_type);
             self.unions.remove(&unused_type);
+            self.enums.remove(&unused_type);
         }
 
         self
     }
 
+    /// Removes the types, unions and enums that aren't reachable from the
+    /// query and mutation roots.
+    pub fn prune_unused(self) -> Self {
+        let unused_types = self.unused_types();
+        self.remove_types(unused_types)
+    }
+
     pub fn unused_types(&self) -> HashSet<String> {
         let used_types = self.get_all_used_type_names();
         let all_types: HashSet<String> = self
             .types
             .keys()
             .chain(self.unions.keys())
+            .chain(self.enums.keys())
             .cloned()
             .collect();
         all_types.difference(&used_types).cloned().collect()
@@ -600,6 +711,9 @@ impl Config {
         if let Some(mutation) = &self.schema.mutation {
             stack.push(mutation.clone());
         }
+        if let Some(subscription) = &self.schema.subscription {
+            stack.push(subscription.clone());
+        }
         while let Some(type_name) = stack.pop() {
             if set.contains(&type_name) {
                 continue;
@@ -618,6 +732,8 @@ impl Config {
                 for interface in typ.implements.iter() {
                     stack.push(interface.clone())
                 }
+            } else if self.enums.contains_key(&type_name) {
+                set.insert(type_name);
             }
         }
 
@@ -663,6 +779,55 @@ impl Config {
     }
 }
 
+/// Finds strongly connected components of the type graph with Tarjan's
+/// algorithm, so every type is visited once.
+#[derive(Default)]
+struct CycleDetector<'a> {
+    index: HashMap<&'a str, usize>,
+    low_link: HashMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    on_stack: HashSet<&'a str>,
+    cycles: Vec<Vec<String>>,
+}
+
+impl<'a> CycleDetector<'a> {
+    fn visit(&mut self, config: &'a Config, type_of: &'a str) {
+        let index = self.index.len();
+        self.index.insert(type_of, index);
+        self.low_link.insert(type_of, index);
+        self.stack.push(type_of);
+        self.on_stack.insert(type_of);
+
+        let references = config.referenced_types(type_of);
+        for reference in references.iter().copied() {
+            if !self.index.contains_key(reference) {
+                self.visit(config, reference);
+                let low_link = self.low_link[type_of].min(self.low_link[reference]);
+                self.low_link.insert(type_of, low_link);
+            } else if self.on_stack.contains(reference) {
+                let low_link = self.low_link[type_of].min(self.index[reference]);
+                self.low_link.insert(type_of, low_link);
+            }
+        }
+
+        if self.low_link[type_of] == index {
+            let mut component = vec![];
+            while let Some(name) = self.stack.pop() {
+                self.on_stack.remove(name);
+                component.push(name.to_owned());
+                if name == type_of {
+                    break;
+                }
+            }
+
+            if component.len() > 1 || references.contains(&type_of) {
+                component.sort();
+                self.cycles.push(component);
+            }
+        }
+    }
+}
+
 impl From<RuntimeConfig> for Config {
     fn from(config: RuntimeConfig) -> Self {
         Self {
@@ -689,7 +854,7 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     use super::*;
-    use crate::core::config::Resolver;
+    use crate::core::config::{ConfigModule, Resolver};
     use crate::core::directive::DirectiveCodec;
 
     #[test]
@@ -731,6 +896,52 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_field_deprecation() {
+        let config = Config::from_sdl(
+            r#"
+            type Foo {
+                a: Int @deprecated(reason: "use b")
+                b: Int
+                c: Int @deprecated
+            }
+            "#,
+        )
+        .to_result()
+        .unwrap();
+
+        let foo = config.find_type("Foo").unwrap();
+        assert_eq!(foo.fields["a"].deprecation.as_deref(), Some("use b"));
+        assert_eq!(foo.fields["b"].deprecation, None);
+        assert_eq!(
+            foo.fields["c"].deprecation.as_deref(),
+            Some(DEFAULT_DEPRECATION_REASON)
+        );
+
+        let actual = Config::from_sdl(&config.to_sdl()).to_result().unwrap();
+        assert_eq!(actual.find_type("Foo"), Some(foo));
+    }
+
+    #[test]
+    fn test_merge_enum_variants_with_doc() {
+        let variant = |doc: Option<&str>| Variant {
+            name: "ACTIVE".to_string(),
+            alias: None,
+            doc: doc.map(|doc| doc.to_string()),
+        };
+        let left = Enum { variants: BTreeSet::from([variant(None)]), doc: None };
+        let right = Enum {
+            variants: BTreeSet::from([variant(Some("Active user"))]),
+            doc: None,
+        };
+
+        let actual = left.merge_right(right);
+        let variants = actual.variants.into_iter().collect::<Vec<_>>();
+
+        assert_eq!(variants.len(), 1);
+        assert_eq!(variants[0].doc.as_deref(), Some("Active user"));
+    }
+
     #[test]
     fn test_unused_types_with_cyclic_types() {
         let config = Config::from_sdl(
@@ -757,6 +968,141 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_unused_enums_and_unions() {
+        let config = Config::from_sdl(
+            "
+            enum Status {ACTIVE INACTIVE}
+            enum Unused {A B}
+            input Filter {status: Status}
+
+            type Foo {a: Int}
+            type Bar {b: Int}
+            union FooBar = Foo | Bar
+            union Orphan = Foo
+
+            type Query {
+                foos(filter: Filter): [FooBar]
+            }
+
+            schema {
+                query: Query
+            }
+            ",
+        )
+        .to_result()
+        .unwrap();
+
+        let actual = config.unused_types();
+        let expected = HashSet::from(["Unused".to_string(), "Orphan".to_string()]);
+
+        assert_eq!(actual, expected);
+    }
+
+    fn prune_unused(sdl: &str) -> Config {
+        Config::from_sdl(sdl).to_result().unwrap().prune_unused()
+    }
+
+    #[test]
+    fn test_prune_unused_type() {
+        let config = prune_unused(
+            "
+            type Foo {a: Int}
+            type Orphan {b: Int}
+
+            type Query {
+                foo: Foo
+            }
+
+            schema {
+                query: Query
+            }
+            ",
+        );
+
+        assert!(config.find_type("Foo").is_some());
+        assert!(config.find_type("Orphan").is_none());
+    }
+
+    #[test]
+    fn test_prune_unused_enum() {
+        let config = prune_unused(
+            "
+            enum Status {ACTIVE INACTIVE}
+            enum Orphan {A B}
+            input Filter {status: Status}
+
+            type Query {
+                foo(filter: Filter): Int
+            }
+
+            schema {
+                query: Query
+            }
+            ",
+        );
+
+        assert!(config.find_enum("Status").is_some());
+        assert!(config.find_type("Filter").is_some());
+        assert!(config.find_enum("Orphan").is_none());
+    }
+
+    #[test]
+    fn test_prune_unused_union() {
+        let config = prune_unused(
+            "
+            type Foo {a: Int}
+            type Bar {b: Int}
+            union FooBar = Foo | Bar
+            union Orphan = Foo
+
+            type Mutation {
+                foobar: FooBar
+            }
+
+            type Query {
+                foo: Foo
+            }
+
+            schema {
+                query: Query
+                mutation: Mutation
+            }
+            ",
+        );
+
+        assert!(config.find_union("FooBar").is_some());
+        assert!(config.find_type("Bar").is_some());
+        assert!(config.find_union("Orphan").is_none());
+    }
+
+    #[test]
+    fn test_prune_unused_subscription() {
+        let config = prune_unused(
+            "
+            type Price {value: Int}
+            type Orphan {b: Int}
+
+            type Subscription {
+                price: Price
+            }
+
+            type Query {
+                foo: Int
+            }
+
+            schema {
+                query: Query
+                subscription: Subscription
+            }
+            ",
+        );
+
+        assert!(config.find_type("Subscription").is_some());
+        assert!(config.find_type("Price").is_some());
+        assert!(config.find_type("Orphan").is_none());
+    }
+
     #[test]
     fn test_is_root_operation_type_with_query() {
         let mut config = Config::default();
@@ -858,4 +1204,61 @@ mod tests {
 
         assert_eq!(interfaces_types_map, expected_union_types);
     }
+
+    #[test]
+    fn test_detect_cycles() {
+        let config = Config::from_sdl(
+            "
+            type User {posts: [Post], name: String}
+            type Post {author: User}
+            type Node {parent: Node}
+            type Bar {a: Int}
+
+            type Query {
+                users: [User]
+            }
+
+            schema {
+                query: Query
+            }
+            ",
+        )
+        .to_result()
+        .unwrap();
+
+        let mut actual = config.detect_cycles();
+        actual.sort();
+
+        assert_eq!(
+            actual,
+            vec![
+                vec!["Node".to_string()],
+                vec!["Post".to_string(), "User".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_used_as_input_and_output() {
+        let config = Config::from_sdl(
+            "
+            type Address {city: String}
+            type User {name: String, address: Address}
+
+            type Query {
+                users(address: Address): [User]
+            }
+
+            schema {
+                query: Query
+            }
+            ",
+        )
+        .to_result()
+        .unwrap();
+        let config_module = ConfigModule::from(config);
+
+        assert!(config_module.input_types().contains("Address"));
+        assert!(config_module.output_types().contains("Address"));
+    }
 }
@@ -1,10 +1,12 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::ops::Deref;
+use std::time::Duration;
 
 use jsonwebtoken::jwk::JwkSet;
 use prost_reflect::prost_types::{FileDescriptorProto, FileDescriptorSet};
 use rustls_pki_types::{CertificateDer, PrivateKeyDer};
 use tailcall_valid::{Valid, Validator};
+use url::Url;
 
 use crate::core::config::Config;
 use crate::core::macros::MergeRight;
@@ -63,6 +65,28 @@ impl ConfigModule {
         self
     }
 
+    /// Adds a htpasswd file used by the basic auth provider. Extensions don't
+    /// affect the computed input and output types, so they're not recomputed.
+    pub fn with_htpasswd(mut self, htpasswd: Content<String>) -> Self {
+        self.extensions.htpasswd.push(htpasswd);
+        self
+    }
+
+    /// Adds a JWKS used by the JWT auth provider.
+    pub fn with_jwks(mut self, jwks: Content<JwkSet>) -> Self {
+        self.extensions.jwks.push(Content {
+            id: jwks.id,
+            content: JwksSource { set: jwks.content, refresh: None },
+        });
+        self
+    }
+
+    /// Sets the contents of the JS file.
+    pub fn with_script(mut self, script: String) -> Self {
+        self.extensions.script = Some(script);
+        self
+    }
+
     pub fn config(&self) -> &Config {
         &self.cache.config
     }
@@ -141,12 +165,33 @@ pub struct Extensions {
     /// Contains the key used on HTTP2 with TLS
     pub keys: Vec<PrivateKey>,
 
+    /// Contains the CA bundle used to verify the client certificates on HTTP2
+    /// with TLS
+    pub client_ca: Option<String>,
+
     /// Contains the endpoints
     pub endpoint_set: EndpointSet<Unchecked>,
 
     pub htpasswd: Vec<Content<String>>,
 
-    pub jwks: Vec<Content<JwkSet>>,
+    pub jwks: Vec<Content<JwksSource>>,
+}
+
+/// JWKS imported with `@link` along with the options to fetch it again from
+/// its source.
+#[derive(Clone, Debug)]
+pub struct JwksSource {
+    pub set: JwkSet,
+    pub refresh: Option<JwksRefresh>,
+}
+
+/// Options to fetch the JWKS again periodically, see
+/// [`Link::refresh_interval`](super::Link::refresh_interval).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JwksRefresh {
+    pub url: Url,
+    pub interval: Duration,
+    pub grace_period: Duration,
 }
 
 impl Extensions {
@@ -168,7 +213,19 @@ impl Extensions {
 
 impl MergeRight for FileDescriptorSet {
     fn merge_right(mut self, other: Self) -> Self {
-        self.file.extend(other.file);
+        // files shared by both sets (e.g. well-known types) are kept only once,
+        // otherwise their symbols would be defined twice
+        let mut names = self
+            .file
+            .iter()
+            .map(|file| file.name().to_string())
+            .collect::<HashSet<_>>();
+        self.file.extend(
+            other
+                .file
+                .into_iter()
+                .filter(|file| names.insert(file.name().to_string())),
+        );
 
         self
     }
@@ -186,3 +243,107 @@ impl From<Config> for ConfigModule {
         ConfigModule { cache: Cache::from(config), ..Default::default() }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use jsonwebtoken::jwk::JwkSet;
+    use prost_reflect::prost_types::{FileDescriptorProto, FileDescriptorSet};
+    use tailcall_valid::Validator;
+
+    use super::{ConfigModule, Content};
+    use crate::core::config::Config;
+    use crate::core::merge_right::MergeRight;
+
+    #[test]
+    fn test_builder() {
+        let config = Config::from_sdl(
+            r#"
+            schema {
+              query: Query
+            }
+
+            input Filter {
+              name: String
+            }
+
+            type Query {
+              users(filter: Filter): [User] @http(url: "http://localhost/users")
+            }
+
+            type User {
+              name: String
+            }
+            "#,
+        )
+        .to_result()
+        .unwrap();
+
+        let config_module = ConfigModule::from(config)
+            .with_htpasswd(Content {
+                id: Some("basic".to_string()),
+                content: "user:{SHA}Y2fEjdGT1W6nsLqtJbGUVeUp9e4=".to_string(),
+            })
+            .with_jwks(Content {
+                id: Some("jwt".to_string()),
+                content: JwkSet { keys: vec![] },
+            })
+            .with_script("function onRequest() {}".to_string());
+
+        let extensions = config_module.extensions();
+        assert_eq!(extensions.htpasswd.len(), 1);
+        assert_eq!(extensions.htpasswd[0].id.as_deref(), Some("basic"));
+        assert_eq!(extensions.jwks.len(), 1);
+        assert_eq!(extensions.jwks[0].id.as_deref(), Some("jwt"));
+        assert_eq!(
+            extensions.script.as_deref(),
+            Some("function onRequest() {}")
+        );
+        assert!(extensions.has_auth());
+
+        assert_eq!(
+            config_module.input_types(),
+            &HashSet::from(["Filter".to_string()])
+        );
+        assert_eq!(
+            config_module.output_types(),
+            &HashSet::from(["Query".to_string(), "User".to_string()])
+        );
+    }
+
+    fn file(name: &str, package: &str) -> FileDescriptorProto {
+        FileDescriptorProto {
+            name: Some(name.to_string()),
+            package: Some(package.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_file_descriptor_sets() {
+        let left = FileDescriptorSet {
+            file: vec![
+                file("google/protobuf/timestamp.proto", "google.protobuf"),
+                file("news.proto", "news"),
+            ],
+        };
+        let right = FileDescriptorSet {
+            file: vec![
+                file("google/protobuf/timestamp.proto", "other"),
+                file("users.proto", "users"),
+            ],
+        };
+
+        let actual = left.merge_right(right);
+        let expected = FileDescriptorSet {
+            file: vec![
+                file("google/protobuf/timestamp.proto", "google.protobuf"),
+                file("news.proto", "news"),
+                file("users.proto", "users"),
+            ],
+        };
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -95,6 +95,7 @@ impl Contravariant for Field {
                 type_of,
                 args,
                 doc: self.doc.merge_right(other.doc),
+                deprecation: self.deprecation.merge_right(other.deprecation),
                 modify: self.modify.merge_right(other.modify),
                 omit: self.omit.merge_right(other.omit),
                 cache: self.cache.merge_right(other.cache),
@@ -117,6 +118,7 @@ impl Covariant for Field {
                 type_of,
                 args,
                 doc: self.doc.merge_right(other.doc),
+                deprecation: self.deprecation.merge_right(other.deprecation),
                 modify: self.modify.merge_right(other.modify),
                 omit: self.omit.merge_right(other.omit),
                 cache: self.cache.merge_right(other.cache),
@@ -50,7 +50,7 @@ pub struct Cors {
     /// OPTIONS requests in order to avoid sending excessive requests to the
     /// server.
     #[serde(default, skip_serializing_if = "is_default")]
-    pub max_age: Option<usize>,
+    pub max_age: Option<i64>,
 
     /// A list of header names that indicate the values of which might cause the
     /// server's response to vary, potentially affecting caching.
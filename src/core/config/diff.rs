@@ -0,0 +1,637 @@
+use std::collections::BTreeSet;
+use std::fmt::{self, Display};
+
+use super::{Config, Type};
+
+/// Structured difference between two versions of a config.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    pub changes: Vec<Change>,
+}
+
+impl ConfigDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the changes that break the existing clients according to the
+    /// GraphQL schema evolution rules.
+    pub fn breaking_changes(&self) -> Vec<BreakingChange> {
+        self.changes
+            .iter()
+            .filter_map(|change| {
+                change
+                    .breaking_reason()
+                    .map(|reason| BreakingChange { change: change.clone(), reason })
+            })
+            .collect()
+    }
+}
+
+/// Change that breaks the existing clients along with the explanation why.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BreakingChange {
+    pub change: Change,
+    pub reason: &'static str,
+}
+
+impl Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.change, self.reason)
+    }
+}
+
+/// Single change between two versions of a config.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    RootOperationChanged {
+        operation: &'static str,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    TypeAdded {
+        type_name: String,
+    },
+    TypeRemoved {
+        type_name: String,
+    },
+    FieldAdded {
+        type_name: String,
+        field_name: String,
+        type_of: crate::core::Type,
+        is_input: bool,
+    },
+    FieldRemoved {
+        type_name: String,
+        field_name: String,
+    },
+    FieldTypeChanged {
+        type_name: String,
+        field_name: String,
+        from: crate::core::Type,
+        to: crate::core::Type,
+        is_input: bool,
+    },
+    /// Definition of the field other than its type and arguments, e.g. the
+    /// resolver or the doc, has changed. Isn't reported by [`Config::diff`]
+    /// since it doesn't affect the schema.
+    FieldChanged {
+        type_name: String,
+        field_name: String,
+    },
+    /// Definition of the type other than its fields, e.g. the doc, the
+    /// directives or the members of a union, has changed. Isn't reported by
+    /// [`Config::diff`].
+    TypeChanged {
+        type_name: String,
+    },
+    /// Schema directive, i.e. `@server`, `@upstream`, `@telemetry` or the
+    /// `@link`s, has changed. Isn't reported by [`Config::diff`].
+    DirectiveChanged {
+        directive: &'static str,
+    },
+    ArgAdded {
+        type_name: String,
+        field_name: String,
+        arg_name: String,
+        type_of: crate::core::Type,
+    },
+    ArgRemoved {
+        type_name: String,
+        field_name: String,
+        arg_name: String,
+    },
+    ArgTypeChanged {
+        type_name: String,
+        field_name: String,
+        arg_name: String,
+        from: crate::core::Type,
+        to: crate::core::Type,
+    },
+    EnumValueAdded {
+        enum_name: String,
+        value: String,
+    },
+    EnumValueRemoved {
+        enum_name: String,
+        value: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl ChangeKind {
+    fn title(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "Added",
+            ChangeKind::Removed => "Removed",
+            ChangeKind::Changed => "Changed",
+        }
+    }
+}
+
+/// Checks if the types differ only by the nullability of the outermost type.
+fn is_nullability_change(from: &crate::core::Type, to: &crate::core::Type) -> bool {
+    from.clone().into_required() == to.clone().into_required()
+}
+
+impl Change {
+    pub fn kind(&self) -> ChangeKind {
+        match self {
+            Change::TypeAdded { .. }
+            | Change::FieldAdded { .. }
+            | Change::ArgAdded { .. }
+            | Change::EnumValueAdded { .. } => ChangeKind::Added,
+            Change::TypeRemoved { .. }
+            | Change::FieldRemoved { .. }
+            | Change::ArgRemoved { .. }
+            | Change::EnumValueRemoved { .. } => ChangeKind::Removed,
+            Change::RootOperationChanged { .. }
+            | Change::FieldTypeChanged { .. }
+            | Change::FieldChanged { .. }
+            | Change::TypeChanged { .. }
+            | Change::DirectiveChanged { .. }
+            | Change::ArgTypeChanged { .. } => ChangeKind::Changed,
+        }
+    }
+
+    /// Checks if the change can break the existing clients of the schema.
+    pub fn is_breaking(&self) -> bool {
+        self.breaking_reason().is_some()
+    }
+
+    /// Explains why the change breaks the existing clients or returns `None`
+    /// if the change is safe.
+    pub fn breaking_reason(&self) -> Option<&'static str> {
+        match self {
+            Change::RootOperationChanged { from: None, .. }
+            | Change::TypeAdded { .. }
+            | Change::FieldChanged { .. }
+            | Change::TypeChanged { .. }
+            | Change::DirectiveChanged { .. }
+            | Change::EnumValueAdded { .. } => None,
+            Change::RootOperationChanged { .. } => {
+                Some("operations of the previous root type are no longer available")
+            }
+            Change::TypeRemoved { .. } => Some("type is no longer available"),
+            Change::FieldRemoved { .. } => Some("queries selecting the field will fail"),
+            Change::ArgRemoved { .. } => Some("queries passing the argument will fail"),
+            Change::EnumValueRemoved { .. } => Some("value can no longer be passed or returned"),
+            Change::FieldAdded { type_of, is_input: true, .. } if !type_of.is_nullable() => {
+                Some("existing clients don't provide the new required input field")
+            }
+            Change::FieldAdded { .. } => None,
+            Change::ArgAdded { type_of, .. } if !type_of.is_nullable() => {
+                Some("existing queries don't provide the new required argument")
+            }
+            Change::ArgAdded { .. } => None,
+            Change::FieldTypeChanged { from, to, is_input, .. } => {
+                if !is_nullability_change(from, to) {
+                    Some("type of the field has changed")
+                } else if *is_input {
+                    (!to.is_nullable()).then_some("input field became required")
+                } else {
+                    to.is_nullable().then_some("field can now return null")
+                }
+            }
+            Change::ArgTypeChanged { from, to, .. } => {
+                if !is_nullability_change(from, to) {
+                    Some("type of the argument has changed")
+                } else {
+                    (!to.is_nullable()).then_some("argument became required")
+                }
+            }
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::RootOperationChanged { operation, from, to } => {
+                let from = from.as_deref().unwrap_or("none");
+                let to = to.as_deref().unwrap_or("none");
+                write!(f, "Root {operation} type changed from `{from}` to `{to}`")
+            }
+            Change::TypeAdded { type_name } => write!(f, "Type `{type_name}` was added"),
+            Change::TypeRemoved { type_name } => write!(f, "Type `{type_name}` was removed"),
+            Change::FieldAdded { type_name, field_name, type_of, .. } => {
+                write!(f, "Field `{type_name}.{field_name}: {type_of:?}` was added")
+            }
+            Change::FieldRemoved { type_name, field_name } => {
+                write!(f, "Field `{type_name}.{field_name}` was removed")
+            }
+            Change::FieldTypeChanged { type_name, field_name, from, to, .. } => write!(
+                f,
+                "Field `{type_name}.{field_name}` changed type from `{from:?}` to `{to:?}`"
+            ),
+            Change::FieldChanged { type_name, field_name } => {
+                write!(f, "Field `{type_name}.{field_name}` was changed")
+            }
+            Change::TypeChanged { type_name } => write!(f, "Type `{type_name}` was changed"),
+            Change::DirectiveChanged { directive } => {
+                write!(f, "Directive `@{directive}` was changed")
+            }
+            Change::ArgAdded { type_name, field_name, arg_name, type_of } => write!(
+                f,
+                "Argument `{type_name}.{field_name}({arg_name}: {type_of:?})` was added"
+            ),
+            Change::ArgRemoved { type_name, field_name, arg_name } => {
+                write!(f, "Argument `{type_name}.{field_name}({arg_name})` was removed")
+            }
+            Change::ArgTypeChanged { type_name, field_name, arg_name, from, to } => write!(
+                f,
+                "Argument `{type_name}.{field_name}({arg_name})` changed type from `{from:?}` to `{to:?}`"
+            ),
+            Change::EnumValueAdded { enum_name, value } => {
+                write!(f, "Enum value `{enum_name}.{value}` was added")
+            }
+            Change::EnumValueRemoved { enum_name, value } => {
+                write!(f, "Enum value `{enum_name}.{value}` was removed")
+            }
+        }
+    }
+}
+
+impl Config {
+    /// Computes the changes that turn the current config into the `other`
+    /// one.
+    pub fn diff(&self, other: &Config) -> ConfigDiff {
+        let mut changes = Vec::new();
+
+        let root_operations = [
+            ("query", &self.schema.query, &other.schema.query),
+            ("mutation", &self.schema.mutation, &other.schema.mutation),
+            (
+                "subscription",
+                &self.schema.subscription,
+                &other.schema.subscription,
+            ),
+        ];
+
+        for (operation, from, to) in root_operations {
+            if from != to {
+                changes.push(Change::RootOperationChanged {
+                    operation,
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+            }
+        }
+
+        let old_names = self.definition_names();
+        let new_names = other.definition_names();
+
+        for type_name in old_names.difference(&new_names) {
+            changes.push(Change::TypeRemoved { type_name: type_name.to_string() });
+        }
+
+        for type_name in new_names.difference(&old_names) {
+            changes.push(Change::TypeAdded { type_name: type_name.to_string() });
+        }
+
+        let input_types = other.input_types();
+
+        for (type_name, old_type) in self.types.iter() {
+            if let Some(new_type) = other.types.get(type_name) {
+                let is_input = input_types.contains(type_name);
+                diff_fields(type_name, old_type, new_type, is_input, &mut changes);
+            }
+        }
+
+        for (enum_name, old_enum) in self.enums.iter() {
+            let Some(new_enum) = other.enums.get(enum_name) else {
+                continue;
+            };
+
+            let old_values = old_enum
+                .variants
+                .iter()
+                .map(|v| &v.name)
+                .collect::<BTreeSet<_>>();
+            let new_values = new_enum
+                .variants
+                .iter()
+                .map(|v| &v.name)
+                .collect::<BTreeSet<_>>();
+
+            for value in old_values.difference(&new_values) {
+                changes.push(Change::EnumValueRemoved {
+                    enum_name: enum_name.to_owned(),
+                    value: value.to_string(),
+                });
+            }
+
+            for value in new_values.difference(&old_values) {
+                changes.push(Change::EnumValueAdded {
+                    enum_name: enum_name.to_owned(),
+                    value: value.to_string(),
+                });
+            }
+        }
+
+        ConfigDiff { changes }
+    }
+
+    /// Names of all the types, unions and enums defined in the config.
+    fn definition_names(&self) -> BTreeSet<&String> {
+        self.types
+            .keys()
+            .chain(self.unions.keys())
+            .chain(self.enums.keys())
+            .collect()
+    }
+}
+
+fn diff_fields(
+    type_name: &str,
+    old_type: &Type,
+    new_type: &Type,
+    is_input: bool,
+    changes: &mut Vec<Change>,
+) {
+    for (field_name, old_field) in old_type.fields.iter() {
+        let Some(new_field) = new_type.fields.get(field_name) else {
+            changes.push(Change::FieldRemoved {
+                type_name: type_name.to_owned(),
+                field_name: field_name.to_owned(),
+            });
+            continue;
+        };
+
+        if old_field.type_of != new_field.type_of {
+            changes.push(Change::FieldTypeChanged {
+                type_name: type_name.to_owned(),
+                field_name: field_name.to_owned(),
+                from: old_field.type_of.clone(),
+                to: new_field.type_of.clone(),
+                is_input,
+            });
+        }
+
+        for (arg_name, old_arg) in old_field.args.iter() {
+            match new_field.args.get(arg_name) {
+                None => changes.push(Change::ArgRemoved {
+                    type_name: type_name.to_owned(),
+                    field_name: field_name.to_owned(),
+                    arg_name: arg_name.to_owned(),
+                }),
+                Some(new_arg) if new_arg.type_of != old_arg.type_of => {
+                    changes.push(Change::ArgTypeChanged {
+                        type_name: type_name.to_owned(),
+                        field_name: field_name.to_owned(),
+                        arg_name: arg_name.to_owned(),
+                        from: old_arg.type_of.clone(),
+                        to: new_arg.type_of.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        for (arg_name, new_arg) in new_field.args.iter() {
+            if !old_field.args.contains_key(arg_name) {
+                changes.push(Change::ArgAdded {
+                    type_name: type_name.to_owned(),
+                    field_name: field_name.to_owned(),
+                    arg_name: arg_name.to_owned(),
+                    type_of: new_arg.type_of.clone(),
+                });
+            }
+        }
+    }
+
+    for (field_name, new_field) in new_type.fields.iter() {
+        if !old_type.fields.contains_key(field_name) {
+            changes.push(Change::FieldAdded {
+                type_name: type_name.to_owned(),
+                field_name: field_name.to_owned(),
+                type_of: new_field.type_of.clone(),
+                is_input,
+            });
+        }
+    }
+}
+
+/// Renders the diff as a markdown changelog grouped by the kind of change.
+/// Breaking changes are listed in a separate section at the top.
+pub fn render_config_diff(diff: &ConfigDiff) -> String {
+    if diff.is_empty() {
+        return "No changes".to_string();
+    }
+
+    let (breaking, safe): (Vec<_>, Vec<_>) =
+        diff.changes.iter().partition(|change| change.is_breaking());
+
+    let mut sections = Vec::new();
+    sections.extend(render_section("Breaking changes", &breaking));
+
+    for kind in [ChangeKind::Added, ChangeKind::Removed, ChangeKind::Changed] {
+        let changes = safe
+            .iter()
+            .filter(|change| change.kind() == kind)
+            .copied()
+            .collect::<Vec<_>>();
+
+        sections.extend(render_section(kind.title(), &changes));
+    }
+
+    sections.join("\n\n")
+}
+
+fn render_section(title: &str, changes: &[&Change]) -> Option<String> {
+    if changes.is_empty() {
+        return None;
+    }
+
+    let items = changes
+        .iter()
+        .map(|change| format!("- {change}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Some(format!("### {title}\n\n{items}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use tailcall_valid::Validator;
+
+    use super::{render_config_diff, Change};
+    use crate::core::config::{BreakingChange, Config, Field};
+
+    fn config(sdl: &str) -> Config {
+        Config::from_sdl(sdl).to_result().unwrap()
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = config(
+            r#"
+            schema {
+                query: Query
+            }
+
+            type Query {
+                user: User @http(url: "http://localhost/user")
+            }
+
+            type User {
+                id: Int!
+            }
+
+            type Post {
+                id: Int!
+            }
+            "#,
+        );
+        let new = config(
+            r#"
+            schema {
+                query: Query
+            }
+
+            type Query {
+                user: User @http(url: "http://localhost/user")
+            }
+
+            type User {
+                id: Int!
+                age: Int
+            }
+            "#,
+        );
+
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff.changes,
+            vec![
+                Change::TypeRemoved { type_name: "Post".to_string() },
+                Change::FieldAdded {
+                    type_name: "User".to_string(),
+                    field_name: "age".to_string(),
+                    type_of: Field::int().type_of,
+                    is_input: false,
+                },
+            ]
+        );
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn test_render_config_diff() {
+        let old = config(
+            r#"
+            schema {
+                query: Query
+            }
+
+            type Query {
+                user: User @http(url: "http://localhost/user")
+            }
+
+            type User {
+                id: Int!
+                email: String
+            }
+            "#,
+        );
+        let new = config(
+            r#"
+            schema {
+                query: Query
+            }
+
+            type Query {
+                user: User @http(url: "http://localhost/user")
+            }
+
+            type User {
+                id: Int!
+                age: Int
+            }
+            "#,
+        );
+
+        let diff = old.diff(&new);
+        assert!(diff.changes[0].is_breaking());
+        assert!(matches!(diff.changes[1], Change::FieldAdded { .. }));
+
+        insta::assert_snapshot!(render_config_diff(&diff));
+    }
+
+    #[test]
+    fn test_breaking_changes() {
+        let old = config(
+            r#"
+            schema {
+                query: Query
+            }
+
+            enum Role {
+                ADMIN
+                USER
+            }
+
+            type Query {
+                users(limit: Int, offset: Int!): [User] @http(url: "http://localhost/users")
+            }
+
+            type User {
+                id: Int!
+                name: String
+                email: String
+                role: Role
+            }
+            "#,
+        );
+        let new = config(
+            r#"
+            schema {
+                query: Query
+            }
+
+            enum Role {
+                USER
+                GUEST
+            }
+
+            type Query {
+                users(limit: Int!, offset: Int): [User] @http(url: "http://localhost/users")
+            }
+
+            type User {
+                id: Int!
+                name: String!
+                role: String
+                age: Int
+            }
+            "#,
+        );
+
+        let diff = old.diff(&new);
+        let actual = diff
+            .breaking_changes()
+            .into_iter()
+            .map(|BreakingChange { change, .. }| change.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            actual,
+            vec![
+                "Argument `Query.users(limit)` changed type from `Int` to `Int!`".to_string(),
+                "Field `User.email` was removed".to_string(),
+                "Field `User.role` changed type from `Role` to `String`".to_string(),
+                "Enum value `Role.ADMIN` was removed".to_string(),
+            ]
+        );
+
+        // safe changes are still part of the diff
+        assert_eq!(diff.changes.len() - actual.len(), 4);
+    }
+}
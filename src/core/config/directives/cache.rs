@@ -3,6 +3,8 @@ use std::num::NonZeroU64;
 use serde::{Deserialize, Serialize};
 use tailcall_macros::{DirectiveDefinition, InputDefinition, MergeRight};
 
+use crate::core::is_default;
+
 #[derive(
     Clone,
     Debug,
@@ -21,6 +23,12 @@ use tailcall_macros::{DirectiveDefinition, InputDefinition, MergeRight};
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
 pub struct Cache {
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// Names of the arguments that form the cache key, so the requests that
+    /// differ only in the other arguments, e.g. a request id, share the cached
+    /// value. All the arguments form the key if empty.
+    pub key_args: Vec<String>,
+
     /// Specifies the duration, in milliseconds, of how long the value has to be
     /// stored in the cache.
     pub max_age: NonZeroU64,
@@ -39,5 +39,8 @@ pub struct Key {
 /// Resolver for `_entities` field for Apollo Federation
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct EntityResolver {
-    pub resolver_by_type: BTreeMap<String, Resolver>,
+    /// Resolvers of the entities grouped by the type name and then by the
+    /// `fields` of the `@key` that the resolver relies on. Types could define
+    /// multiple keys with a separate resolver for each of them.
+    pub resolver_by_type: BTreeMap<String, BTreeMap<String, Resolver>>,
 }
@@ -60,6 +60,12 @@ pub struct Http {
     /// key-value map of header names and their values.
     pub headers: Vec<KeyValue>,
 
+    #[serde(rename = "captureHeaders", default, skip_serializing_if = "is_default")]
+    /// Names of the headers of the upstream response to capture. The `select`
+    /// of the same `@http` and the fields resolved afterwards, like the fields
+    /// of the returned type, can read them as `{{.response.headers.<name>}}`.
+    pub capture_headers: Vec<String>,
+
     #[serde(default, skip_serializing_if = "is_default")]
     /// Schema of the input of the API call. It is automatically inferred in
     /// most cases.
@@ -103,6 +109,16 @@ pub struct Http {
     ///   "{{.fizz.buzz}}" }`
     pub select: Option<Value>,
 
+    #[serde(
+        rename = "responseTransform",
+        default,
+        skip_serializing_if = "is_default"
+    )]
+    /// JQ-style expression that reshapes the response before it's mapped to
+    /// the field's type, e.g. `.data.items` or `.users[].name`. It's applied
+    /// before `select`.
+    pub response_transform: Option<String>,
+
     /// Specifies a JavaScript function to be executed after receiving the
     /// response body. This function can modify or transform the response
     /// body before it's sent back to the client.
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use tailcall_macros::{DirectiveDefinition, InputDefinition};
 
+use crate::core::is_default;
+
 #[derive(
     Serialize,
     Deserialize,
@@ -15,4 +17,7 @@ use tailcall_macros::{DirectiveDefinition, InputDefinition};
 #[directive_definition(repeatable, locations = "FieldDefinition, Object", lowercase_name)]
 pub struct JS {
     pub name: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// Enables deduplication of the script calls with identical inputs.
+    pub dedupe: Option<bool>,
 }
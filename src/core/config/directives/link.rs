@@ -38,6 +38,10 @@ pub enum LinkType {
     /// to serve over HTTPS.
     Key,
 
+    /// Points to a bundle of CA certificates. When set, the server serving over
+    /// HTTPS requires clients to present a certificate signed by one of them.
+    ClientCA,
+
     /// A trusted document that contains GraphQL operations (queries, mutations)
     /// that can be exposed a REST API using the `@rest` directive.
     Operation,
@@ -98,4 +102,16 @@ pub struct Link {
     /// Only valid when [`Link::type_of`] is [`LinkType::Protobuf`]
     #[serde(default, skip_serializing_if = "is_default")]
     pub proto_paths: Option<Vec<String>>,
+    ///
+    /// Interval in seconds to fetch the JWKS again from the `src` URL, so the
+    /// keys rotated by the identity provider are picked up without a restart.
+    /// Only valid when [`Link::type_of`] is [`LinkType::Jwks`]
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub refresh_interval: Option<u64>,
+    ///
+    /// Time in seconds the keys that are no longer published are kept after
+    /// a refresh, so the tokens signed before the rotation stay valid.
+    /// Defaults to the `refresh_interval`.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub refresh_grace_period: Option<u64>,
 }
@@ -34,6 +34,12 @@ pub struct Server {
     #[schemars(skip)]
     pub enable_jit: Option<bool>,
 
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `alpnFallback` advertises both `h2` and `http/1.1` during the ALPN
+    /// negotiation when the server runs on `HTTP2`, so clients that only speak
+    /// HTTP/1.1 can use the same TLS port. @default `true`.
+    pub alpn_fallback: Option<bool>,
+
     #[serde(default, skip_serializing_if = "is_default")]
     /// `apolloTracing` exposes GraphQL query performance data, including
     /// execution time of queries and individual resolvers.
@@ -66,6 +72,12 @@ pub struct Server {
     /// and operations. @default `true`.
     pub introspection: Option<bool>,
 
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `maxRequestBodyBytes` sets the maximum size in bytes of the request
+    /// body. Larger requests are rejected with `413 Payload Too Large` before
+    /// the body is buffered. If not set, the size is not limited.
+    pub max_request_body_bytes: Option<usize>,
+
     /// `enableFederation` enables functionality to Tailcall server to act
     /// as a federation subgraph.
     #[serde(default, skip_serializing_if = "is_default")]
@@ -80,12 +92,29 @@ pub struct Server {
     /// `port` sets the Tailcall running port. @default `8000`.
     pub port: Option<u16>,
 
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `preferIpv6` resolves the `localhost` hostname to the IPv6 loopback
+    /// address `::1` instead of `127.0.0.1`. @default `false`.
+    pub prefer_ipv6: Option<bool>,
+
     #[serde(default, skip_serializing_if = "is_default")]
     /// `queryValidation` checks incoming GraphQL queries against the schema,
     /// preventing errors from invalid queries. Can be disabled for performance.
     /// @default `false`.
     pub query_validation: Option<bool>,
 
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `rateLimit` limits the number of requests a single client can make
+    /// within a time window, protecting the upstream services from being
+    /// overwhelmed by one client.
+    pub rate_limit: Option<RateLimit>,
+
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `redisUrl` stores the results of the `@cache` fields in Redis at the
+    /// given URL, so they are shared between the replicas of the server. If
+    /// not set, the results are cached in memory.
+    pub redis_url: Option<String>,
+
     #[serde(default, skip_serializing_if = "is_default")]
     /// `responseValidation` Tailcall automatically validates responses from
     /// upstream services using inferred schema. @default `false`.
@@ -100,6 +129,11 @@ pub struct Server {
     /// `showcase` enables the /showcase/graphql endpoint.
     pub showcase: Option<bool>,
 
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `shutdownTimeout` sets the time in milliseconds given to the in-flight
+    /// requests to complete when the server shuts down.
+    pub shutdown_timeout: Option<i64>,
+
     #[serde(default, skip_serializing_if = "is_default")]
     #[merge_right(merge_right_fn = "merge_right_vars")]
     /// This configuration defines local variables for server operations. Useful
@@ -169,6 +203,19 @@ pub struct ScriptOptions {
     pub timeout: Option<u64>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, schemars::JsonSchema, MergeRight)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimit {
+    /// Number of requests a client is allowed to make within the window.
+    pub requests: u64,
+    /// Length of the window in milliseconds.
+    pub window: u64,
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// Number of requests a client is allowed to make on top of `requests`
+    /// in a short burst. @default `0`.
+    pub burst: Option<u64>,
+}
+
 #[derive(
     Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Default, schemars::JsonSchema, MergeRight,
 )]
@@ -222,6 +269,10 @@ impl Server {
         self.showcase.unwrap_or(false)
     }
 
+    pub fn get_prefer_ipv6(&self) -> bool {
+        self.prefer_ipv6.unwrap_or(false)
+    }
+
     pub fn get_hostname(&self) -> String {
         self.hostname.clone().unwrap_or("127.0.0.1".to_string())
     }
@@ -253,10 +304,21 @@ impl Server {
             .unwrap_or_default()
     }
 
+    pub fn get_experimental_prefix(&self) -> bool {
+        self.headers
+            .as_ref()
+            .map(|h| h.experimental_prefix())
+            .unwrap_or(true)
+    }
+
     pub fn get_version(self) -> HttpVersion {
         self.version.unwrap_or(HttpVersion::HTTP1)
     }
 
+    pub fn get_alpn_fallback(&self) -> bool {
+        self.alpn_fallback.unwrap_or(true)
+    }
+
     pub fn get_pipeline_flush(&self) -> bool {
         self.pipeline_flush.unwrap_or(true)
     }
@@ -12,7 +12,10 @@ use indexmap::IndexMap;
 use tailcall_valid::{Valid, ValidationError, Validator};
 
 use super::directive::{to_directive, Directive};
-use super::{Alias, Discriminate, Resolver, RuntimeConfig, Telemetry, FEDERATION_DIRECTIVES};
+use super::{
+    Alias, Discriminate, Resolver, RuntimeConfig, Telemetry, DEFAULT_DEPRECATION_REASON,
+    DEPRECATED_DIRECTIVE, FEDERATION_DIRECTIVES,
+};
 use crate::core::config::{
     self, Cache, Config, Enum, Link, Modify, Omit, Protected, RootSchema, Server, Union, Upstream,
     Variant,
@@ -324,6 +327,7 @@ where
         .map_err(|err| ValidationError::new(err.to_string()))
         .into();
     let doc = description.to_owned().map(|pos| pos.node);
+    let deprecation = to_deprecation(directives);
 
     config::Resolver::from_directives(directives)
         .fuse(Cache::from_directives(directives.iter()))
@@ -332,7 +336,7 @@ where
         .fuse(Protected::from_directives(directives.iter()))
         .fuse(Discriminate::from_directives(directives.iter()))
         .fuse(default_value)
-        .fuse(to_federation_directives(directives))
+        .fuse(to_custom_directives(directives))
         .map(
             |(
                 resolvers,
@@ -347,6 +351,7 @@ where
                 type_of: type_of.into(),
                 args,
                 doc,
+                deprecation,
                 modify,
                 omit,
                 cache,
@@ -403,15 +408,20 @@ fn to_union(union_type: UnionType, doc: &Option<String>) -> Valid<Union, String>
 fn to_enum(enum_type: EnumType, doc: Option<String>) -> Valid<Enum, String> {
     let variants = Valid::from_iter(enum_type.values.iter(), |member| {
         let name = member.node.value.node.as_str().to_owned();
+        let doc = member.node.description.to_owned().map(|pos| pos.node);
         let alias = member
             .node
             .directives
             .iter()
             .find(|d| d.node.name.node.as_str() == Alias::directive_name());
         if let Some(alias) = alias {
-            Alias::from_directive(&alias.node).map(|alias| Variant { name, alias: Some(alias) })
+            Alias::from_directive(&alias.node).map(|alias| Variant {
+                name,
+                alias: Some(alias),
+                doc,
+            })
         } else {
-            Valid::succeed(Variant { name, alias: None })
+            Valid::succeed(Variant { name, alias: None, doc })
         }
     });
     variants.map(|v| Enum { variants: v.into_iter().collect::<BTreeSet<Variant>>(), doc })
@@ -431,6 +441,22 @@ fn to_add_fields_from_directives(
     )
 }
 
+fn to_deprecation(directives: &[Positioned<ConstDirective>]) -> Option<String> {
+    directives
+        .iter()
+        .find(|directive| directive.node.name.node.as_str() == DEPRECATED_DIRECTIVE)
+        .map(|directive| {
+            directive
+                .node
+                .get_argument("reason")
+                .and_then(|reason| match &reason.node {
+                    ConstValue::String(reason) => Some(reason.clone()),
+                    _ => None,
+                })
+                .unwrap_or(DEFAULT_DEPRECATION_REASON.to_string())
+        })
+}
+
 fn to_federation_directives(
     directives: &[Positioned<ConstDirective>],
 ) -> Valid<Vec<Directive>, String> {
@@ -447,6 +473,37 @@ fn to_federation_directives(
     .map(|directives| directives.into_iter().flatten().collect())
 }
 
+/// Collects the directives of the field that aren't parsed into the config,
+/// e.g. the federation or user-defined directives, so they are kept in the
+/// schema.
+fn to_custom_directives(
+    directives: &[Positioned<ConstDirective>],
+) -> Valid<Vec<Directive>, String> {
+    let known = [
+        config::Http::directive_name(),
+        config::Grpc::directive_name(),
+        config::GraphQL::directive_name(),
+        config::Call::directive_name(),
+        config::JS::directive_name(),
+        config::Expr::directive_name(),
+        Cache::directive_name(),
+        Omit::directive_name(),
+        Modify::directive_name(),
+        Protected::directive_name(),
+        Discriminate::directive_name(),
+        DEPRECATED_DIRECTIVE.to_string(),
+    ];
+
+    Valid::from_iter(
+        directives.iter().filter(|directive| {
+            !known
+                .iter()
+                .any(|name| name == directive.node.name.node.as_str())
+        }),
+        |directive| to_directive(directive.node.clone()),
+    )
+}
+
 trait HasName {
     fn name(&self) -> &Positioned<Name>;
 }
@@ -29,6 +29,12 @@ pub struct Headers {
     /// in the response. @default `[]`.
     pub experimental: Option<BTreeSet<String>>,
 
+    #[serde(default, skip_serializing_if = "is_default")]
+    /// `experimentalPrefix` requires the names of `experimental` headers to
+    /// start with `x-`. Disable it to use any valid header name. @default
+    /// `true`.
+    pub experimental_prefix: Option<bool>,
+
     /// `setCookies` when enabled stores `set-cookie` headers
     /// and all the response will be sent with the headers.
     #[serde(default, skip_serializing_if = "is_default")]
@@ -43,6 +49,9 @@ impl Headers {
     pub fn enable_cache_control(&self) -> bool {
         self.cache_control.unwrap_or(false)
     }
+    pub fn experimental_prefix(&self) -> bool {
+        self.experimental_prefix.unwrap_or(true)
+    }
     pub fn set_cookies(&self) -> bool {
         self.set_cookies.unwrap_or_default()
     }
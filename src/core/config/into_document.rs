@@ -4,7 +4,7 @@ use async_graphql_value::{ConstValue, Name};
 use tailcall_valid::Validator;
 
 use super::directive::to_const_directive;
-use super::Config;
+use super::{Config, DEPRECATED_DIRECTIVE};
 use crate::core::directive::DirectiveCodec;
 use crate::core::pos;
 
@@ -187,7 +187,7 @@ fn config_document(config: &Config) -> ServiceDocument {
                     .iter()
                     .map(|variant| {
                         pos(EnumValueDefinition {
-                            description: None,
+                            description: variant.doc.clone().map(pos),
                             value: pos(Name::new(&variant.name)),
                             directives: variant
                                 .alias
@@ -212,11 +212,27 @@ fn into_directives(
         .map(pos)
 }
 
+fn deprecated_directive(reason: &str) -> ConstDirective {
+    ConstDirective {
+        name: pos(Name::new(DEPRECATED_DIRECTIVE)),
+        arguments: vec![(
+            pos(Name::new("reason")),
+            pos(ConstValue::String(reason.to_owned())),
+        )],
+    }
+}
+
 fn field_directives(field: &crate::core::config::Field) -> Vec<Positioned<ConstDirective>> {
     field
         .resolvers
         .iter()
         .filter_map(|resolver| resolver.to_directive().map(pos))
+        .chain(
+            field
+                .deprecation
+                .as_deref()
+                .map(|reason| pos(deprecated_directive(reason))),
+        )
         .chain(field.modify.as_ref().map(|d| pos(d.to_directive())))
         .chain(field.omit.as_ref().map(|d| pos(d.to_directive())))
         .chain(field.cache.as_ref().map(|d| pos(d.to_directive())))
@@ -0,0 +1,191 @@
+use serde_json::{json, Map, Value};
+
+use super::Config;
+use crate::core::Type;
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+impl Config {
+    /// Exports the types, enums and unions as a JSON Schema (draft 2020-12)
+    /// with a definition per type under `$defs`, so JSON values can be
+    /// validated against the shapes described by the config.
+    pub fn to_json_schema(&self) -> Value {
+        let mut defs = Map::new();
+
+        for (name, ty) in self.types.iter() {
+            let properties = ty
+                .fields
+                .iter()
+                .map(|(field_name, field)| (field_name.clone(), self.type_schema(&field.type_of)))
+                .collect::<Map<_, _>>();
+            let required = ty
+                .fields
+                .iter()
+                .filter(|(_, field)| !field.type_of.is_nullable())
+                .map(|(field_name, _)| field_name.clone())
+                .collect::<Vec<_>>();
+
+            let mut schema = json!({ "type": "object", "properties": properties });
+            if !required.is_empty() {
+                schema["required"] = json!(required);
+            }
+            with_description(&mut schema, &ty.doc);
+
+            defs.insert(name.clone(), schema);
+        }
+
+        for (name, enum_) in self.enums.iter() {
+            let variants = enum_
+                .variants
+                .iter()
+                .map(|variant| variant.name.clone())
+                .collect::<Vec<_>>();
+
+            let mut schema = json!({ "enum": variants });
+            with_description(&mut schema, &enum_.doc);
+
+            defs.insert(name.clone(), schema);
+        }
+
+        for (name, union) in self.unions.iter() {
+            let members = union
+                .types
+                .iter()
+                .map(|type_name| json!({ "$ref": format!("#/$defs/{type_name}") }))
+                .collect::<Vec<_>>();
+
+            let mut schema = json!({ "oneOf": members });
+            with_description(&mut schema, &union.doc);
+
+            defs.insert(name.clone(), schema);
+        }
+
+        json!({ "$schema": DRAFT, "$defs": defs })
+    }
+
+    fn type_schema(&self, type_of: &Type) -> Value {
+        let schema = match type_of {
+            Type::Named { name, .. } => self.named_schema(name),
+            Type::List { of_type, .. } => {
+                json!({ "type": "array", "items": self.type_schema(of_type) })
+            }
+        };
+
+        if type_of.is_nullable() {
+            json!({ "anyOf": [schema, { "type": "null" }] })
+        } else {
+            schema
+        }
+    }
+
+    /// Maps the scalars back to the JSON types and refers to the definitions
+    /// of the other types.
+    fn named_schema(&self, name: &str) -> Value {
+        match name {
+            "Int" | "Int8" | "Int16" | "Int32" | "UInt8" | "UInt16" | "UInt32" => {
+                json!({ "type": "integer" })
+            }
+            // 64 and 128 bit integers are represented by strings to avoid precision loss
+            "Int64" | "UInt64" | "Int128" | "UInt128" => json!({ "type": "string" }),
+            "Float" => json!({ "type": "number" }),
+            "Boolean" => json!({ "type": "boolean" }),
+            "String" | "ID" | "PhoneNumber" | "Bytes" => json!({ "type": "string" }),
+            "Email" => json!({ "type": "string", "format": "email" }),
+            "Date" => json!({ "type": "string", "format": "date" }),
+            "DateTime" => json!({ "type": "string", "format": "date-time" }),
+            "Url" => json!({ "type": "string", "format": "uri" }),
+            "Empty" => json!({ "type": "null" }),
+            name if self.types.contains_key(name)
+                || self.enums.contains_key(name)
+                || self.unions.contains_key(name) =>
+            {
+                json!({ "$ref": format!("#/$defs/{name}") })
+            }
+            // `JSON` and unknown scalars accept any value
+            _ => json!({}),
+        }
+    }
+}
+
+fn with_description(schema: &mut Value, doc: &Option<String>) {
+    if let Some(doc) = doc {
+        schema["description"] = json!(doc);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tailcall_valid::Validator;
+
+    use crate::core::config::Config;
+
+    #[test]
+    fn test_to_json_schema() {
+        let config = Config::from_sdl(
+            r#"
+            schema {
+              query: Query
+            }
+
+            type Query {
+              user: User
+            }
+
+            "A registered user"
+            type User {
+              id: Int!
+              email: Email
+              role: Role!
+              tags: [String!]!
+              pet: Pet
+            }
+
+            type Cat {
+              meows: Boolean!
+            }
+
+            type Dog {
+              barks: Boolean!
+            }
+
+            union Pet = Cat | Dog
+
+            enum Role {
+              ADMIN
+              MEMBER
+            }
+            "#,
+        )
+        .to_result()
+        .unwrap();
+
+        let schema = config.to_json_schema();
+        let defs = &schema["$defs"];
+
+        assert_eq!(
+            schema["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+        assert_eq!(
+            defs["User"],
+            json!({
+                "type": "object",
+                "description": "A registered user",
+                "properties": {
+                    "email": { "anyOf": [{ "type": "string", "format": "email" }, { "type": "null" }] },
+                    "id": { "type": "integer" },
+                    "pet": { "anyOf": [{ "$ref": "#/$defs/Pet" }, { "type": "null" }] },
+                    "role": { "$ref": "#/$defs/Role" },
+                    "tags": { "type": "array", "items": { "type": "string" } }
+                },
+                "required": ["id", "role", "tags"]
+            })
+        );
+        assert_eq!(defs["Role"], json!({ "enum": ["ADMIN", "MEMBER"] }));
+        assert_eq!(
+            defs["Pet"],
+            json!({ "oneOf": [{ "$ref": "#/$defs/Cat" }, { "$ref": "#/$defs/Dog" }] })
+        );
+    }
+}
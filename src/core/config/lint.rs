@@ -0,0 +1,178 @@
+use std::fmt::{self, Display};
+
+use super::Config;
+
+/// Suspicious part of a config that is still valid, e.g. the leftover of a
+/// bad merge.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LintWarning {
+    MultipleResolvers {
+        type_name: String,
+        field_name: String,
+        resolvers: Vec<String>,
+    },
+    RootFieldWithoutResolver {
+        type_name: String,
+        field_name: String,
+    },
+    UnreferencedType {
+        type_name: String,
+    },
+}
+
+impl Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintWarning::MultipleResolvers { type_name, field_name, resolvers } => {
+                let resolvers = resolvers
+                    .iter()
+                    .map(|name| format!("@{name}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "Field `{type_name}.{field_name}` has multiple resolvers: {resolvers}"
+                )
+            }
+            LintWarning::RootFieldWithoutResolver { type_name, field_name } => {
+                write!(f, "Root field `{type_name}.{field_name}` has no resolver")
+            }
+            LintWarning::UnreferencedType { type_name } => {
+                write!(
+                    f,
+                    "Type `{type_name}` is not referenced from the root types"
+                )
+            }
+        }
+    }
+}
+
+impl Config {
+    /// Reports the parts of the config that are valid but most likely a
+    /// mistake, so they are noticed before one of the resolvers silently wins
+    /// at runtime.
+    pub fn lint(&self) -> Vec<LintWarning> {
+        let mut warnings = Vec::new();
+
+        for (type_name, type_of) in self.types.iter() {
+            let is_root = self.is_root_operation_type(type_name);
+
+            for (field_name, field) in type_of.fields.iter() {
+                if field.resolvers.len() > 1 {
+                    warnings.push(LintWarning::MultipleResolvers {
+                        type_name: type_name.clone(),
+                        field_name: field_name.clone(),
+                        resolvers: field
+                            .resolvers
+                            .iter()
+                            .map(|resolver| resolver.directive_name())
+                            .collect(),
+                    });
+                }
+
+                if is_root && !field.has_resolver() {
+                    warnings.push(LintWarning::RootFieldWithoutResolver {
+                        type_name: type_name.clone(),
+                        field_name: field_name.clone(),
+                    });
+                }
+            }
+        }
+
+        let mut unused_types = self
+            .unused_types()
+            .into_iter()
+            .filter(|type_name| !self.is_root_operation_type(type_name))
+            .collect::<Vec<_>>();
+        unused_types.sort();
+
+        warnings.extend(
+            unused_types
+                .into_iter()
+                .map(|type_name| LintWarning::UnreferencedType { type_name }),
+        );
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tailcall_valid::Validator;
+
+    use super::LintWarning;
+    use crate::core::config::Config;
+
+    fn config(sdl: &str) -> Config {
+        Config::from_sdl(sdl).to_result().unwrap()
+    }
+
+    #[test]
+    fn test_lint_multiple_resolvers() {
+        let config = config(
+            r#"
+            schema {
+                query: Query
+            }
+
+            type Query {
+                user: User @http(url: "http://localhost/user") @grpc(url: "http://localhost:50051", method: "users.UserService.GetUser")
+            }
+
+            type User {
+                id: Int!
+            }
+            "#,
+        );
+
+        let warnings = config.lint();
+
+        assert_eq!(
+            warnings,
+            vec![LintWarning::MultipleResolvers {
+                type_name: "Query".to_string(),
+                field_name: "user".to_string(),
+                resolvers: vec!["http".to_string(), "grpc".to_string()],
+            }]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "Field `Query.user` has multiple resolvers: @http, @grpc"
+        );
+    }
+
+    #[test]
+    fn test_lint_unreferenced_type() {
+        let config = config(
+            r#"
+            schema {
+                query: Query
+            }
+
+            type Query {
+                user: User @http(url: "http://localhost/user")
+                version: String
+            }
+
+            type User {
+                id: Int!
+            }
+
+            type Post {
+                id: Int!
+            }
+            "#,
+        );
+
+        assert_eq!(
+            config.lint(),
+            vec![
+                LintWarning::RootFieldWithoutResolver {
+                    type_name: "Query".to_string(),
+                    field_name: "version".to_string(),
+                },
+                LintWarning::UnreferencedType { type_name: "Post".to_string() },
+            ]
+        );
+    }
+}
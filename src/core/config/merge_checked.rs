@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+
+use tailcall_valid::{Valid, Validator};
+
+use super::{Config, Type};
+use crate::core::merge_right::MergeRight;
+
+/// Definition that is present in both merged configs but defined differently.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum MergeConflict {
+    #[error("Field '{field_name}' is defined differently in the root type '{type_name}'")]
+    RootField {
+        type_name: String,
+        field_name: String,
+    },
+    #[error("Type '{0}' is defined differently")]
+    Type(String),
+}
+
+impl Config {
+    /// Merges the configs like [`MergeRight`] does, but fails instead of
+    /// silently overriding a definition with a different one. Fields of the
+    /// root operation types are merged individually, so the configs are
+    /// allowed to contribute different fields to e.g. `Query`.
+    pub fn merge_checked(self, other: Config) -> Valid<Config, MergeConflict> {
+        let types = Valid::from_iter(other.types.iter(), |(type_name, ty)| {
+            match self.types.get(type_name) {
+                Some(existing) if existing != ty => {
+                    if self.is_root_operation_type(type_name)
+                        || other.is_root_operation_type(type_name)
+                    {
+                        check_root_fields(type_name, existing, ty)
+                    } else {
+                        Valid::fail(MergeConflict::Type(type_name.to_owned()))
+                    }
+                }
+                _ => Valid::succeed(()),
+            }
+        });
+
+        types
+            .fuse(check_definitions(&self.unions, &other.unions))
+            .fuse(check_definitions(&self.enums, &other.enums))
+            .map_to(self.merge_right(other))
+    }
+}
+
+fn check_root_fields(type_name: &str, existing: &Type, ty: &Type) -> Valid<(), MergeConflict> {
+    Valid::from_iter(ty.fields.iter(), |(field_name, field)| {
+        match existing.fields.get(field_name) {
+            Some(existing) if existing != field => Valid::fail(MergeConflict::RootField {
+                type_name: type_name.to_owned(),
+                field_name: field_name.to_owned(),
+            }),
+            _ => Valid::succeed(()),
+        }
+    })
+    .unit()
+}
+
+fn check_definitions<V: PartialEq>(
+    existing: &BTreeMap<String, V>,
+    other: &BTreeMap<String, V>,
+) -> Valid<(), MergeConflict> {
+    Valid::from_iter(other.iter(), |(name, definition)| {
+        match existing.get(name) {
+            Some(existing) if existing != definition => {
+                Valid::fail(MergeConflict::Type(name.to_owned()))
+            }
+            _ => Valid::succeed(()),
+        }
+    })
+    .unit()
+}
+
+#[cfg(test)]
+mod tests {
+    use tailcall_valid::Validator;
+
+    use super::MergeConflict;
+    use crate::core::config::Config;
+
+    const REST: &str = r#"
+        schema {
+          query: Query
+        }
+
+        type Query {
+          users: [User] @http(url: "http://jsonplaceholder.typicode.com/users")
+        }
+
+        type User {
+          id: Int
+          name: String
+        }
+    "#;
+
+    #[test]
+    fn test_merge_checked() {
+        let rest = Config::from_sdl(REST).to_result().unwrap();
+        let grpc = Config::from_sdl(
+            r#"
+            schema {
+              query: Query
+            }
+
+            type Query {
+              news: [News] @grpc(url: "http://localhost:50051", method: "news.NewsService.GetAllNews")
+            }
+
+            type News {
+              id: Int
+            }
+            "#,
+        )
+        .to_result()
+        .unwrap();
+
+        let merged = rest.merge_checked(grpc).to_result().unwrap();
+        let fields = merged.types["Query"].fields.keys().collect::<Vec<_>>();
+
+        assert_eq!(fields, vec!["news", "users"]);
+        assert!(merged.types.contains_key("User"));
+        assert!(merged.types.contains_key("News"));
+    }
+
+    #[test]
+    fn test_merge_checked_conflict() {
+        let rest = Config::from_sdl(REST).to_result().unwrap();
+        let grpc = Config::from_sdl(
+            r#"
+            schema {
+              query: Query
+            }
+
+            type Query {
+              users: [User] @grpc(url: "http://localhost:50051", method: "users.UserService.GetAllUsers")
+            }
+
+            type User {
+              id: Int
+            }
+            "#,
+        )
+        .to_result()
+        .unwrap();
+
+        let error = rest
+            .merge_checked(grpc)
+            .to_result()
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.contains(
+            &MergeConflict::RootField {
+                type_name: "Query".to_string(),
+                field_name: "users".to_string(),
+            }
+            .to_string()
+        ));
+        assert!(error.contains(&MergeConflict::Type("User".to_string()).to_string()));
+    }
+}
@@ -0,0 +1,575 @@
+use std::collections::BTreeMap;
+
+use super::{Change, Config, Field, Type};
+
+/// Result of reapplying the manual edits on top of the regenerated config.
+#[derive(Clone, Debug)]
+pub struct MergedConfig {
+    pub config: Config,
+    /// Manual edits that weren't applied because the regeneration changed
+    /// the same definition in a different way.
+    pub conflicts: Vec<Change>,
+}
+
+/// Reapplies the manual edits of the generated config on top of the config
+/// generated again from the updated sources. The edits are computed as the
+/// diff between the `base` config, i.e. the previous generator output, and
+/// the `edited` one. In case of conflicts the regenerated definition wins and
+/// the edit is reported in [`MergedConfig::conflicts`].
+pub fn merge_generated(base: Config, edited: Config, regenerated: Config) -> MergedConfig {
+    let mut config = regenerated;
+    let mut conflicts: Vec<Change> = base
+        .diff(&edited)
+        .changes
+        .into_iter()
+        .filter(|change| !apply_change(&base, &edited, &mut config, change))
+        .collect();
+
+    let directives = [
+        (
+            "server",
+            merge_definition(&mut config.server, &base.server, &edited.server),
+        ),
+        (
+            "upstream",
+            merge_definition(&mut config.upstream, &base.upstream, &edited.upstream),
+        ),
+        (
+            "telemetry",
+            merge_definition(&mut config.telemetry, &base.telemetry, &edited.telemetry),
+        ),
+        (
+            "link",
+            merge_definition(&mut config.links, &base.links, &edited.links),
+        ),
+    ];
+    for (directive, merged) in directives {
+        if !merged {
+            conflicts.push(Change::DirectiveChanged { directive });
+        }
+    }
+
+    for (type_name, edited_union) in edited.unions.iter() {
+        let (Some(base_union), Some(union_)) =
+            (base.unions.get(type_name), config.unions.get_mut(type_name))
+        else {
+            continue;
+        };
+        if !merge_definition(union_, base_union, edited_union) {
+            conflicts.push(Change::TypeChanged { type_name: type_name.to_owned() });
+        }
+    }
+
+    for (type_name, edited_enum) in edited.enums.iter() {
+        let (Some(base_enum), Some(enum_)) =
+            (base.enums.get(type_name), config.enums.get_mut(type_name))
+        else {
+            continue;
+        };
+        // the variants are merged through the `Change`s of the diff
+        if !merge_definition(&mut enum_.doc, &base_enum.doc, &edited_enum.doc) {
+            conflicts.push(Change::TypeChanged { type_name: type_name.to_owned() });
+        }
+    }
+
+    for (type_name, edited_type) in edited.types.iter() {
+        let Some(base_type) = base.types.get(type_name) else {
+            continue;
+        };
+
+        if let Some(ty) = config.types.get_mut(type_name) {
+            if !apply_type_edit(ty, base_type, edited_type) {
+                conflicts.push(Change::TypeChanged { type_name: type_name.to_owned() });
+            }
+        }
+
+        for (field_name, edited_field) in edited_type.fields.iter() {
+            let Some(base_field) = base_type.fields.get(field_name) else {
+                continue;
+            };
+            if !apply_field_edit(&mut config, type_name, field_name, base_field, edited_field) {
+                conflicts.push(Change::FieldChanged {
+                    type_name: type_name.to_owned(),
+                    field_name: field_name.to_owned(),
+                });
+            }
+        }
+    }
+
+    MergedConfig { config, conflicts }
+}
+
+/// Reapplies the edit of the definition unless the regeneration changed it in
+/// a different way and returns `false` on conflict.
+fn merge_definition<V: Clone + PartialEq>(current: &mut V, base: &V, edited: &V) -> bool {
+    if edited == base || current == edited {
+        true
+    } else if current == base {
+        current.clone_from(edited);
+        true
+    } else {
+        false
+    }
+}
+
+/// Type without the fields that are merged separately.
+fn strip_type(ty: &Type) -> Type {
+    Type { fields: Default::default(), ..ty.clone() }
+}
+
+/// Reapplies the edits of the type other than its fields, e.g. the doc or the
+/// directives, and returns `false` on conflict.
+fn apply_type_edit(ty: &mut Type, base: &Type, edited: &Type) -> bool {
+    let mut current = strip_type(ty);
+    if !merge_definition(&mut current, &strip_type(base), &strip_type(edited)) {
+        return false;
+    }
+
+    *ty = Type { fields: std::mem::take(&mut ty.fields), ..current };
+    true
+}
+
+/// Field without the type and the arguments that are merged separately
+/// through the [`Change`]s of the diff.
+fn strip_field(field: &Field) -> Field {
+    Field {
+        type_of: Default::default(),
+        args: Default::default(),
+        ..field.clone()
+    }
+}
+
+/// Reapplies the edits of the field other than its type and arguments, e.g.
+/// the resolver or the doc, and returns `false` on conflict.
+fn apply_field_edit(
+    config: &mut Config,
+    type_name: &str,
+    field_name: &str,
+    base: &Field,
+    edited: &Field,
+) -> bool {
+    let edited = strip_field(edited);
+    if edited == strip_field(base) {
+        return true;
+    }
+
+    let Some(field) = config
+        .types
+        .get_mut(type_name)
+        .and_then(|ty| ty.fields.get_mut(field_name))
+    else {
+        // the regeneration removed the field
+        return true;
+    };
+
+    let current = strip_field(field);
+    if current == edited {
+        true
+    } else if current == strip_field(base) {
+        *field = Field {
+            type_of: field.type_of.clone(),
+            args: field.args.clone(),
+            ..edited
+        };
+        true
+    } else {
+        false
+    }
+}
+
+/// Inserts the definition unless the map already contains a different one.
+fn add_definition<V: Clone + PartialEq>(
+    map: &mut BTreeMap<String, V>,
+    name: &str,
+    value: &V,
+) -> bool {
+    match map.get(name) {
+        Some(existing) => existing == value,
+        None => {
+            map.insert(name.to_owned(), value.clone());
+            true
+        }
+    }
+}
+
+/// Removes the definition unless it was changed by the regeneration.
+fn remove_definition<V: PartialEq>(map: &mut BTreeMap<String, V>, name: &str, base: &V) -> bool {
+    match map.get(name) {
+        Some(existing) if existing != base => false,
+        _ => {
+            map.remove(name);
+            true
+        }
+    }
+}
+
+/// Applies a single edit to the config and returns `false` on conflict.
+fn apply_change(base: &Config, edited: &Config, config: &mut Config, change: &Change) -> bool {
+    match change {
+        Change::RootOperationChanged { operation, from, to } => {
+            let root = match *operation {
+                "query" => &mut config.schema.query,
+                "mutation" => &mut config.schema.mutation,
+                _ => &mut config.schema.subscription,
+            };
+
+            if root == from || root == to {
+                root.clone_from(to);
+                true
+            } else {
+                false
+            }
+        }
+        Change::TypeAdded { type_name } => {
+            if let Some(ty) = edited.types.get(type_name) {
+                add_definition(&mut config.types, type_name, ty)
+            } else if let Some(union_) = edited.unions.get(type_name) {
+                add_definition(&mut config.unions, type_name, union_)
+            } else if let Some(enum_) = edited.enums.get(type_name) {
+                add_definition(&mut config.enums, type_name, enum_)
+            } else {
+                false
+            }
+        }
+        Change::TypeRemoved { type_name } => {
+            if let Some(ty) = base.types.get(type_name) {
+                remove_definition(&mut config.types, type_name, ty)
+            } else if let Some(union_) = base.unions.get(type_name) {
+                remove_definition(&mut config.unions, type_name, union_)
+            } else if let Some(enum_) = base.enums.get(type_name) {
+                remove_definition(&mut config.enums, type_name, enum_)
+            } else {
+                false
+            }
+        }
+        Change::FieldAdded { type_name, field_name, .. } => {
+            let field = edited
+                .types
+                .get(type_name)
+                .and_then(|ty| ty.fields.get(field_name));
+
+            match (config.types.get_mut(type_name), field) {
+                (Some(ty), Some(field)) => add_definition(&mut ty.fields, field_name, field),
+                _ => false,
+            }
+        }
+        Change::FieldRemoved { type_name, field_name } => {
+            let field = base
+                .types
+                .get(type_name)
+                .and_then(|ty| ty.fields.get(field_name));
+
+            match (config.types.get_mut(type_name), field) {
+                (Some(ty), Some(field)) => remove_definition(&mut ty.fields, field_name, field),
+                // the regeneration removed the type as well
+                (None, _) => true,
+                _ => false,
+            }
+        }
+        Change::FieldTypeChanged { type_name, field_name, from, to, .. } => {
+            let field = config
+                .types
+                .get_mut(type_name)
+                .and_then(|ty| ty.fields.get_mut(field_name));
+
+            match field {
+                Some(field) if field.type_of == *from || field.type_of == *to => {
+                    field.type_of = to.clone();
+                    true
+                }
+                _ => false,
+            }
+        }
+        Change::ArgAdded { type_name, field_name, arg_name, .. } => {
+            let arg = edited
+                .types
+                .get(type_name)
+                .and_then(|ty| ty.fields.get(field_name))
+                .and_then(|field| field.args.get(arg_name));
+            let field = config
+                .types
+                .get_mut(type_name)
+                .and_then(|ty| ty.fields.get_mut(field_name));
+
+            match (field, arg) {
+                (Some(field), Some(arg)) => match field.args.get(arg_name) {
+                    Some(existing) => existing == arg,
+                    None => {
+                        field.args.insert(arg_name.to_owned(), arg.clone());
+                        true
+                    }
+                },
+                _ => false,
+            }
+        }
+        Change::ArgRemoved { type_name, field_name, arg_name } => {
+            let arg = base
+                .types
+                .get(type_name)
+                .and_then(|ty| ty.fields.get(field_name))
+                .and_then(|field| field.args.get(arg_name));
+            let field = config
+                .types
+                .get_mut(type_name)
+                .and_then(|ty| ty.fields.get_mut(field_name));
+
+            match (field, arg) {
+                (Some(field), Some(arg)) => match field.args.get(arg_name) {
+                    Some(existing) if existing != arg => false,
+                    _ => {
+                        field.args.shift_remove(arg_name);
+                        true
+                    }
+                },
+                (None, _) => true,
+                _ => false,
+            }
+        }
+        Change::ArgTypeChanged { type_name, field_name, arg_name, from, to } => {
+            let arg = config
+                .types
+                .get_mut(type_name)
+                .and_then(|ty| ty.fields.get_mut(field_name))
+                .and_then(|field| field.args.get_mut(arg_name));
+
+            match arg {
+                Some(arg) if arg.type_of == *from || arg.type_of == *to => {
+                    arg.type_of = to.clone();
+                    true
+                }
+                _ => false,
+            }
+        }
+        Change::EnumValueAdded { enum_name, value } => {
+            let variant = edited
+                .enums
+                .get(enum_name)
+                .and_then(|enum_| enum_.variants.iter().find(|v| v.name == *value));
+
+            match (config.enums.get_mut(enum_name), variant) {
+                (Some(enum_), Some(variant)) => {
+                    if !enum_.variants.iter().any(|v| v.name == *value) {
+                        enum_.variants.insert(variant.clone());
+                    }
+                    true
+                }
+                _ => false,
+            }
+        }
+        Change::EnumValueRemoved { enum_name, value } => {
+            if let Some(enum_) = config.enums.get_mut(enum_name) {
+                enum_.variants.retain(|v| v.name != *value);
+            }
+            true
+        }
+        // not produced by the diff, see `apply_field_edit`
+        Change::FieldChanged { .. } => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge_generated;
+    use crate::core::config::{Change, Config, Directive, Field, Link, LinkType, Type, Union};
+
+    fn field(type_of: &str) -> Field {
+        Field { type_of: type_of.to_string().into(), ..Default::default() }
+    }
+
+    fn user(fields: &[(&str, &str)]) -> Config {
+        let ty = Type {
+            fields: fields
+                .iter()
+                .map(|(name, type_of)| (name.to_string(), field(type_of)))
+                .collect(),
+            ..Default::default()
+        };
+
+        Config::default().types(vec![("User", ty)])
+    }
+
+    #[test]
+    fn test_merge_generated() {
+        let base = user(&[("id", "Int"), ("name", "String")]);
+        // user renamed the field manually
+        let edited = user(&[("id", "Int"), ("fullName", "String")]);
+        // the source got a new field
+        let regenerated = user(&[("id", "Int"), ("name", "String"), ("email", "String")]);
+
+        let merged = merge_generated(base, edited, regenerated);
+        let fields = merged.config.types["User"]
+            .fields
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        assert_eq!(fields, vec!["email", "fullName", "id"]);
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_merge_generated_conflict() {
+        let base = user(&[("id", "Int")]);
+        let edited = user(&[("id", "ID")]);
+        let regenerated = user(&[("id", "String")]);
+
+        let merged = merge_generated(base, edited, regenerated);
+
+        assert_eq!(
+            merged.config.types["User"].fields["id"].type_of.name(),
+            "String"
+        );
+        assert!(matches!(
+            merged.conflicts.as_slice(),
+            [Change::FieldTypeChanged { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_merge_generated_field_edit() {
+        let base = user(&[("id", "Int"), ("name", "String")]);
+        let mut edited = base.clone();
+        let mut regenerated = user(&[("id", "Int"), ("name", "String"), ("email", "String")]);
+
+        // user documented both fields manually
+        for field in ["id", "name"] {
+            edited
+                .types
+                .get_mut("User")
+                .unwrap()
+                .fields
+                .get_mut(field)
+                .unwrap()
+                .doc = Some(format!("User {field}"));
+        }
+        // the source documented the name differently
+        regenerated
+            .types
+            .get_mut("User")
+            .unwrap()
+            .fields
+            .get_mut("name")
+            .unwrap()
+            .doc = Some("Name of the user".to_string());
+
+        let merged = merge_generated(base, edited, regenerated);
+        let fields = &merged.config.types["User"].fields;
+
+        assert_eq!(fields["id"].doc.as_deref(), Some("User id"));
+        assert_eq!(fields["name"].doc.as_deref(), Some("Name of the user"));
+        assert_eq!(
+            merged.conflicts,
+            vec![Change::FieldChanged {
+                type_name: "User".to_string(),
+                field_name: "name".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_merge_generated_schema_directives() {
+        let base = user(&[("id", "Int")]);
+        let mut edited = base.clone();
+        let mut regenerated = base.clone();
+
+        // user changed the port and linked a proto file manually
+        edited.server.port = Some(8080);
+        edited.links = vec![Link {
+            src: "news.proto".to_string(),
+            type_of: LinkType::Protobuf,
+            ..Default::default()
+        }];
+        // user and the source changed the upstream differently
+        edited.upstream.http_cache = Some(42);
+        regenerated.upstream.http_cache = Some(10);
+
+        let merged = merge_generated(base, edited, regenerated);
+
+        assert_eq!(merged.config.server.port, Some(8080));
+        assert_eq!(merged.config.links.len(), 1);
+        assert_eq!(merged.config.upstream.http_cache, Some(10));
+        assert_eq!(
+            merged.conflicts,
+            vec![Change::DirectiveChanged { directive: "upstream" }]
+        );
+    }
+
+    #[test]
+    fn test_merge_generated_union_members() {
+        let union_ = |types: &[&str]| Union {
+            types: types.iter().map(|ty| ty.to_string()).collect(),
+            doc: None,
+        };
+        let mut base = user(&[("id", "Int")]);
+        base.unions.insert("Node".to_string(), union_(&["User"]));
+        let mut edited = base.clone();
+        let mut regenerated = base.clone();
+
+        // user added a member manually
+        edited
+            .unions
+            .insert("Node".to_string(), union_(&["User", "Admin"]));
+        // the source documented the union
+        let mut result = union_(&["User"]);
+        result.doc = Some("Node of the graph".to_string());
+        regenerated.unions.insert("Node".to_string(), result);
+
+        let merged = merge_generated(base.clone(), edited.clone(), regenerated);
+
+        assert_eq!(merged.config.unions["Node"].types, union_(&["User"]).types);
+        assert_eq!(
+            merged.conflicts,
+            vec![Change::TypeChanged { type_name: "Node".to_string() }]
+        );
+
+        // without the change of the source the member is kept
+        let merged = merge_generated(base.clone(), edited, base);
+
+        assert_eq!(
+            merged.config.unions["Node"].types,
+            union_(&["Admin", "User"]).types
+        );
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_merge_generated_type_edit() {
+        let base = user(&[("id", "Int")]);
+        let mut edited = base.clone();
+        let regenerated = user(&[("id", "Int"), ("email", "String")]);
+
+        // user documented the type and tagged it with a directive manually
+        let ty = edited.types.get_mut("User").unwrap();
+        ty.doc = Some("User of the app".to_string());
+        ty.directives = vec![Directive { name: "key".to_string(), arguments: Default::default() }];
+
+        let merged = merge_generated(base, edited, regenerated);
+        let ty = &merged.config.types["User"];
+
+        assert_eq!(ty.doc.as_deref(), Some("User of the app"));
+        assert_eq!(ty.directives.len(), 1);
+        assert_eq!(ty.fields.keys().collect::<Vec<_>>(), vec!["email", "id"]);
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_merge_generated_type_conflict() {
+        let base = user(&[("id", "Int")]);
+        let mut edited = base.clone();
+        let mut regenerated = base.clone();
+
+        edited.types.get_mut("User").unwrap().doc = Some("User of the app".to_string());
+        regenerated.types.get_mut("User").unwrap().doc = Some("Registered user".to_string());
+
+        let merged = merge_generated(base, edited, regenerated);
+
+        assert_eq!(
+            merged.config.types["User"].doc.as_deref(),
+            Some("Registered user")
+        );
+        assert_eq!(
+            merged.conflicts,
+            vec![Change::TypeChanged { type_name: "User".to_string() }]
+        );
+    }
+}
@@ -1,9 +1,13 @@
 pub use apollo::*;
 pub use config::*;
 pub use config_module::*;
+pub use diff::*;
 pub use directive::Directive;
 pub use directives::*;
 pub use key_values::*;
+pub use lint::*;
+pub use merge_checked::*;
+pub use merge_generated::*;
 pub use npo::QueryPath;
 pub use reader_context::*;
 pub use resolver::*;
@@ -13,13 +17,18 @@ mod apollo;
 mod config;
 mod config_module;
 pub mod cors;
+mod diff;
 mod directive;
 pub mod directives;
 mod from_document;
 pub mod group_by;
 mod headers;
 mod into_document;
+mod into_json_schema;
 mod key_values;
+mod lint;
+mod merge_checked;
+mod merge_generated;
 mod npo;
 pub mod reader;
 pub mod reader_context;
@@ -27,3 +36,4 @@ mod resolver;
 mod source;
 pub mod transformer;
 mod url_query;
+mod validate_resolvers;
@@ -1,4 +1,5 @@
 use std::path::Path;
+use std::time::Duration;
 
 use futures_util::future::join_all;
 use rustls_pemfile;
@@ -8,7 +9,8 @@ use rustls_pki_types::{
 use tailcall_valid::{Valid, ValidationError, Validator};
 use url::Url;
 
-use super::{ConfigModule, Content, Link, LinkType, PrivateKey};
+use super::{ConfigModule, Content, JwksRefresh, JwksSource, Link, LinkType, PrivateKey};
+use crate::core::auth::basic::validate_htpasswd;
 use crate::core::config::{Config, ConfigReaderContext, Source};
 use crate::core::proto_reader::ProtoReader;
 use crate::core::resource_reader::{Cached, Resource, ResourceReader};
@@ -103,6 +105,10 @@ impl ConfigReader {
                     let content = source.content;
                     extensions.keys = self.load_private_key(content).await?
                 }
+                LinkType::ClientCA => {
+                    let source = self.resource_reader.read_file(path).await?;
+                    extensions.client_ca = Some(source.content);
+                }
                 LinkType::Operation => {
                     let source = self.resource_reader.read_file(path).await?;
                     let content = source.content;
@@ -112,6 +118,7 @@ impl ConfigReader {
                 LinkType::Htpasswd => {
                     let source = self.resource_reader.read_file(path).await?;
                     let content = source.content;
+                    validate_htpasswd(&content)?;
 
                     extensions
                         .htpasswd
@@ -122,10 +129,19 @@ impl ConfigReader {
                     let content = source.content;
 
                     let de = &mut serde_json::Deserializer::from_str(&content);
+                    let refresh = link.refresh_interval.and_then(|interval| {
+                        let grace_period = link.refresh_grace_period.unwrap_or(interval);
+
+                        Some(JwksRefresh {
+                            url: Url::parse(&link.src).ok()?,
+                            interval: Duration::from_secs(interval),
+                            grace_period: Duration::from_secs(grace_period),
+                        })
+                    });
 
                     extensions.jwks.push(Content {
                         id: link.id.clone(),
-                        content: serde_path_to_error::deserialize(de)?,
+                        content: JwksSource { set: serde_path_to_error::deserialize(de)?, refresh },
                     })
                 }
                 LinkType::Grpc => {
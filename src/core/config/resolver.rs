@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::ops::Deref;
 
 use async_graphql::parser::types::ConstDirective;
@@ -50,6 +51,7 @@ impl Resolver {
                 entity_resolver
                     .resolver_by_type
                     .values()
+                    .flat_map(BTreeMap::values)
                     .any(Resolver::is_batched)
             }
             _ => false,
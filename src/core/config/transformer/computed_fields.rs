@@ -0,0 +1,124 @@
+use indexmap::IndexMap;
+use serde_json::Value;
+use tailcall_valid::{Valid, Validator};
+
+use crate::core::config::{Config, Expr, Field, Resolver};
+use crate::core::{Mustache, Transform, Type};
+
+/// A transformer that adds fields computed from the sibling fields of the type.
+/// Accepts a map of `Type.field` to the mustache template, e.g.
+/// `User.fullName` to `{{.value.firstName}} {{.value.lastName}}`, and injects
+/// a `String` field resolved by rendering the template against the parent
+/// value.
+pub struct ComputedFields(IndexMap<String, String>);
+
+impl ComputedFields {
+    pub fn new<I: Iterator<Item = (S, S)>, S: ToString>(fields: I) -> Self {
+        Self(
+            fields
+                .map(|(path, template)| (path.to_string(), template.to_string()))
+                .collect(),
+        )
+    }
+}
+
+impl Transform for ComputedFields {
+    type Value = Config;
+    type Error = String;
+
+    fn transform(&self, mut config: Self::Value) -> Valid<Self::Value, Self::Error> {
+        Valid::from_iter(self.0.iter(), |(path, template)| {
+            let Some((type_name, field_name)) = path.split_once('.') else {
+                return Valid::fail(format!(
+                    "Expected computed field in the format `Type.field`, found `{path}`"
+                ));
+            };
+
+            let Some(ty) = config.types.get_mut(type_name) else {
+                return Valid::fail(format!("Type '{type_name}' not found in config"));
+            };
+
+            if ty.fields.contains_key(field_name) {
+                return Valid::fail(format!(
+                    "Field '{field_name}' is already defined in '{type_name}'"
+                ));
+            }
+
+            if Mustache::parse(template).is_const() {
+                return Valid::fail(format!("Template `{template}` doesn't reference any value"));
+            }
+
+            let field = Field {
+                type_of: Type::from("String".to_owned()),
+                resolvers: Resolver::Expr(Expr { body: Value::String(template.to_owned()) }).into(),
+                ..Default::default()
+            };
+            ty.fields.insert(field_name.to_owned(), field);
+
+            Valid::succeed(())
+        })
+        .map_to(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tailcall_valid::Validator;
+
+    use super::ComputedFields;
+    use crate::core::blueprint::DynamicValue;
+    use crate::core::config::{Config, Resolver};
+    use crate::core::serde_value_ext::ValueExt;
+    use crate::core::transform::Transform;
+
+    const SDL: &str = r#"
+        schema {
+          query: Query
+        }
+
+        type Query {
+          user: User @http(url: "http://jsonplaceholder.typicode.com/users/1")
+        }
+
+        type User {
+          firstName: String
+          lastName: String
+        }
+    "#;
+
+    #[test]
+    fn test_computed_field() {
+        let config = Config::from_sdl(SDL).to_result().unwrap();
+        let transformer = ComputedFields::new(
+            [("User.fullName", "{{.value.firstName}} {{.value.lastName}}")].into_iter(),
+        );
+        let config = transformer.transform(config).to_result().unwrap();
+
+        let field = &config.find_type("User").unwrap().fields["fullName"];
+        assert_eq!(field.type_of.name(), "String");
+
+        let Some(Resolver::Expr(expr)) = field.resolvers.first() else {
+            panic!("expected expr resolver");
+        };
+        let value = DynamicValue::try_from(&expr.body).unwrap();
+        let ctx = json!({"value": {"firstName": "John", "lastName": "Doe"}});
+
+        assert_eq!(
+            value.render_value(&ctx),
+            async_graphql::Value::from_json(json!("John Doe")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_computed_field_conflict() {
+        let config = Config::from_sdl(SDL).to_result().unwrap();
+        let transformer =
+            ComputedFields::new([("User.firstName", "{{.value.lastName}}")].into_iter());
+        let error = transformer.transform(config).to_result().unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("Field 'firstName' is already defined in 'User'"));
+    }
+}
@@ -1,4 +1,5 @@
 mod ambiguous_type;
+mod computed_fields;
 mod flatten_single_field;
 mod improve_type_names;
 mod merge_types;
@@ -6,11 +7,13 @@ mod nested_unions;
 mod preset;
 mod rename_types;
 mod required;
+mod shared_args;
 mod subgraph;
 mod tree_shake;
 mod union_input_type;
 
 pub use ambiguous_type::{AmbiguousType, Resolution};
+pub use computed_fields::ComputedFields;
 pub use flatten_single_field::FlattenSingleField;
 pub use improve_type_names::ImproveTypeNames;
 pub use merge_types::TypeMerger;
@@ -18,6 +21,7 @@ pub use nested_unions::NestedUnions;
 pub use preset::Preset;
 pub use rename_types::RenameTypes;
 pub use required::Required;
+pub use shared_args::SharedArgs;
 pub use subgraph::Subgraph;
 pub use tree_shake::TreeShake;
 pub use union_input_type::UnionInputType;
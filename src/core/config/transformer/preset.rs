@@ -11,6 +11,7 @@ pub struct Preset {
     pub tree_shake: bool,
     pub infer_type_names: bool,
     pub unwrap_single_field_types: bool,
+    pub shared_args: bool,
 }
 
 impl Preset {
@@ -20,6 +21,7 @@ impl Preset {
             tree_shake: false,
             infer_type_names: true,
             unwrap_single_field_types: true,
+            shared_args: false,
         }
     }
 }
@@ -37,6 +39,7 @@ impl Transform for Preset {
                     .when(super::TypeMerger::is_enabled(self.merge_type)),
             )
             .pipe(super::FlattenSingleField.when(self.unwrap_single_field_types))
+            .pipe(super::SharedArgs.when(self.shared_args))
             .pipe(super::ImproveTypeNames.when(self.infer_type_names))
             .transform(config)
     }
@@ -49,6 +52,7 @@ impl Default for Preset {
             infer_type_names: true,
             tree_shake: true,
             unwrap_single_field_types: false,
+            shared_args: false,
         }
     }
 }
@@ -0,0 +1,258 @@
+use convert_case::{Case, Casing};
+use indexmap::IndexMap;
+use regex::{Captures, Regex};
+use tailcall_valid::{Valid, ValidationError, Validator};
+
+use crate::core::config::{Arg, Config, Field, Resolver, ResolverSet, Type};
+use crate::core::transform::Transform;
+
+/// Name of the argument that replaces the extracted args on every field.
+const SHARED_ARG_NAME: &str = "input";
+
+/// Extracts argument sets shared by multiple fields into a single input type
+/// and replaces the args of those fields with one argument referencing it.
+///
+/// Only sets with at least two args that appear on more than one field are
+/// extracted. Templates of the field resolvers are rewritten to read the
+/// values from the new argument, i.e. `{{.args.limit}}` becomes
+/// `{{.args.input.limit}}` and `{{.args}}` becomes `{{.args.input}}`.
+#[derive(Default)]
+pub struct SharedArgs;
+
+/// Args of a field together with `(type_name, field_name)` of every field
+/// that defines exactly the same args.
+type ArgGroup = (IndexMap<String, Arg>, Vec<(String, String)>);
+
+fn shared_arg_groups(config: &Config) -> Vec<ArgGroup> {
+    let mut groups: Vec<ArgGroup> = Vec::new();
+
+    for (type_name, ty) in config.types.iter() {
+        for (field_name, field) in ty.fields.iter() {
+            if field.args.len() < 2 {
+                continue;
+            }
+
+            let location = (type_name.to_owned(), field_name.to_owned());
+            match groups.iter_mut().find(|(args, _)| args == &field.args) {
+                Some((_, fields)) => fields.push(location),
+                None => groups.push((field.args.clone(), vec![location])),
+            }
+        }
+    }
+
+    groups.retain(|(_, fields)| fields.len() > 1);
+    groups
+}
+
+fn input_type_name(config: &Config, args: &IndexMap<String, Arg>) -> String {
+    let base_name = args
+        .keys()
+        .map(|name| name.to_case(Case::Pascal))
+        .collect::<String>();
+    let base_name = format!("{base_name}Input");
+
+    let mut name = base_name.clone();
+    let mut index = 1;
+    while config.contains(&name) {
+        name = format!("{base_name}{index}");
+        index += 1;
+    }
+
+    name
+}
+
+fn to_input_type(args: &IndexMap<String, Arg>) -> Type {
+    let fields = args
+        .iter()
+        .map(|(name, arg)| {
+            let field = Field {
+                type_of: arg.type_of.clone(),
+                doc: arg.doc.clone(),
+                modify: arg.modify.clone(),
+                default_value: arg.default_value.clone(),
+                ..Default::default()
+            };
+
+            (name.to_owned(), field)
+        })
+        .collect();
+
+    Type { fields, ..Default::default() }
+}
+
+/// Builds the regex matching the references to any of the args in mustache
+/// templates.
+fn args_pattern(args: &IndexMap<String, Arg>) -> Regex {
+    let names = args
+        .keys()
+        .map(|name| regex::escape(name))
+        .collect::<Vec<_>>()
+        .join("|");
+
+    // arg names are escaped so the pattern is always valid, the whole args
+    // object i.e. `{{.args}}` is matched as well
+    Regex::new(&format!(r"(\{{\{{\s*\.?args)(?:\.({names})\b|(\s*\}}\}}))")).unwrap()
+}
+
+fn rewrite_templates(value: &mut serde_json::Value, pattern: &Regex) {
+    match value {
+        serde_json::Value::String(template) => {
+            *template = pattern
+                .replace_all(template, |captures: &Captures| match captures.get(2) {
+                    Some(name) => {
+                        format!("{}.{SHARED_ARG_NAME}.{}", &captures[1], name.as_str())
+                    }
+                    None => format!("{}.{SHARED_ARG_NAME}{}", &captures[1], &captures[3]),
+                })
+                .into_owned();
+        }
+        serde_json::Value::Array(values) => {
+            values
+                .iter_mut()
+                .for_each(|value| rewrite_templates(value, pattern));
+        }
+        serde_json::Value::Object(map) => {
+            map.values_mut()
+                .for_each(|value| rewrite_templates(value, pattern));
+        }
+        _ => {}
+    }
+}
+
+fn rewrite_resolver(resolver: Resolver, pattern: &Regex) -> Valid<Resolver, String> {
+    // resolvers that can't be represented as json (e.g. federation entities)
+    // don't contain any templates and are kept as is
+    let Ok(mut value) = serde_json::to_value(&resolver) else {
+        return Valid::succeed(resolver);
+    };
+
+    rewrite_templates(&mut value, pattern);
+
+    match serde_json::from_value(value) {
+        Ok(resolver) => Valid::succeed(resolver),
+        Err(e) => Valid::fail(format!(
+            "Failed to rewrite the resolver with shared args: {e}"
+        )),
+    }
+}
+
+impl Transform for SharedArgs {
+    type Value = Config;
+    type Error = String;
+
+    fn transform(&self, mut config: Self::Value) -> Valid<Self::Value, Self::Error> {
+        let mut errors = ValidationError::empty();
+
+        for (args, fields) in shared_arg_groups(&config) {
+            let input_name = input_type_name(&config, &args);
+            let pattern = args_pattern(&args);
+
+            // the shared arg is required only if at least one of the args
+            // must be provided by the client
+            let is_required = args
+                .values()
+                .any(|arg| !arg.type_of.is_nullable() && arg.default_value.is_none());
+            let type_of = crate::core::Type::from(input_name.clone());
+            let type_of = if is_required {
+                type_of.into_required()
+            } else {
+                type_of
+            };
+
+            for (type_name, field_name) in fields {
+                let field = config
+                    .types
+                    .get_mut(&type_name)
+                    .and_then(|ty| ty.fields.get_mut(&field_name));
+
+                if let Some(field) = field {
+                    let shared_arg = Arg { type_of: type_of.clone(), ..Default::default() };
+                    field.args = IndexMap::from([(SHARED_ARG_NAME.to_owned(), shared_arg)]);
+                    let resolvers =
+                        Valid::from_iter(std::mem::take(&mut field.resolvers.0), |resolver| {
+                            rewrite_resolver(resolver, &pattern)
+                        })
+                        .trace(&field_name)
+                        .trace(&type_name);
+
+                    match resolvers.to_result() {
+                        Ok(resolvers) => field.resolvers = ResolverSet(resolvers),
+                        Err(e) => errors = errors.combine(e),
+                    }
+                }
+            }
+
+            config.types.insert(input_name, to_input_type(&args));
+        }
+
+        if errors.is_empty() {
+            Valid::succeed(config)
+        } else {
+            Valid::from_validation_err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tailcall_valid::Validator;
+
+    use super::SharedArgs;
+    use crate::core::config::Resolver;
+    use crate::core::transform::Transform;
+    use crate::include_config;
+
+    #[test]
+    fn test_shared_args() {
+        let config = include_config!("./fixtures/shared-args.graphql").unwrap();
+        let config = SharedArgs.transform(config).to_result().unwrap();
+
+        let input = config.find_type("LimitOffsetInput").unwrap();
+        assert_eq!(input.fields.len(), 2);
+        assert_eq!(input.fields["limit"].type_of.name(), "Int");
+        assert_eq!(input.fields["offset"].type_of.name(), "Int");
+
+        let query = config.find_type("Query").unwrap();
+        for field_name in ["users", "posts", "comments"] {
+            let field = &query.fields[field_name];
+            assert_eq!(field.args.len(), 1);
+            assert_eq!(field.args["input"].type_of.name(), "LimitOffsetInput");
+        }
+
+        let Resolver::Http(http) = &query.fields["users"].resolvers.0[0] else {
+            panic!("expected http resolver");
+        };
+        assert_eq!(
+            http.url,
+            "http://jsonplaceholder.typicode.com/users?_limit={{.args.input.limit}}&_start={{.args.input.offset}}"
+        );
+
+        // args used by a single field only are left untouched
+        let user = &query.fields["user"];
+        assert_eq!(user.args.len(), 2);
+        assert!(user.args.contains_key("id"));
+    }
+
+    #[test]
+    fn test_shared_args_whole_args() {
+        let config = include_config!("./fixtures/shared-args-body.graphql").unwrap();
+        let config = SharedArgs.transform(config).to_result().unwrap();
+
+        let query = config.find_type("Query").unwrap();
+        let body = |field_name: &str| {
+            let Resolver::Http(http) = &query.fields[field_name].resolvers.0[0] else {
+                panic!("expected http resolver");
+            };
+            http.body.clone()
+        };
+
+        assert_eq!(
+            body("searchUsers"),
+            Some(serde_json::json!("{{.args.input}}"))
+        );
+        assert_eq!(
+            body("searchAdmins"),
+            Some(serde_json::json!("{{ .args.input }}"))
+        );
+    }
+}
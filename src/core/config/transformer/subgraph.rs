@@ -43,36 +43,44 @@ impl Transform for Subgraph {
         let mut resolver_by_type = BTreeMap::new();
 
         let valid = Valid::from_iter(config.types.iter_mut(), |(type_name, ty)| {
-            if ty.resolvers.len() > 1 {
-                // TODO: should support multiple different resolvers actually, see https://www.apollographql.com/docs/graphos/schema-design/federated-schemas/entities/define-keys#multiple-keys
-                return Valid::fail(
-                    "Only single resolver for entity is currently supported".to_string(),
-                );
-            }
+            // every resolver of the type is an entity resolver for the separate key, see https://www.apollographql.com/docs/graphos/schema-design/federated-schemas/entities/define-keys#multiple-keys
+            let mut resolver_by_key = BTreeMap::new();
+
+            Valid::from_iter(ty.resolvers.iter(), |resolver| {
+                KeysExtractor::validate(&config_types, resolver, type_name)
+                    .and_then(|_| KeysExtractor::extract_keys(resolver))
+                    .and_then(|fields| {
+                        let fields = fields.unwrap_or_default();
+
+                        if resolver_by_key.contains_key(&fields) {
+                            return Valid::fail(format!(
+                                "Multiple resolvers for entity are defined with the same key `{fields}`"
+                            ));
+                        }
 
-            if let Some(resolver) = ty.resolvers.first() {
-                resolver_by_type.insert(type_name.clone(), resolver.clone());
-
-                KeysExtractor::validate(&config_types, resolver, type_name).and_then(|_| {
-                    KeysExtractor::extract_keys(resolver).and_then(|fields| match fields {
-                        Some(fields) => {
-                            let key = Key { fields };
-
-                            to_directive(key.to_directive()).map(|directive| {
-                                // Prevent transformer to push the same directive multiple times
-                                if !ty.directives.iter().any(|d| {
-                                    d.name == directive.name && d.arguments == directive.arguments
-                                }) {
-                                    ty.directives.push(directive);
-                                }
-                            })
+                        resolver_by_key.insert(fields.clone(), resolver.clone());
+
+                        if fields.is_empty() {
+                            return Valid::succeed(());
                         }
-                        None => Valid::succeed(()),
+
+                        let key = Key { fields };
+
+                        to_directive(key.to_directive()).map(|directive| {
+                            // Prevent transformer to push the same directive multiple times
+                            if !ty.directives.iter().any(|d| {
+                                d.name == directive.name && d.arguments == directive.arguments
+                            }) {
+                                ty.directives.push(directive);
+                            }
+                        })
                     })
-                })
-            } else {
-                Valid::succeed(())
-            }
+            })
+            .map(|_| {
+                if !resolver_by_key.is_empty() {
+                    resolver_by_type.insert(type_name.clone(), resolver_by_key);
+                }
+            })
             .trace(type_name)
         });
 
@@ -583,4 +591,64 @@ mod tests {
             assert_debug_snapshot!(keys);
         }
     }
+    mod transform {
+        use tailcall_valid::Validator;
+
+        use super::*;
+
+        const SDL: &str = r#"
+            schema @server(enableFederation: true) {
+              query: Query
+            }
+
+            type Query {
+              products: [Product] @http(url: "http://api.com/products")
+            }
+
+            type Product
+              @http(url: "http://api.com/products/{{.value.id}}")
+              @http(url: "http://api.com/products", query: [{key: "sku", value: "{{.value.sku}}"}]) {
+              id: Int!
+              sku: String!
+              name: String
+            }
+        "#;
+
+        #[test]
+        fn test_multiple_keys() {
+            let config = Config::from_sdl(SDL).to_result().unwrap();
+            let config = Subgraph.transform(config).to_result().unwrap();
+
+            let product = config.find_type("Product").unwrap();
+            let keys = product
+                .directives
+                .iter()
+                .filter(|directive| directive.name == "key")
+                .map(|directive| directive.arguments["fields"].as_str().unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(keys, vec!["id", "sku"]);
+
+            let query = config.find_type("Query").unwrap();
+            let Some(Resolver::ApolloFederation(ApolloFederation::EntityResolver(entity_resolver))) =
+                query.fields[ENTITIES_FIELD_NAME].resolvers.first()
+            else {
+                panic!("expected entity resolver");
+            };
+            let product_keys = entity_resolver.resolver_by_type["Product"]
+                .keys()
+                .collect::<Vec<_>>();
+            assert_eq!(product_keys, vec!["id", "sku"]);
+        }
+
+        #[test]
+        fn test_multiple_resolvers_with_same_key() {
+            let sdl = SDL.replace("{{.value.sku}}", "{{.value.id}}");
+            let config = Config::from_sdl(&sdl).to_result().unwrap();
+            let error = Subgraph.transform(config).to_result().unwrap_err();
+
+            assert!(error
+                .to_string()
+                .contains("Multiple resolvers for entity are defined with the same key `id`"));
+        }
+    }
 }
@@ -0,0 +1,179 @@
+use tailcall_valid::{Valid, Validator};
+
+use super::{Config, Field, Grpc, Http, Resolver, Type};
+use crate::core::blueprint::{FieldDefinition, InputFieldDefinition};
+use crate::core::directive::DirectiveCodec;
+use crate::core::Mustache;
+
+impl Config {
+    /// Checks that the mustache templates of the `@http` and `@grpc` resolvers
+    /// resolve against the arguments of the field and the fields of its parent
+    /// type, so a renamed argument or field doesn't leave a dangling template
+    /// that only fails at runtime. The templates are validated the same way as
+    /// while building the blueprint.
+    pub fn validate_resolvers(&self) -> Valid<(), String> {
+        Valid::from_iter(self.types.iter(), |(type_name, type_of)| {
+            Valid::from_iter(type_of.fields.iter(), |(field_name, field)| {
+                let field_definition = to_field_definition(field_name, field);
+
+                Valid::from_iter(field.resolvers.iter(), |resolver| match resolver {
+                    Resolver::Http(http) => self
+                        .validate_templates(type_of, &field_definition, http_templates(http))
+                        .trace(Http::trace_name().as_str()),
+                    Resolver::Grpc(grpc) => self
+                        .validate_templates(type_of, &field_definition, grpc_templates(grpc))
+                        .trace(Grpc::trace_name().as_str()),
+                    _ => Valid::succeed(()),
+                })
+                .trace(field_name)
+            })
+            .trace(type_name)
+        })
+        .unit()
+    }
+
+    fn validate_templates(
+        &self,
+        type_of: &Type,
+        field: &FieldDefinition,
+        templates: Vec<(&'static str, String, bool)>,
+    ) -> Valid<(), String> {
+        Valid::from_iter(templates, |(name, template, is_query)| {
+            let mustache = Mustache::parse(&template);
+            match field
+                .validate_template(type_of, self, &mustache, is_query)
+                .to_result()
+            {
+                Ok(()) => Valid::succeed(()),
+                Err(e) => Valid::from_validation_err(e.transform(&|e| e.to_string())),
+            }
+            .trace(&template)
+            .trace(name)
+        })
+        .unit()
+    }
+}
+
+/// Builds the part of the blueprint field definition that's used to validate
+/// the templates.
+fn to_field_definition(name: &str, field: &Field) -> FieldDefinition {
+    FieldDefinition {
+        name: name.to_string(),
+        args: field
+            .args
+            .iter()
+            .map(|(name, arg)| InputFieldDefinition {
+                name: name.clone(),
+                of_type: arg.type_of.clone(),
+                default_value: arg.default_value.clone(),
+                description: arg.doc.clone(),
+            })
+            .collect(),
+        of_type: field.type_of.clone(),
+        ..Default::default()
+    }
+}
+
+/// Lists the templates that are validated while building the blueprint along
+/// with whether they are validated as a query. Bodies of `@http` aren't
+/// validated since they can contain the whole `{{.args}}`.
+fn http_templates(http: &Http) -> Vec<(&'static str, String, bool)> {
+    let mut templates = vec![("url", http.url.clone(), false)];
+    templates.extend(
+        http.query
+            .iter()
+            .map(|query| ("query", query.value.clone(), true)),
+    );
+    templates
+}
+
+fn grpc_templates(grpc: &Grpc) -> Vec<(&'static str, String, bool)> {
+    let mut templates = vec![("url", grpc.url.clone(), false)];
+    templates.extend(
+        grpc.headers
+            .iter()
+            .map(|header| ("headers", header.value.clone(), true)),
+    );
+    templates.extend(
+        grpc.body
+            .iter()
+            .map(|body| ("body", body.to_string(), true)),
+    );
+    templates
+}
+
+#[cfg(test)]
+mod tests {
+    use tailcall_valid::{Valid, Validator};
+
+    use crate::core::config::Config;
+
+    #[test]
+    fn test_validate_resolvers() {
+        let config = Config::from_sdl(
+            r#"
+            schema {
+              query: Query
+              mutation: Mutation
+            }
+
+            input Filter {
+              status: String
+            }
+
+            type Query {
+              user(id: Int!): User @http(url: "http://jsonplaceholder.typicode.com/users/{{.args.id}}")
+              posts(filter: Filter): [Post] @http(url: "http://jsonplaceholder.typicode.com/posts", query: [{key: "status", value: "{{.args.filter.status}}"}])
+            }
+
+            type User {
+              id: Int!
+              posts: [Post] @http(url: "http://jsonplaceholder.typicode.com/users/{{.value.id}}/posts", headers: [{key: "x-auth", value: "{{.headers.authorization}}"}])
+            }
+
+            type Mutation {
+              createUser(name: String!, email: String!): User @http(method: POST, url: "http://jsonplaceholder.typicode.com/users", body: "{{.args}}")
+            }
+
+            type Post {
+              id: Int!
+            }
+            "#,
+        )
+        .to_result()
+        .unwrap();
+
+        assert_eq!(config.validate_resolvers(), Valid::succeed(()));
+    }
+
+    #[test]
+    fn test_validate_resolvers_dangling_argument() {
+        let config = Config::from_sdl(
+            r#"
+            schema {
+              query: Query
+            }
+
+            type Query {
+              user(id: Int!): User @http(url: "http://jsonplaceholder.typicode.com/users/{{.args.missing}}")
+            }
+
+            type User {
+              id: Int!
+            }
+            "#,
+        )
+        .to_result()
+        .unwrap();
+
+        assert_eq!(
+            config.validate_resolvers(),
+            Valid::fail("no argument 'missing' found".to_string())
+                .trace("http://jsonplaceholder.typicode.com/users/{{.args.missing}}")
+                .trace("url")
+                .trace("@http")
+                .trace("user")
+                .trace("Query")
+        );
+    }
+}
@@ -1,9 +1,14 @@
-use std::collections::HashMap;
 use std::hash::Hash;
+use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
 
+use futures_timer::Delay;
 use futures_util::Future;
+use lru::LruCache;
+use rand::Rng;
 use tokio::sync::broadcast;
+use web_time::Instant;
 
 pub trait Key: Send + Sync + Eq + Hash + Clone {}
 impl<A: Send + Sync + Eq + Hash + Clone> Key for A {}
@@ -15,17 +20,27 @@ impl<A: Send + Sync + Clone> Value for A {}
 /// Allows deduplication of async operations based on a key.
 pub struct Dedupe<Key, Value> {
     /// Cache storage for the operations.
-    cache: Arc<Mutex<HashMap<Key, State<Value>>>>,
+    cache: Arc<Mutex<LruCache<Key, State<Value>>>>,
     /// Initial size of the multi-producer, multi-consumer channel.
     size: usize,
     /// When enabled allows the operations to be cached forever.
     persist: bool,
+    /// Maximum number of entries in the cache. Once exceeded, the least
+    /// recently used results are evicted.
+    max_entries: Option<usize>,
+    /// Time after which the cached result is considered stale and the
+    /// operation is executed again.
+    ttl: Option<Duration>,
+    /// Decides whether a computed value is persisted. Values that are not
+    /// retained are shared with the concurrent calls only.
+    retain: fn(&Value) -> bool,
 }
 
 /// Represents the current state of the operation.
 enum State<Value> {
-    /// Means that the operation has been executed and the result is stored.
-    Ready(Value),
+    /// Means that the operation has been executed and the result is stored
+    /// until the optional expiry time.
+    Ready(Value, Option<Instant>),
 
     /// Means that the operation is in progress and the result can be sent via
     /// the stored sender whenever it's available in the future.
@@ -48,7 +63,22 @@ enum Step<Value> {
 
 impl<K: Key, V: Value> Dedupe<K, V> {
     pub fn new(size: usize, persist: bool) -> Self {
-        Self { cache: Arc::new(Mutex::new(HashMap::new())), size, persist }
+        Self {
+            cache: Arc::new(Mutex::new(LruCache::unbounded())),
+            size,
+            persist,
+            max_entries: None,
+            ttl: None,
+            retain: |_| true,
+        }
+    }
+
+    pub fn with_capacity(size: usize, persist: bool, max_entries: usize) -> Self {
+        Self { max_entries: Some(max_entries), ..Self::new(size, persist) }
+    }
+
+    pub fn new_with_ttl(size: usize, ttl: Duration) -> Self {
+        Self { ttl: Some(ttl), ..Self::new(size, true) }
     }
 
     pub async fn dedupe<'a, Fn, Fut>(&'a self, key: &'a K, or_else: Fn) -> V
@@ -72,10 +102,12 @@ impl<K: Key, V: Value> Dedupe<K, V> {
                 Step::Init(tx) => {
                     let value = or_else().await;
                     let mut guard = self.cache.lock().unwrap();
-                    if self.persist {
-                        guard.insert(key.to_owned(), State::Ready(value.clone()));
+                    if self.persist && (self.retain)(&value) {
+                        let expires_at = self.ttl.map(|ttl| Instant::now() + ttl);
+                        guard.put(key.to_owned(), State::Ready(value.clone(), expires_at));
+                        self.evict(&mut guard);
                     } else {
-                        guard.remove(key);
+                        guard.pop(key);
                     }
                     let _ = tx.send(value.clone());
                     value
@@ -91,7 +123,12 @@ impl<K: Key, V: Value> Dedupe<K, V> {
 
         if let Some(state) = this.get(key) {
             match state {
-                State::Ready(value) => return Step::Return(value.clone()),
+                State::Ready(value, expires_at) => {
+                    // stale results are replaced by executing the operation again
+                    if !expires_at.is_some_and(|expires_at| expires_at <= Instant::now()) {
+                        return Step::Return(value.clone());
+                    }
+                }
                 State::Pending(tx) => {
                     // We can upgrade from Weak to Arc only in case when
                     // original tx is still alive
@@ -109,16 +146,111 @@ impl<K: Key, V: Value> Dedupe<K, V> {
         // to control if tx is still alive and will be able to handle the request.
         // Only single `strong` reference to tx should exist so we can
         // understand when the execution is still alive and we'll get the response
-        this.insert(key.to_owned(), State::Pending(Arc::downgrade(&tx)));
+        this.put(key.to_owned(), State::Pending(Arc::downgrade(&tx)));
         Step::Init(tx)
     }
+
+    /// Evicts the least recently used results until the cache fits into
+    /// `max_entries`. Operations in progress are never evicted, so the
+    /// concurrent calls with the same key are still executed only once.
+    fn evict(&self, cache: &mut LruCache<K, State<V>>) {
+        let Some(max_entries) = self.max_entries else {
+            return;
+        };
+
+        while cache.len() > max_entries {
+            let key = cache
+                .iter()
+                .rev()
+                .find(|(_, state)| matches!(state, State::Ready(..)))
+                .map(|(key, _)| key.clone());
+
+            match key {
+                Some(key) => {
+                    cache.pop(&key);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+pub struct DedupeResult<K, V, E> {
+    dedupe: Dedupe<K, Result<V, E>>,
+    retry: Option<Retry<K>>,
+}
+
+/// Maximum number of keys whose consecutive failures are tracked. Once
+/// exceeded, the keys that failed least recently start over from the initial
+/// jitter.
+const MAX_TRACKED_FAILURES: usize = 1024;
+
+/// Backoff applied before recomputing a key whose previous attempt failed.
+struct Retry<K> {
+    /// Upper bound of the jitter after the first failure. It's doubled on
+    /// every consecutive failure.
+    jitter: Duration,
+    /// Upper bound of the jitter regardless of the number of failures.
+    max_jitter: Duration,
+    /// Number of consecutive failures per key.
+    failures: Mutex<LruCache<K, u32>>,
 }
 
-pub struct DedupeResult<K, V, E>(Dedupe<K, Result<V, E>>);
+impl<K: Key> Retry<K> {
+    /// Returns a random delay in `[0, min(jitter * 2^(failures - 1),
+    /// max_jitter)]`, or `None` if the last attempt for the key succeeded.
+    fn delay(&self, key: &K) -> Option<Duration> {
+        let failures = *self.failures.lock().unwrap().peek(key)?;
+        let upper = self
+            .jitter
+            .saturating_mul(2u32.saturating_pow(failures.saturating_sub(1)))
+            .min(self.max_jitter);
+
+        Some(rand::thread_rng().gen_range(Duration::ZERO..=upper))
+    }
+
+    fn record(&self, key: &K, success: bool) {
+        let mut failures = self.failures.lock().unwrap();
+        if success {
+            failures.pop(key);
+        } else {
+            *failures.get_or_insert_mut(key.clone(), || 0) += 1;
+        }
+    }
+}
 
 impl<K: Key, V: Value, E: Value> DedupeResult<K, V, E> {
     pub fn new(persist: bool) -> Self {
-        Self(Dedupe::new(1, persist))
+        Self { dedupe: Dedupe::new(1, persist), retry: None }
+    }
+
+    pub fn with_capacity(persist: bool, max_entries: usize) -> Self {
+        Self {
+            dedupe: Dedupe::with_capacity(1, persist, max_entries),
+            retry: None,
+        }
+    }
+
+    pub fn new_with_ttl(ttl: Duration) -> Self {
+        Self { dedupe: Dedupe::new_with_ttl(1, ttl), retry: None }
+    }
+
+    /// Errors are never persisted, so the next call after a failure computes
+    /// the result again. Before doing so it waits for a random delay of up to
+    /// `jitter`, doubled on every consecutive failure and capped at
+    /// `max_jitter`, so that the retries of a failing upstream are not
+    /// synchronized.
+    pub fn with_retry(persist: bool, jitter: Duration, max_jitter: Duration) -> Self {
+        Self {
+            dedupe: Dedupe { retain: Result::is_ok, ..Dedupe::new(1, persist) },
+            retry: Some(Retry {
+                jitter,
+                max_jitter,
+                failures: Mutex::new(LruCache::new(
+                    NonZeroUsize::new(MAX_TRACKED_FAILURES).unwrap(),
+                )),
+            }),
+        }
     }
 }
 
@@ -128,7 +260,20 @@ impl<K: Key, V: Value, E: Value> DedupeResult<K, V, E> {
         Fn: FnOnce() -> Fut,
         Fut: Future<Output = Result<V, E>>,
     {
-        self.0.dedupe(key, or_else).await
+        let Some(retry) = &self.retry else {
+            return self.dedupe.dedupe(key, or_else).await;
+        };
+
+        self.dedupe
+            .dedupe(key, || async move {
+                if let Some(delay) = retry.delay(key) {
+                    Delay::new(delay).await;
+                }
+                let result = or_else().await;
+                retry.record(key, result.is_ok());
+                result
+            })
+            .await
     }
 }
 
@@ -343,6 +488,117 @@ mod tests {
         assert_eq!(actual, Status { call_1: true, call_2: false })
     }
 
+    #[tokio::test]
+    async fn test_with_capacity() {
+        let cache = DedupeResult::<u64, u64, ()>::with_capacity(true, 2);
+        let counter = Arc::new(AtomicUsize::new(0));
+        let compute = |value: u64| {
+            let counter = counter.clone();
+            move || async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                sleep(Duration::from_millis(10)).await;
+                Ok(value)
+            }
+        };
+
+        for key in 1..=3 {
+            cache.dedupe(&key, compute(key)).await.unwrap();
+        }
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+
+        // the latest entries are still cached
+        assert_eq!(cache.dedupe(&3, compute(30)).await, Ok(3));
+        assert_eq!(cache.dedupe(&2, compute(20)).await, Ok(2));
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+
+        // the least recently used entry is evicted
+        assert_eq!(cache.dedupe(&1, compute(10)).await, Ok(10));
+        assert_eq!(counter.load(Ordering::SeqCst), 4);
+
+        // concurrent calls are deduplicated while the capacity is exceeded
+        let (a, b) = join!(cache.dedupe(&4, compute(4)), cache.dedupe(&4, compute(40)));
+        assert_eq!(a, Ok(4));
+        assert_eq!(b, Ok(4));
+        assert_eq!(counter.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_with_ttl() {
+        let cache = DedupeResult::<u64, usize, ()>::new_with_ttl(Duration::from_millis(50));
+        let counter = Arc::new(AtomicUsize::new(0));
+        let compute = || {
+            let counter = counter.clone();
+            move || async move {
+                let value = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                sleep(Duration::from_millis(10)).await;
+                Ok(value)
+            }
+        };
+
+        // burst within the ttl is computed once
+        let (a, b, c) = join!(
+            cache.dedupe(&1, compute()),
+            cache.dedupe(&1, compute()),
+            cache.dedupe(&1, compute())
+        );
+        assert_eq!((a, b, c), (Ok(1), Ok(1), Ok(1)));
+        assert_eq!(cache.dedupe(&1, compute()).await, Ok(1));
+
+        sleep(Duration::from_millis(60)).await;
+
+        // the stale value is computed again
+        assert_eq!(cache.dedupe(&1, compute()).await, Ok(2));
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_with_retry() {
+        let cache = DedupeResult::<u64, usize, usize>::with_retry(
+            true,
+            Duration::from_millis(1),
+            Duration::from_millis(5),
+        );
+        let counter = Arc::new(AtomicUsize::new(0));
+        let compute = || {
+            let counter = counter.clone();
+            move || async move {
+                let attempt = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                sleep(Duration::from_millis(10)).await;
+                if attempt < 3 {
+                    Err(attempt)
+                } else {
+                    Ok(attempt)
+                }
+            }
+        };
+
+        // concurrent calls share the failure of a single attempt
+        let (a, b) = join!(cache.dedupe(&1, compute()), cache.dedupe(&1, compute()));
+        assert_eq!((a, b), (Err(1), Err(1)));
+
+        // the error is not cached, so the next calls try again
+        assert_eq!(cache.dedupe(&1, compute()).await, Err(2));
+        assert_eq!(cache.dedupe(&1, compute()).await, Ok(3));
+
+        // the successful result is cached
+        assert_eq!(cache.dedupe(&1, compute()).await, Ok(3));
+        assert_eq!(counter.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_with_retry_bounded_failures() {
+        let cache =
+            DedupeResult::<usize, (), ()>::with_retry(false, Duration::ZERO, Duration::ZERO);
+
+        for key in 0..MAX_TRACKED_FAILURES * 2 {
+            assert_eq!(cache.dedupe(&key, || async { Err(()) }).await, Err(()));
+        }
+
+        let retry = cache.retry.as_ref().unwrap();
+        assert_eq!(retry.failures.lock().unwrap().len(), MAX_TRACKED_FAILURES);
+        assert!(retry.delay(&0).is_none());
+    }
+
     #[tokio::test]
     async fn test_should_abort_all() {
         #[derive(Debug, PartialEq, Clone)]
@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Context as _, Result};
+use serde_json::{Map, Value};
+
+use super::graphql_type::{GraphQLType, Unparsed};
+use crate::core::config::{self, Config, Enum, Expr, Field, Resolver, Variant};
+use crate::core::Type;
+
+/// Scalar used to represent Avro binary data i.e. `bytes` and `fixed`.
+const BYTES: &str = "Bytes";
+
+/// Assists in the mapping of Avro schemas to the config types.
+struct Context {
+    /// Final configuration that's being built up.
+    config: Config,
+
+    /// Full names of the visited named Avro types mapped to the GraphQL type
+    /// names.
+    named_types: HashMap<String, String>,
+}
+
+impl Context {
+    fn new() -> Self {
+        Self { config: Default::default(), named_types: Default::default() }
+    }
+
+    /// Resolves the GraphQL type for the Avro schema, defining the named types
+    /// met along the way. Avro values are never null unless the schema is a
+    /// union with `null`, so all the resolved types are required by default.
+    fn to_type(&mut self, schema: &Value, namespace: Option<&str>) -> Result<Type> {
+        match schema {
+            Value::String(name) => self.to_named_type(name, namespace),
+            Value::Array(members) => self.to_union_type(members, namespace),
+            Value::Object(object) => {
+                let type_name = object
+                    .get("type")
+                    .and_then(Value::as_str)
+                    .context("Avro schema is missing the type")?;
+
+                match type_name {
+                    "record" | "error" => self.append_record(object, namespace),
+                    "enum" => self.append_enum(object, namespace),
+                    "fixed" => {
+                        let (full_name, _) = full_name(object, namespace)?;
+                        self.named_types.insert(full_name, BYTES.to_string());
+
+                        Ok(Type::from(BYTES.to_string()).into_required())
+                    }
+                    "array" => {
+                        let items = object.get("items").context("Avro array is missing items")?;
+                        let type_of = self.to_type(items, namespace)?;
+
+                        Ok(type_of.into_list().into_required())
+                    }
+                    // maps have arbitrary keys that can't be expressed by GraphQL types
+                    "map" => Ok(Type::from("JSON".to_string()).into_required()),
+                    // primitive types with attributes i.e. logical types
+                    _ => self.to_named_type(type_name, namespace),
+                }
+            }
+            _ => bail!("Unsupported Avro schema: {schema}"),
+        }
+    }
+
+    /// Resolves primitive types and references to already defined named
+    /// types.
+    fn to_named_type(&self, name: &str, namespace: Option<&str>) -> Result<Type> {
+        let type_name = match name {
+            "null" => return Ok(Type::from("JSON".to_string())),
+            "boolean" => "Boolean",
+            "int" => "Int",
+            // use string-backed scalar for 64bit integers to avoid precision loss
+            "long" => "Int64",
+            "float" | "double" => "Float",
+            "string" => "String",
+            "bytes" => BYTES,
+            _ => {
+                let full_name = match namespace {
+                    Some(namespace) if !name.contains('.') => format!("{namespace}.{name}"),
+                    _ => name.to_string(),
+                };
+
+                self.named_types
+                    .get(&full_name)
+                    .with_context(|| format!("Unknown Avro type {full_name}"))?
+                    .as_str()
+            }
+        };
+
+        Ok(Type::from(type_name.to_string()).into_required())
+    }
+
+    /// Converts Avro unions. Union with `null` makes the type nullable, while
+    /// the unions of multiple non-null types are represented as `JSON`.
+    fn to_union_type(&mut self, members: &[Value], namespace: Option<&str>) -> Result<Type> {
+        let is_nullable = members.iter().any(|member| member == "null");
+        let members = members
+            .iter()
+            .filter(|member| *member != "null")
+            .collect::<Vec<_>>();
+
+        let type_of = match members.as_slice() {
+            [member] => self.to_type(member, namespace)?,
+            _ => {
+                // define named types anyway since they can be referenced later
+                for member in members.iter() {
+                    self.to_type(member, namespace)?;
+                }
+
+                Type::from("JSON".to_string()).into_required()
+            }
+        };
+
+        if is_nullable {
+            Ok(type_of.into_nullable())
+        } else {
+            Ok(type_of)
+        }
+    }
+
+    /// Converts Avro record to the object type.
+    fn append_record(
+        &mut self,
+        record: &Map<String, Value>,
+        namespace: Option<&str>,
+    ) -> Result<Type> {
+        let (full_name, namespace) = full_name(record, namespace)?;
+        let type_name = to_graphql_type(&full_name).into_object_type().to_string();
+
+        // register the name before the fields to support recursive records
+        self.named_types.insert(full_name, type_name.clone());
+
+        let mut ty = config::Type { doc: doc(record), ..Default::default() };
+
+        let fields = record
+            .get("fields")
+            .and_then(Value::as_array)
+            .with_context(|| format!("Avro record {type_name} is missing fields"))?;
+
+        for field in fields {
+            let field = field
+                .as_object()
+                .with_context(|| format!("Invalid field in Avro record {type_name}"))?;
+            let field_name = field
+                .get("name")
+                .and_then(Value::as_str)
+                .with_context(|| format!("Avro record {type_name} has a field without name"))?;
+            let schema = field.get("type").with_context(|| {
+                format!("Field {field_name} of Avro record {type_name} is missing the type")
+            })?;
+
+            // field names are kept as is, since Avro encodes the values with the
+            // exact names from the schema
+            let cfg_field = Field {
+                type_of: self.to_type(schema, namespace.as_deref())?,
+                doc: doc(field),
+                ..Default::default()
+            };
+
+            ty.fields.insert(field_name.to_string(), cfg_field);
+        }
+
+        self.config.types.insert(type_name.clone(), ty);
+
+        Ok(Type::from(type_name).into_required())
+    }
+
+    /// Converts Avro enum to the GraphQL enum.
+    fn append_enum(&mut self, enum_: &Map<String, Value>, namespace: Option<&str>) -> Result<Type> {
+        let (full_name, _) = full_name(enum_, namespace)?;
+        let type_name = to_graphql_type(&full_name).into_enum().to_string();
+
+        let variants = enum_
+            .get("symbols")
+            .and_then(Value::as_array)
+            .with_context(|| format!("Avro enum {type_name} is missing symbols"))?
+            .iter()
+            .filter_map(Value::as_str)
+            .map(|symbol| Variant { name: symbol.to_string(), alias: None, doc: None })
+            .collect();
+
+        self.named_types.insert(full_name, type_name.clone());
+        self.config
+            .enums
+            .insert(type_name.clone(), Enum { variants, doc: doc(enum_) });
+
+        Ok(Type::from(type_name).into_required())
+    }
+}
+
+/// Resolves the full name of the named type along with the namespace for the
+/// types nested in it.
+fn full_name(
+    schema: &Map<String, Value>,
+    namespace: Option<&str>,
+) -> Result<(String, Option<String>)> {
+    let name = schema
+        .get("name")
+        .and_then(Value::as_str)
+        .context("Avro named type is missing the name")?;
+
+    if let Some((namespace, _)) = name.rsplit_once('.') {
+        return Ok((name.to_string(), Some(namespace.to_string())));
+    }
+
+    let namespace = schema
+        .get("namespace")
+        .and_then(Value::as_str)
+        .or(namespace)
+        .filter(|namespace| !namespace.is_empty());
+
+    match namespace {
+        Some(namespace) => Ok((format!("{namespace}.{name}"), Some(namespace.to_string()))),
+        None => Ok((name.to_string(), None)),
+    }
+}
+
+fn to_graphql_type(full_name: &str) -> GraphQLType<Unparsed> {
+    match full_name.rsplit_once('.') {
+        Some((namespace, name)) => GraphQLType::new(name).push(namespace),
+        None => GraphQLType::new(full_name),
+    }
+}
+
+fn doc(schema: &Map<String, Value>) -> Option<String> {
+    schema.get("doc").and_then(Value::as_str).map(String::from)
+}
+
+/// Builds the config from the Avro schema. Every top-level record is exposed
+/// as a field of the query type. Avro doesn't describe where the records come
+/// from, so the fields resolve to `null` until their resolver is replaced with
+/// the actual source.
+pub fn from_avro(schema: &str, query: &str) -> Result<Config> {
+    let schema: Value = serde_json::from_str(schema)?;
+
+    // Avro schema could be a list of named types as well
+    let schemas = match &schema {
+        Value::Array(schemas) => schemas.iter().collect(),
+        schema => vec![schema],
+    };
+
+    let mut ctx = Context::new();
+    let mut query_type = config::Type::default();
+
+    for schema in schemas {
+        let type_of = ctx.to_type(schema, None)?;
+
+        if ctx.config.types.contains_key(type_of.name()) {
+            let field_name = schema
+                .get("name")
+                .and_then(Value::as_str)
+                .map(|name| name.rsplit('.').next().unwrap_or(name))
+                .unwrap_or(type_of.name());
+            let field_name = GraphQLType::new(field_name).into_field().to_string();
+
+            query_type.fields.insert(
+                field_name,
+                Field {
+                    type_of: type_of.into_nullable(),
+                    resolvers: Resolver::Expr(Expr { body: Value::Null }).into(),
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    let mut config = ctx.config;
+    config.types.insert(query.to_string(), query_type);
+    config.schema.query = Some(query.to_string());
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+    use tailcall_fixtures::avro;
+
+    use super::from_avro;
+    use crate::core::blueprint::Blueprint;
+    use crate::core::config::ConfigModule;
+
+    #[test]
+    fn test_from_avro() -> Result<()> {
+        let schema = fs::read_to_string(avro::USER)?;
+        let config = from_avro(&schema, "Query")?;
+
+        insta::assert_snapshot!(ConfigModule::from(config).to_sdl());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_avro_blueprint() -> Result<()> {
+        let schema = fs::read_to_string(avro::USER)?;
+        let config = from_avro(&schema, "Query")?;
+
+        Blueprint::try_from(&ConfigModule::from(config))?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_reference() {
+        let schema = r#"{
+            "type": "record",
+            "name": "Order",
+            "fields": [{"name": "user", "type": "User"}]
+        }"#;
+        let error = from_avro(schema, "Query").unwrap_err();
+
+        assert_eq!(error.to_string(), "Unknown Avro type User");
+    }
+}
@@ -0,0 +1,103 @@
+use anyhow::Result;
+use tailcall_valid::Validator;
+
+use crate::core::config::{Config, GraphQL, KeyValue, Resolver};
+
+/// Builds the config from the SDL of an existing GraphQL API. The types of the
+/// SDL are kept as is, while every root field without a resolver gets a
+/// `@graphQL` resolver requesting the same field from the upstream.
+pub fn from_graphql_sdl(sdl: &str, url: &str) -> Result<Config> {
+    let mut config = Config::from_sdl(sdl).to_result()?;
+
+    // SDL of a service usually relies on the default names of the root types
+    if config.schema.query.is_none() && config.types.contains_key("Query") {
+        config.schema.query = Some("Query".to_string());
+    }
+    if config.schema.mutation.is_none() && config.types.contains_key("Mutation") {
+        config.schema.mutation = Some("Mutation".to_string());
+    }
+
+    let roots = [config.schema.query.clone(), config.schema.mutation.clone()];
+
+    for root in roots.into_iter().flatten() {
+        let Some(root) = config.types.get_mut(&root) else {
+            continue;
+        };
+
+        for (field_name, field) in root.fields.iter_mut() {
+            if field.resolvers.has_resolver() {
+                continue;
+            }
+
+            let args = field
+                .args
+                .keys()
+                .map(|arg| KeyValue { key: arg.clone(), value: format!("{{{{.args.{arg}}}}}") })
+                .collect::<Vec<_>>();
+
+            field.resolvers = Resolver::Graphql(GraphQL {
+                args: (!args.is_empty()).then_some(args),
+                url: url.to_string(),
+                name: field_name.clone(),
+                ..Default::default()
+            })
+            .into();
+        }
+    }
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+    use tailcall_fixtures::graphql;
+
+    use super::from_graphql_sdl;
+    use crate::core::config::{Config, KeyValue, Resolver};
+
+    const URL: &str = "http://upstream/graphql";
+
+    fn resolver(config: &Config, type_name: &str, field_name: &str) -> Option<Resolver> {
+        config.types[type_name].fields[field_name]
+            .resolvers
+            .first()
+            .cloned()
+    }
+
+    #[test]
+    fn test_from_graphql_sdl() -> Result<()> {
+        let sdl = fs::read_to_string(graphql::USERS)?;
+        let config = from_graphql_sdl(&sdl, URL)?;
+
+        assert_eq!(config.schema.query.as_deref(), Some("Query"));
+        assert_eq!(config.schema.mutation.as_deref(), Some("Mutation"));
+
+        let Some(Resolver::Graphql(user)) = resolver(&config, "Query", "user") else {
+            panic!("Expected @graphQL resolver on Query.user");
+        };
+        assert_eq!(user.url, URL);
+        assert_eq!(user.name, "user");
+        assert_eq!(
+            user.args,
+            Some(vec![KeyValue {
+                key: "id".to_string(),
+                value: "{{.args.id}}".to_string()
+            }])
+        );
+
+        let Some(Resolver::Graphql(create_user)) = resolver(&config, "Mutation", "createUser")
+        else {
+            panic!("Expected @graphQL resolver on Mutation.createUser");
+        };
+        assert_eq!(create_user.name, "createUser");
+
+        // only the root fields are resolved by the upstream
+        assert!(resolver(&config, "User", "posts").is_none());
+        assert!(config.enums.contains_key("Role"));
+
+        Ok(())
+    }
+}
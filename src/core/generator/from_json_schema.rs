@@ -0,0 +1,370 @@
+use std::collections::{BTreeSet, HashMap};
+
+use anyhow::{bail, Context as _, Result};
+use convert_case::{Case, Casing};
+use serde_json::{Map, Value};
+
+use super::graphql_type::GraphQLType;
+use crate::core::config::{self, Config, Enum, Field, Variant};
+use crate::core::Type;
+
+/// Keywords under which the reusable schemas are defined. `definitions` is
+/// used by the drafts preceding 2019-09.
+const DEFINITIONS: &[&str] = &["$defs", "definitions"];
+
+/// Scalar used for the values that can't be expressed by GraphQL types.
+const JSON: &str = "JSON";
+
+/// Assists in the mapping of JSON Schemas to the config types.
+struct Context {
+    /// Final configuration that's being built up.
+    config: Config,
+
+    /// Reusable schemas of the root schema mapped by their names.
+    definitions: Map<String, Value>,
+
+    /// Names of the converted object and enum schemas mapped to the GraphQL
+    /// type names.
+    named_types: HashMap<String, String>,
+}
+
+impl Context {
+    fn new(root: &Value) -> Self {
+        let definitions = DEFINITIONS
+            .iter()
+            .filter_map(|keyword| root.get(keyword).and_then(Value::as_object))
+            .flat_map(|definitions| definitions.clone())
+            .collect();
+
+        Self {
+            config: Default::default(),
+            definitions,
+            named_types: Default::default(),
+        }
+    }
+
+    /// Resolves the GraphQL type for the JSON Schema, defining the object and
+    /// enum types met along the way under the provided name. The resolved
+    /// types are required unless the schema allows `null`, the optional
+    /// properties are made nullable by the object they belong to.
+    fn to_type(&mut self, schema: &Value, name: &str) -> Result<Type> {
+        let Value::Object(schema) = schema else {
+            // boolean schemas either accept any value or none at all
+            return Ok(Type::from(JSON.to_string()));
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            return self.to_ref_type(reference);
+        }
+
+        if schema.contains_key("allOf") {
+            let schema = self.merge_all_of(schema)?;
+            return self.append_object(&schema, name);
+        }
+
+        if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            return self.append_enum(schema, values, name);
+        }
+
+        let (type_name, is_nullable) = match schema.get("type") {
+            Some(Value::String(type_name)) => (type_name.as_str(), false),
+            Some(Value::Array(types)) => {
+                let is_nullable = types.iter().any(|type_name| type_name == "null");
+                let types = types
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .filter(|type_name| *type_name != "null")
+                    .collect::<Vec<_>>();
+
+                match types.as_slice() {
+                    [type_name] => (*type_name, is_nullable),
+                    // unions of multiple types are represented as `JSON`
+                    _ => (JSON, is_nullable),
+                }
+            }
+            _ if schema.contains_key("properties") => ("object", false),
+            _ => (JSON, false),
+        };
+
+        let type_of = match type_name {
+            "object" => self.append_object(schema, name)?,
+            "array" => match schema.get("items") {
+                Some(items) => self.to_type(items, name)?.into_list().into_required(),
+                None => Type::from(JSON.to_string()).into_list().into_required(),
+            },
+            "string" => Type::from(string_type(schema).to_string()).into_required(),
+            "integer" => match schema.get("format").and_then(Value::as_str) {
+                // use string-backed scalar for 64bit integers to avoid precision loss
+                Some("int64") => Type::from("Int64".to_string()).into_required(),
+                _ => Type::from("Int".to_string()).into_required(),
+            },
+            "number" => Type::from("Float".to_string()).into_required(),
+            "boolean" => Type::from("Boolean".to_string()).into_required(),
+            _ => Type::from(JSON.to_string()),
+        };
+
+        if is_nullable {
+            Ok(type_of.into_nullable())
+        } else {
+            Ok(type_of)
+        }
+    }
+
+    /// Resolves the type of the reusable schema the reference points to.
+    fn to_ref_type(&mut self, reference: &str) -> Result<Type> {
+        let (name, schema) = self.resolve_ref(reference)?;
+
+        match self.named_types.get(&name) {
+            Some(type_name) => Ok(Type::from(type_name.clone()).into_required()),
+            None => self.to_type(&schema, &name),
+        }
+    }
+
+    /// Finds the reusable schema by a local reference, e.g. `#/$defs/User`.
+    fn resolve_ref(&self, reference: &str) -> Result<(String, Value)> {
+        let name = DEFINITIONS
+            .iter()
+            .find_map(|keyword| reference.strip_prefix(&format!("#/{keyword}/")))
+            .with_context(|| format!("Unsupported JSON Schema reference {reference}"))?;
+        let schema = self
+            .definitions
+            .get(name)
+            .with_context(|| format!("Unknown JSON Schema reference {reference}"))?;
+
+        Ok((name.to_string(), schema.clone()))
+    }
+
+    /// Combines the properties of the `allOf` schemas along with the own
+    /// properties of the schema into a single object schema.
+    fn merge_all_of(&self, schema: &Map<String, Value>) -> Result<Map<String, Value>> {
+        let mut merged = schema.clone();
+        merged.remove("allOf");
+        merged.insert("type".to_string(), Value::from("object"));
+
+        let members = schema
+            .get("allOf")
+            .and_then(Value::as_array)
+            .context("JSON Schema allOf must be a list")?;
+
+        for member in members {
+            let member = match member.get("$ref").and_then(Value::as_str) {
+                Some(reference) => self.resolve_ref(reference)?.1,
+                None => member.clone(),
+            };
+            let member = match member {
+                Value::Object(member) if member.contains_key("allOf") => {
+                    self.merge_all_of(&member)?
+                }
+                Value::Object(member) => member,
+                member => bail!("Unsupported allOf member in JSON Schema: {member}"),
+            };
+
+            for keyword in ["properties", "required"] {
+                let Some(value) = member.get(keyword) else {
+                    continue;
+                };
+
+                match (merged.get_mut(keyword), value) {
+                    (Some(Value::Object(existing)), Value::Object(value)) => {
+                        existing.extend(value.clone())
+                    }
+                    (Some(Value::Array(existing)), Value::Array(value)) => {
+                        existing.extend(value.clone())
+                    }
+                    _ => {
+                        merged.insert(keyword.to_string(), value.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(merged)
+    }
+
+    /// Converts object schema to the object type. Objects without properties
+    /// have arbitrary keys that can't be expressed by GraphQL types.
+    fn append_object(&mut self, schema: &Map<String, Value>, name: &str) -> Result<Type> {
+        let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+            return Ok(Type::from(JSON.to_string()).into_required());
+        };
+
+        if let Some(type_name) = self.named_types.get(name) {
+            return Ok(Type::from(type_name.clone()).into_required());
+        }
+
+        let type_name = GraphQLType::new(name).into_object_type().to_string();
+
+        // register the name before the properties to support recursive schemas
+        self.named_types.insert(name.to_string(), type_name.clone());
+
+        let required = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|required| required.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_else(BTreeSet::new);
+
+        let mut ty = config::Type { doc: doc(schema), ..Default::default() };
+
+        for (property_name, property) in properties {
+            let nested_name = format!("{name}{}", property_name.to_case(Case::Pascal));
+            let type_of = self.to_type(property, &nested_name)?;
+
+            // property names are kept as is, since they are the exact keys of the
+            // JSON values
+            let field = Field {
+                type_of: if required.contains(property_name.as_str()) {
+                    type_of
+                } else {
+                    type_of.into_nullable()
+                },
+                doc: property.as_object().and_then(doc),
+                ..Default::default()
+            };
+
+            ty.fields.insert(property_name.clone(), field);
+        }
+
+        self.config.types.insert(type_name.clone(), ty);
+
+        Ok(Type::from(type_name).into_required())
+    }
+
+    /// Converts enum schema with string values to the GraphQL enum, enums of
+    /// other values are represented as `JSON`.
+    fn append_enum(
+        &mut self,
+        schema: &Map<String, Value>,
+        values: &[Value],
+        name: &str,
+    ) -> Result<Type> {
+        if !values.iter().all(Value::is_string) {
+            return Ok(Type::from(JSON.to_string()).into_required());
+        }
+
+        if let Some(type_name) = self.named_types.get(name) {
+            return Ok(Type::from(type_name.clone()).into_required());
+        }
+
+        let type_name = GraphQLType::new(name).into_enum().to_string();
+
+        let variants = values
+            .iter()
+            .filter_map(Value::as_str)
+            .map(|value| Variant { name: value.to_string(), alias: None, doc: None })
+            .collect();
+
+        self.named_types.insert(name.to_string(), type_name.clone());
+        self.config
+            .enums
+            .insert(type_name.clone(), Enum { variants, doc: doc(schema) });
+
+        Ok(Type::from(type_name).into_required())
+    }
+}
+
+/// Picks the scalar for the string schema based on its format.
+fn string_type(schema: &Map<String, Value>) -> &'static str {
+    match schema.get("format").and_then(Value::as_str) {
+        Some("date-time") => "DateTime",
+        Some("date") => "Date",
+        Some("email") => "Email",
+        Some("uri") => "Url",
+        _ => "String",
+    }
+}
+
+fn doc(schema: &Map<String, Value>) -> Option<String> {
+    schema
+        .get("description")
+        .and_then(Value::as_str)
+        .map(String::from)
+}
+
+/// Builds the config from the JSON Schema. The root schema is exposed as a
+/// field of the query type when it describes an object, otherwise every object
+/// type of the reusable schemas is exposed.
+pub fn from_json_schema(schema: &str, query: &str) -> Result<Config> {
+    let schema: Value = serde_json::from_str(schema)?;
+    let mut ctx = Context::new(&schema);
+
+    // reusable schemas are converted even when they aren't referenced
+    let definitions = ctx.definitions.clone();
+    for (name, definition) in definitions.iter() {
+        if !ctx.named_types.contains_key(name) {
+            ctx.to_type(definition, name)?;
+        }
+    }
+
+    let root_name = schema
+        .get("title")
+        .and_then(Value::as_str)
+        .unwrap_or("Root")
+        .to_case(Case::Pascal);
+    let root_type = ctx.to_type(&schema, &root_name)?;
+
+    let roots = if ctx.config.types.contains_key(root_type.name()) {
+        vec![(root_name, root_type)]
+    } else {
+        definitions
+            .keys()
+            .filter_map(|name| {
+                let type_name = ctx.named_types.get(name)?;
+                ctx.config
+                    .types
+                    .contains_key(type_name)
+                    .then(|| (name.clone(), Type::from(type_name.clone())))
+            })
+            .collect()
+    };
+
+    let mut query_type = config::Type::default();
+    for (name, type_of) in roots {
+        let field_name = GraphQLType::new(&name).into_field().to_string();
+
+        query_type.fields.insert(
+            field_name,
+            Field { type_of: type_of.into_nullable(), ..Default::default() },
+        );
+    }
+
+    let mut config = ctx.config;
+    config.types.insert(query.to_string(), query_type);
+    config.schema.query = Some(query.to_string());
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use anyhow::Result;
+    use tailcall_fixtures::json_schema;
+
+    use super::from_json_schema;
+    use crate::core::config::ConfigModule;
+
+    #[test]
+    fn test_from_json_schema() -> Result<()> {
+        let schema = fs::read_to_string(json_schema::USER)?;
+        let config = from_json_schema(&schema, "Query")?;
+
+        insta::assert_snapshot!(ConfigModule::from(config).to_sdl());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_reference() {
+        let schema = r##"{
+            "type": "object",
+            "properties": {"user": {"$ref": "#/$defs/User"}}
+        }"##;
+        let error = from_json_schema(schema, "Query").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Unknown JSON Schema reference #/$defs/User"
+        );
+    }
+}
@@ -1,11 +1,14 @@
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use anyhow::{bail, Result};
+use convert_case::{Case, Casing};
 use derive_setters::Setters;
-use prost_reflect::prost_types::field_descriptor_proto::Label;
+use prost_reflect::prost_types::field_descriptor_proto::{Label, Type as FieldType};
 use prost_reflect::prost_types::{
-    DescriptorProto, EnumDescriptorProto, FileDescriptorSet, ServiceDescriptorProto, SourceCodeInfo,
+    DescriptorProto, EnumDescriptorProto, FieldDescriptorProto, FileDescriptorSet,
+    MethodDescriptorProto, ServiceDescriptorProto, SourceCodeInfo,
 };
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tailcall_valid::Validator;
 
@@ -14,10 +17,105 @@ use super::proto::comments_builder::CommentsBuilder;
 use super::proto::path_builder::PathBuilder;
 use super::proto::path_field::PathField;
 use crate::core::config::transformer::{AmbiguousType, TreeShake};
-use crate::core::config::{self, Arg, Config, Enum, Field, Grpc, Resolver, Union, Variant};
+use crate::core::config::{
+    self, Arg, Config, Directive, Enum, Expr, Field, Grpc, KeyValue, Resolver, Union, Variant,
+    ONE_OF_DIRECTIVE,
+};
 use crate::core::transform::{Transform, TransformerOps};
 use crate::core::Type;
 
+/// Reason attached to the fields marked as `[deprecated = true]` in proto.
+const PROTO_DEPRECATION_REASON: &str = "Marked as deprecated in the proto definition";
+
+/// Options that control how the config is generated from proto files.
+#[derive(Clone, Debug, Default, PartialEq, Setters)]
+pub struct FromProtoOptions {
+    /// Skips the fields marked as `[deprecated = true]` instead of marking
+    /// them as deprecated in the generated config.
+    skip_deprecated: bool,
+
+    /// Defines how the methods of the services are exposed on the query type.
+    service_grouping: ServiceGrouping,
+
+    /// Generates a single input type with all the oneof fields optional for
+    /// messages used as inputs instead of expanding the union of all oneof
+    /// variants.
+    flatten_input_oneofs: bool,
+
+    /// Defines how the messages with oneofs are represented in the generated
+    /// config.
+    oneof_strategy: OneofStrategy,
+
+    /// Methods with the names starting with the prefix, e.g. `Batch`, are
+    /// generated with the `batchKey` when their request has a single repeated
+    /// field of key messages and their response a single repeated field of
+    /// items. The key message must have a single field that is also a field of
+    /// the item, i.e. `BatchGetUsers({ requests: [{ id }] })` returning
+    /// `({ users: [{ id, name }] })` resolves a single `User` by the `id`,
+    /// grouped by `["users", "id"]`.
+    #[setters(strip_option, into)]
+    batch_method_prefix: Option<String>,
+
+    /// Metadata headers attached to every generated `@grpc` resolver, e.g.
+    /// the `authorization` header required by the upstream.
+    headers: Vec<KeyValue>,
+
+    /// Decides the nullability of the proto3 fields by their presence: the
+    /// singular scalar and enum fields without `optional` always have a value
+    /// and are generated as non-null, while `optional`, message and oneof
+    /// fields stay nullable.
+    field_presence: bool,
+}
+
+/// Method batched with the `batchKey`, see
+/// [`FromProtoOptions::batch_method_prefix`].
+struct BatchMethod {
+    /// Type of the key messages collected in the request, e.g.
+    /// `.batch.GetUserRequest`.
+    key_type: String,
+
+    /// Type of the items of the response, e.g. `.batch.User`.
+    item_type: String,
+
+    batch_key: Vec<String>,
+}
+
+/// Strategy to represent the messages with oneof definitions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OneofStrategy {
+    /// Every combination of the oneof variants gets its own type, i.e. the
+    /// union contains the cartesian product of all oneof definitions.
+    #[default]
+    Combinatorial,
+
+    /// Every oneof field gets a single type where this field is required, so
+    /// the union member is discriminated by the field that is present. The
+    /// fields of the other oneof definitions stay optional.
+    Presence,
+}
+
+/// Strategy to expose the methods of the proto services in the generated
+/// config.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ServiceGrouping {
+    /// Every method is added to the query type with a name fully qualified by
+    /// the package and the service, i.e. `GEN__greeter__Greeter__SayHello`.
+    #[default]
+    Flat,
+
+    /// Every method is added to the query type with a name prefixed by the
+    /// service, i.e. `greeter_sayHello`. Services of the same name in
+    /// different packages are rejected.
+    Prefixed,
+
+    /// Every service gets its own type containing its methods, exposed through
+    /// a field of the query type, i.e. `greeter { sayHello }`. Services of the
+    /// same name in different packages are rejected.
+    PerService,
+}
+
 /// Assists in the mapping and retrieval of proto type names to custom formatted
 /// strings based on the descriptor type.
 #[derive(Setters)]
@@ -37,6 +135,20 @@ struct Context {
     /// Optional field to store source code information, including comments, for
     /// each entity.
     comments_builder: CommentsBuilder,
+
+    /// Options provided by the user to tweak the generation.
+    options: FromProtoOptions,
+
+    /// Flat input types for the messages with oneofs, populated only if
+    /// `flatten_input_oneofs` is enabled.
+    flat_oneofs: BTreeMap<String, config::Type>,
+
+    /// Descriptors of all the messages mapped by their fully qualified names,
+    /// e.g. `.news.News`.
+    messages: HashMap<String, DescriptorProto>,
+
+    /// Whether the current proto file uses the proto3 syntax.
+    proto3: bool,
 }
 
 impl Context {
@@ -47,6 +159,10 @@ impl Context {
             config: Default::default(),
             map_types: Default::default(),
             comments_builder: CommentsBuilder::new(None),
+            options: Default::default(),
+            flat_oneofs: Default::default(),
+            messages: Default::default(),
+            proto3: false,
         }
     }
 
@@ -124,14 +240,58 @@ impl Context {
             }
         }
 
-        let mut union_types = Vec::new();
+        fn presence_types(
+            type_name: &str,
+            base_type: &config::Type,
+            oneof_fields: &[Vec<(String, Field)>],
+        ) -> Vec<(String, config::Type)> {
+            // oneof with a single variant is just an optional field
+            let (single, oneof_fields): (Vec<_>, Vec<_>) =
+                oneof_fields.iter().partition(|one_of| one_of.len() == 1);
+            let mut base_type = base_type.clone();
+            for (field_name, field) in single.into_iter().flatten() {
+                base_type.fields.insert(field_name.clone(), field.clone());
+            }
+
+            // type without any of the oneof fields
+            let mut output = vec![(format!("{type_name}__Var"), base_type.clone())];
 
-        collect_types(
-            type_name.clone(),
-            base_type.clone(),
-            &oneof_fields,
-            &mut union_types,
-        );
+            for (group, one_of) in oneof_fields.iter().enumerate() {
+                for (field_name, field) in one_of.iter() {
+                    let mut new_type = base_type.clone();
+
+                    // fields of the other oneofs can be present along with this one
+                    for (_, other) in oneof_fields.iter().enumerate().filter(|(i, _)| *i != group) {
+                        for (field_name, field) in other.iter() {
+                            new_type.fields.insert(field_name.clone(), field.clone());
+                        }
+                    }
+
+                    let mut field = field.clone();
+                    // mark this field as required to discriminate the type by its presence
+                    field.type_of = field.type_of.into_required();
+                    new_type.fields.insert(field_name.clone(), field);
+
+                    output.push((format!("{type_name}__Var{}", output.len() - 1), new_type));
+                }
+            }
+
+            output
+        }
+
+        let mut union_types = match self.options.oneof_strategy {
+            OneofStrategy::Combinatorial => {
+                let mut union_types = Vec::new();
+                collect_types(
+                    type_name.clone(),
+                    base_type.clone(),
+                    &oneof_fields,
+                    &mut union_types,
+                );
+                union_types
+            }
+            OneofStrategy::Presence => presence_types(&type_name, &base_type, &oneof_fields),
+        };
 
         // if there is only one type in union no need
         // to actually create union and use just this type
@@ -141,6 +301,26 @@ impl Context {
             return self;
         }
 
+        if self.options.flatten_input_oneofs {
+            let mut flat_type = base_type.clone();
+
+            // GraphQL can enforce exactly one field to be set only for the whole
+            // input type, so it's possible only if the message consists of a
+            // single oneof
+            if flat_type.fields.is_empty() && oneof_fields.len() == 1 {
+                flat_type.directives.push(Directive {
+                    name: ONE_OF_DIRECTIVE.to_string(),
+                    arguments: Default::default(),
+                });
+            }
+
+            for (field_name, field) in oneof_fields.iter().flatten() {
+                flat_type.fields.insert(field_name.clone(), field.clone());
+            }
+
+            self.flat_oneofs.insert(type_name.clone(), flat_type);
+        }
+
         let mut union_ = Union::default();
         let interface_name = format!("{type_name}__Interface");
 
@@ -177,7 +357,11 @@ impl Context {
             let mut variants_with_comments = BTreeSet::new();
 
             for (value_index, v) in enum_.value.iter().enumerate() {
-                let variant_name = GraphQLType::new(v.name()).into_enum_variant().to_string();
+                let variant_name = GraphQLType::new(v.name())
+                    .extend(self.namespace.as_slice())
+                    .push(enum_name)
+                    .into_enum_variant()
+                    .to_string();
 
                 // Path to the enum value's comments
                 let value_path = PathBuilder::new(&enum_type_path)
@@ -207,7 +391,7 @@ impl Context {
 
             let variants_with_comments = variants_with_comments
                 .into_iter()
-                .map(|v| Variant { name: v, alias: None })
+                .map(|v| Variant { name: v, alias: None, doc: None })
                 .collect();
 
             self.config
@@ -267,41 +451,15 @@ impl Context {
             };
 
             for (field_index, field) in message.field.iter().enumerate() {
+                let Some(mut cfg_field) = self.to_field(field)? else {
+                    continue;
+                };
+
                 let field_name = GraphQLType::new(field.name())
                     .extend(self.namespace.as_slice())
+                    .push(msg_name)
                     .into_field();
 
-                let mut cfg_field = Field::default();
-
-                cfg_field.type_of = match field.label() {
-                    Label::Optional => cfg_field.type_of,
-                    // required only applicable for proto2
-                    Label::Required => cfg_field.type_of.into_required(),
-                    Label::Repeated => cfg_field.type_of.into_list(),
-                };
-
-                if let Some(type_name) = &field.type_name {
-                    // check that current field is map.
-                    // it's done by checking that we've seen this type before
-                    // inside the nested type. It works only if we explore nested types
-                    // before the current type
-                    if self.map_types.contains(&type_name[1..]) {
-                        // override type with single scalar
-                        cfg_field.type_of = "JSON".to_string().into();
-                    } else {
-                        // for non-primitive types
-                        let type_of = graphql_type_from_ref(type_name)?
-                            .into_object_type()
-                            .to_string();
-
-                        cfg_field.type_of = cfg_field.type_of.with_name(type_of);
-                    }
-                } else {
-                    let type_of = convert_primitive_type(field.r#type().as_str_name());
-
-                    cfg_field.type_of = cfg_field.type_of.with_name(type_of);
-                }
-
                 let field_path =
                     PathBuilder::new(&msg_path).extend(PathField::Field, field_index as i32);
                 cfg_field.doc = self.comments_builder.get_comments(&field_path);
@@ -322,6 +480,179 @@ impl Context {
         Ok(self)
     }
 
+    /// Converts proto field to the config field with resolved type. Returns
+    /// `None` in case the field should be skipped.
+    fn to_field(&self, field: &FieldDescriptorProto) -> Result<Option<Field>> {
+        let is_deprecated = field
+            .options
+            .as_ref()
+            .and_then(|opt| opt.deprecated)
+            .unwrap_or_default();
+
+        if is_deprecated && self.options.skip_deprecated {
+            return Ok(None);
+        }
+
+        let mut cfg_field = Field::default();
+
+        cfg_field.type_of = match field.label() {
+            Label::Optional if self.has_implicit_presence(field) => {
+                cfg_field.type_of.into_required()
+            }
+            Label::Optional => cfg_field.type_of,
+            // required only applicable for proto2
+            Label::Required => cfg_field.type_of.into_required(),
+            Label::Repeated => cfg_field.type_of.into_list(),
+        };
+
+        if let Some(type_name) = &field.type_name {
+            // check that current field is map.
+            // it's done by checking that we've seen this type before
+            // inside the nested type. It works only if we explore nested types
+            // before the current type
+            if self.map_types.contains(&type_name[1..]) {
+                // override type with single scalar
+                cfg_field.type_of = "JSON".to_string().into();
+            } else {
+                // for non-primitive types
+                let type_of = graphql_type_from_ref(type_name)?
+                    .into_object_type()
+                    .to_string();
+
+                cfg_field.type_of = cfg_field.type_of.with_name(type_of);
+            }
+        } else {
+            let type_of = convert_primitive_type(field.r#type().as_str_name());
+
+            cfg_field.type_of = cfg_field.type_of.with_name(type_of);
+        }
+
+        if is_deprecated {
+            cfg_field.deprecation = Some(PROTO_DEPRECATION_REASON.to_string());
+        }
+
+        Ok(Some(cfg_field))
+    }
+
+    /// Checks if the proto3 field has no presence, i.e. it always has a value
+    /// that is the default one when it isn't set.
+    fn has_implicit_presence(&self, field: &FieldDescriptorProto) -> bool {
+        self.options.field_presence
+            && self.proto3
+            && !field.proto3_optional()
+            && field.oneof_index.is_none()
+            && !matches!(field.r#type(), FieldType::Message | FieldType::Group)
+    }
+
+    /// Attaches proto2 extension fields to the types they extend.
+    fn append_extensions(mut self, extensions: &[FieldDescriptorProto]) -> Result<Self> {
+        for extension in extensions {
+            let Some(cfg_field) = self.to_field(extension)? else {
+                continue;
+            };
+
+            let field_name = GraphQLType::new(extension.name()).into_field().to_string();
+            let type_name = graphql_type_from_ref(extension.extendee())?
+                .into_object_type()
+                .to_string();
+
+            if let Some(ty) = self.config.types.get_mut(&type_name) {
+                ty.fields.insert(field_name, cfg_field);
+            } else if let Some(union_) = self.config.unions.get(&type_name) {
+                // messages with oneofs are represented as a union of variant types
+                // sharing the same interface, so the field is added to all of them
+                let type_names = union_
+                    .types
+                    .iter()
+                    .cloned()
+                    .chain(std::iter::once(format!("{type_name}__Interface")))
+                    .collect::<Vec<_>>();
+
+                for type_name in type_names {
+                    if let Some(ty) = self.config.types.get_mut(&type_name) {
+                        ty.fields.insert(field_name.clone(), cfg_field.clone());
+                    }
+                }
+            }
+            // extensions of messages that are not part of the generated config
+            // (i.e. custom options on `google.protobuf.*Options`) are ignored
+        }
+
+        Ok(self)
+    }
+
+    /// Collects the explicit `[default = ...]` values of the fields of the
+    /// input message, so the argument can be omitted by the callers. Proto3
+    /// implicit defaults are applied by the upstream and aren't repeated
+    /// here. Messages with a `required` field without a default don't get
+    /// one, since the partial object would be rejected for the argument.
+    fn default_value(&self, input_ty: &str) -> Option<Value> {
+        let message = self.messages.get(input_ty)?;
+        let has_required = message
+            .field
+            .iter()
+            .any(|field| field.label() == Label::Required && field.default_value.is_none());
+        if has_required {
+            return None;
+        }
+
+        let defaults = message
+            .field
+            .iter()
+            .filter_map(|field| {
+                let value = to_default_value(field)?;
+                let name = GraphQLType::new(field.name()).into_field().to_string();
+
+                Some((name, value))
+            })
+            .collect::<serde_json::Map<_, _>>();
+
+        if defaults.is_empty() {
+            None
+        } else {
+            Some(Value::Object(defaults))
+        }
+    }
+
+    /// Resolves the batching of the methods matching the
+    /// `batch_method_prefix`. Returns `None` if the method can't be batched.
+    fn batch_method(&self, method: &MethodDescriptorProto) -> Option<BatchMethod> {
+        let prefix = self.options.batch_method_prefix.as_ref()?;
+
+        if !method.name().starts_with(prefix.as_str()) || method.server_streaming() {
+            return None;
+        }
+
+        let repeated_message =
+            |type_name: &str| match self.messages.get(type_name)?.field.as_slice() {
+                [field]
+                    if field.label() == Label::Repeated && field.r#type() == FieldType::Message =>
+                {
+                    Some(field.clone())
+                }
+                _ => None,
+            };
+
+        let keys = repeated_message(method.input_type())?;
+        let items = repeated_message(method.output_type())?;
+
+        let key = match self.messages.get(keys.type_name())?.field.as_slice() {
+            [key] => key.name(),
+            _ => return None,
+        };
+
+        self.messages
+            .get(items.type_name())?
+            .field
+            .iter()
+            .any(|field| field.name() == key)
+            .then(|| BatchMethod {
+                key_type: keys.type_name().to_string(),
+                item_type: items.type_name().to_string(),
+                batch_key: vec![items.name().to_string(), key.to_string()],
+            })
+    }
+
     /// Processes proto service definitions and their methods.
     fn append_query_service(
         mut self,
@@ -338,7 +669,15 @@ impl Context {
             let path = parent_path.extend(PathField::Service, index as i32);
 
             for (method_index, method) in service.method.iter().enumerate() {
-                let field_name = GraphQLType::new(method.name())
+                if method.client_streaming() {
+                    bail!(
+                        "Client streaming is not supported. Found in method {} of service {}",
+                        method.name(),
+                        service_name
+                    );
+                }
+
+                let method_name = GraphQLType::new(method.name())
                     .extend(self.namespace.as_slice())
                     .push(service_name)
                     .into_method();
@@ -346,7 +685,14 @@ impl Context {
                 let mut cfg_field = Field::default();
                 let mut body = None;
 
-                if let Some(graphql_type) = get_input_type(method.input_type())? {
+                // batched methods resolve a single item by a single key
+                let batch = self.batch_method(method);
+                let (input_type, output_type) = match &batch {
+                    Some(batch) => (batch.key_type.as_str(), batch.item_type.as_str()),
+                    None => (method.input_type(), method.output_type()),
+                };
+
+                if let Some(graphql_type) = get_input_type(input_type)? {
                     let key = graphql_type.clone().into_field().to_string();
                     let type_of = graphql_type.into_object_type().to_string();
                     let val = Arg {
@@ -355,24 +701,34 @@ impl Context {
                          * from proto file */
                         doc: None,
                         modify: None,
-                        default_value: None,
+                        default_value: self.default_value(input_type),
                     };
 
                     body = Some(Value::String(format!("{{{{.args.{key}}}}}")));
                     cfg_field.args.insert(key, val);
                 }
 
-                let output_ty = get_output_type(method.output_type())?
-                    .into_object_type()
-                    .to_string();
+                let output_ty = get_output_type(output_type)?.into_object_type().to_string();
+
+                // every message sent by a server streaming method is collected as an item of
+                // the list
+                if method.server_streaming() {
+                    cfg_field.type_of = cfg_field.type_of.into_list();
+                }
+
+                // the items of the batched responses are non-null
+                if batch.is_some() {
+                    cfg_field.type_of = cfg_field.type_of.into_required();
+                }
+
                 cfg_field.type_of = cfg_field.type_of.with_name(output_ty);
 
                 cfg_field.resolvers = Resolver::Grpc(Grpc {
                     url: url.to_string(),
                     body,
-                    batch_key: vec![],
-                    headers: vec![],
-                    method: field_name.id(),
+                    batch_key: batch.map(|batch| batch.batch_key).unwrap_or_default(),
+                    headers: self.options.headers.clone(),
+                    method: method_name.id(),
                     dedupe: None,
                     select: None,
                     on_response_body: None,
@@ -383,20 +739,92 @@ impl Context {
                     PathBuilder::new(&path).extend(PathField::Method, method_index as i32);
                 cfg_field.doc = self.comments_builder.get_comments(&method_path);
 
-                let ty = self
-                    .config
-                    .types
-                    .entry(self.query.clone())
-                    .or_insert_with(|| {
-                        self.config.schema.query = Some(self.query.clone());
-                        config::Type::default()
-                    });
+                let field_name = method.name().to_case(Case::Camel);
 
-                ty.fields.insert(field_name.to_string(), cfg_field);
+                match self.options.service_grouping {
+                    ServiceGrouping::Flat => {
+                        self.query_type()
+                            .fields
+                            .insert(method_name.to_string(), cfg_field);
+                    }
+                    ServiceGrouping::Prefixed => {
+                        let service_prefix = service_name.to_case(Case::Snake);
+                        let field_name = format!("{service_prefix}_{field_name}");
+                        if self.query_type().fields.contains_key(&field_name) {
+                            bail!(self.service_conflict(service_name));
+                        }
+                        self.query_type().fields.insert(field_name, cfg_field);
+                    }
+                    ServiceGrouping::PerService => {
+                        let type_name = self.insert_service_type(service_name)?;
+                        self.config
+                            .types
+                            .entry(type_name)
+                            .or_default()
+                            .fields
+                            .insert(field_name, cfg_field);
+                    }
+                }
             }
         }
         Ok(self)
     }
+
+    /// Returns the root query type, inserting it if it doesn't exist yet.
+    fn query_type(&mut self) -> &mut config::Type {
+        self.config
+            .types
+            .entry(self.query.clone())
+            .or_insert_with(|| {
+                self.config.schema.query = Some(self.query.clone());
+                config::Type::default()
+            })
+    }
+
+    /// Exposes the type holding the methods of the service through a field of
+    /// the query type and returns the name of the type.
+    fn insert_service_type(&mut self, service_name: &str) -> Result<String> {
+        let type_name = GraphQLType::new(service_name)
+            .extend(self.namespace.as_slice())
+            .into_object_type();
+        let type_name = format!("{type_name}__Service");
+        let field_name = service_name.to_case(Case::Camel);
+
+        // the field is already inserted by the previous methods of the service
+        let existing = self
+            .query_type()
+            .fields
+            .get(&field_name)
+            .map(|field| field.type_of.name().to_owned());
+        if let Some(existing) = existing {
+            if existing != type_name {
+                bail!(self.service_conflict(service_name));
+            }
+            return Ok(type_name);
+        }
+
+        // the field resolves to an empty object so the nested method fields
+        // are resolved by their own resolvers
+        let field = Field {
+            type_of: Type::from(type_name.clone()).into_required(),
+            resolvers: Resolver::Expr(Expr { body: Value::Object(Default::default()) }).into(),
+            ..Default::default()
+        };
+
+        self.query_type().fields.insert(field_name, field);
+
+        Ok(type_name)
+    }
+
+    /// Error for a service whose generated names are already taken by a
+    /// service of the same name in another package.
+    fn service_conflict(&self, service_name: &str) -> String {
+        format!(
+            "Service {} of package {} conflicts with a service of the same name in another package, use the flat service grouping instead",
+            service_name,
+            self.namespace.join(".")
+        )
+    }
 }
 
 fn graphql_type_from_ref(name: &str) -> Result<GraphQLType<Unparsed>> {
@@ -434,6 +862,73 @@ fn convert_primitive_type(proto_ty: &str) -> String {
     .to_string()
 }
 
+/// Converts the textual `[default = ...]` value of the proto2 field to the
+/// JSON value of the corresponding GraphQL type.
+fn to_default_value(field: &FieldDescriptorProto) -> Option<Value> {
+    let default_value = field.default_value.as_deref()?;
+
+    match field.r#type() {
+        FieldType::Bool => default_value.parse::<bool>().ok().map(Value::from),
+        FieldType::Int32 | FieldType::Sint32 | FieldType::Sfixed32 => {
+            default_value.parse::<i32>().ok().map(Value::from)
+        }
+        FieldType::Uint32 | FieldType::Fixed32 => {
+            default_value.parse::<u32>().ok().map(Value::from)
+        }
+        // 64-bit integers are mapped to the string-backed `Int64` and `UInt64`
+        FieldType::Int64 | FieldType::Sint64 | FieldType::Sfixed64 => default_value
+            .parse::<i64>()
+            .ok()
+            .map(|value| Value::from(value.to_string())),
+        FieldType::Uint64 | FieldType::Fixed64 => default_value
+            .parse::<u64>()
+            .ok()
+            .map(|value| Value::from(value.to_string())),
+        // `inf` and `nan` can't be represented in JSON
+        FieldType::Float | FieldType::Double => default_value
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number),
+        FieldType::String => Some(Value::from(default_value)),
+        FieldType::Enum => Some(Value::from(
+            GraphQLType::new(default_value)
+                .into_enum_variant()
+                .to_string(),
+        )),
+        // bytes are C-escaped in the descriptor
+        _ => None,
+    }
+}
+
+/// Collects descriptors of the messages and their nested messages by their
+/// fully qualified names.
+fn collect_messages(
+    prefix: &str,
+    messages: &[DescriptorProto],
+    collected: &mut HashMap<String, DescriptorProto>,
+) {
+    for message in messages {
+        let name = format!("{prefix}.{}", message.name());
+        collect_messages(&name, &message.nested_type, collected);
+        collected.insert(name, message.clone());
+    }
+}
+
+/// Collects extensions declared inside messages and their nested messages.
+fn nested_extensions(messages: &[DescriptorProto]) -> Vec<FieldDescriptorProto> {
+    messages
+        .iter()
+        .flat_map(|message| {
+            message
+                .extension
+                .iter()
+                .cloned()
+                .chain(nested_extensions(&message.nested_type))
+        })
+        .collect()
+}
+
 /// Determines the output type for a service method.
 fn get_output_type(output_ty: &str) -> Result<GraphQLType<Unparsed>> {
     // type, required
@@ -457,11 +952,25 @@ fn get_input_type(input_ty: &str) -> Result<Option<GraphQLType<Unparsed>>> {
 }
 
 /// The main entry point that builds a Config object from proto descriptor sets.
-pub fn from_proto(descriptor_sets: &[FileDescriptorSet], query: &str, url: &str) -> Result<Config> {
-    let mut ctx = Context::new(query);
+pub fn from_proto(
+    descriptor_sets: &[FileDescriptorSet],
+    query: &str,
+    url: &str,
+    options: &FromProtoOptions,
+) -> Result<Config> {
+    let mut ctx = Context::new(query).options(options.clone());
+    for file_descriptor in descriptor_sets.iter().flat_map(|set| set.file.iter()) {
+        let prefix = match file_descriptor.package() {
+            "" => String::new(),
+            package => format!(".{package}"),
+        };
+        collect_messages(&prefix, &file_descriptor.message_type, &mut ctx.messages);
+    }
+
     for descriptor_set in descriptor_sets.iter() {
         for file_descriptor in descriptor_set.file.iter() {
             ctx.namespace = vec![file_descriptor.package().to_string()];
+            ctx.proto3 = file_descriptor.syntax() == "proto3";
 
             if let Some(source_code_info) = &file_descriptor.source_code_info {
                 ctx = ctx.with_source_code_info(source_code_info.clone());
@@ -469,29 +978,92 @@ pub fn from_proto(descriptor_sets: &[FileDescriptorSet], query: &str, url: &str)
 
             let root_path = PathBuilder::new(&[]);
 
+            let extensions = file_descriptor
+                .extension
+                .iter()
+                .cloned()
+                .chain(nested_extensions(&file_descriptor.message_type))
+                .collect::<Vec<_>>();
+
             ctx = ctx
                 .append_enums(&file_descriptor.enum_type, &root_path, false)
                 .append_msg_type(&file_descriptor.message_type, &root_path, false)?
+                .append_extensions(&extensions)?
                 .append_query_service(&file_descriptor.service, &root_path, url)?;
         }
     }
 
+    let config = flatten_input_oneofs(ctx.config, ctx.flat_oneofs);
+
     let config = AmbiguousType::default()
         .pipe(TreeShake)
-        .transform(ctx.config)
+        .transform(config)
         .to_result()?;
 
     Ok(config)
 }
 
+/// Replaces the unions generated for oneofs with the flat input types in every
+/// input position. Types shared between inputs and outputs keep referring to
+/// the union since they are split only later by `AmbiguousType`.
+fn flatten_input_oneofs(mut config: Config, flat_oneofs: BTreeMap<String, config::Type>) -> Config {
+    if flat_oneofs.is_empty() {
+        return config;
+    }
+
+    let to_input = |type_of: &Type| {
+        if flat_oneofs.contains_key(type_of.name()) {
+            type_of
+                .clone()
+                .with_name(format!("{}Input", type_of.name()))
+        } else {
+            type_of.clone()
+        }
+    };
+
+    let output_types = config.output_types();
+    let input_types = config
+        .input_types()
+        .difference(&output_types)
+        .cloned()
+        .collect::<HashSet<_>>();
+
+    for (type_name, ty) in config.types.iter_mut() {
+        let is_input = input_types.contains(type_name);
+
+        for field in ty.fields.values_mut() {
+            if is_input {
+                field.type_of = to_input(&field.type_of);
+            }
+
+            for arg in field.args.values_mut() {
+                arg.type_of = to_input(&arg.type_of);
+            }
+        }
+    }
+
+    for (type_name, ty) in flat_oneofs.iter() {
+        let mut ty = ty.clone();
+        for field in ty.fields.values_mut() {
+            field.type_of = to_input(&field.type_of);
+        }
+
+        config.types.insert(format!("{type_name}Input"), ty);
+    }
+
+    config
+}
+
 #[cfg(test)]
 mod test {
     use anyhow::Result;
     use prost_reflect::prost_types::FileDescriptorSet;
     use tailcall_fixtures::protobuf;
 
-    use super::from_proto;
-    use crate::core::config::ConfigModule;
+    use super::{from_proto, FromProtoOptions, OneofStrategy, ServiceGrouping};
+    use crate::core::blueprint::Blueprint;
+    use crate::core::config::{Config, ConfigModule, Extensions, KeyValue, Resolver};
+    use crate::core::proto_reader::ProtoMetadata;
 
     fn compile_protobuf(files: &[&str]) -> Result<FileDescriptorSet> {
         Ok(protox::compile(files, [protobuf::SELF])?)
@@ -500,7 +1072,13 @@ mod test {
     macro_rules! assert_gen {
         ($( $set:expr ), +) => {
             let set = compile_protobuf(&[$( $set ),+]).unwrap();
-            let config = from_proto(&[set], "Query", "http://localhost:50051").unwrap();
+            let config = from_proto(
+                &[set],
+                "Query",
+                "http://localhost:50051",
+                &FromProtoOptions::default(),
+            )
+            .unwrap();
             let config_module = ConfigModule::from(config);
             let result = config_module.to_sdl();
             insta::assert_snapshot!(result);
@@ -546,8 +1124,10 @@ mod test {
         let set3 = compile_protobuf(&[protobuf::GREETINGS_B])?;
         let url = "http://localhost:50051";
 
-        let actual = from_proto(&[set.clone()], "Query", url)?.to_sdl();
-        let expected = from_proto(&[set1, set2, set3], "Query", url)?.to_sdl();
+        let options = FromProtoOptions::default();
+
+        let actual = from_proto(&[set.clone()], "Query", url, &options)?.to_sdl();
+        let expected = from_proto(&[set1, set2, set3], "Query", url, &options)?.to_sdl();
 
         pretty_assertions::assert_eq!(actual, expected);
         Ok(())
@@ -589,8 +1169,398 @@ mod test {
         assert_gen!(protobuf::SCALARS);
     }
 
+    #[test]
+    fn test_int64_ids() {
+        // 64-bit integers can't be represented by GraphQL `Int` without losing
+        // precision, so they must be generated as string-backed scalars.
+        assert_gen!(protobuf::INT_64_IDS);
+    }
+
     #[test]
     fn test_oneof_types() {
         assert_gen!(protobuf::ONEOF);
     }
+
+    #[test]
+    fn test_oneof_presence() -> Result<()> {
+        let set = compile_protobuf(&[protobuf::ONEOF])?;
+        let options = FromProtoOptions::default().oneof_strategy(OneofStrategy::Presence);
+        let config = from_proto(&[set], "Query", "http://localhost:50051", &options)?;
+        let result = ConfigModule::from(config).to_sdl();
+        insta::assert_snapshot!(result);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_field_presence() -> Result<()> {
+        let set = compile_protobuf(&[protobuf::PRESENCE])?;
+        let options = FromProtoOptions::default().field_presence(true);
+        let config = from_proto(&[set], "Query", "http://localhost:50051", &options)?;
+        let result = ConfigModule::from(config).to_sdl();
+        insta::assert_snapshot!(result);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_server_streaming() {
+        assert_gen!(protobuf::STREAMING);
+    }
+
+    #[test]
+    fn test_client_streaming_not_supported() -> Result<()> {
+        let set = compile_protobuf(&[protobuf::CLIENT_STREAMING])?;
+        let actual = from_proto(
+            &[set],
+            "Query",
+            "http://localhost:50051",
+            &FromProtoOptions::default(),
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert_eq!(
+            actual,
+            "Client streaming is not supported. Found in method UploadPrices of service PriceService"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_deprecated_fields() -> Result<()> {
+        let set = compile_protobuf(&[protobuf::DEPRECATED])?;
+        let url = "http://localhost:50051";
+
+        let config = from_proto(&[set.clone()], "Query", url, &FromProtoOptions::default())?;
+        let user = config.find_type("GEN__deprecated__User").unwrap();
+
+        assert_eq!(
+            user.fields["nickname"].deprecation.as_deref(),
+            Some("Marked as deprecated in the proto definition")
+        );
+        assert_eq!(user.fields["name"].deprecation, None);
+
+        let options = FromProtoOptions::default().skip_deprecated(true);
+        let config = from_proto(&[set], "Query", url, &options)?;
+        let user = config.find_type("GEN__deprecated__User").unwrap();
+
+        assert!(!user.fields.contains_key("nickname"));
+        assert!(user.fields.contains_key("name"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_values() -> Result<()> {
+        let set = compile_protobuf(&[protobuf::DEFAULTS])?;
+        let config = from_proto(
+            &[set],
+            "Query",
+            "http://localhost:50051",
+            &FromProtoOptions::default(),
+        )?;
+
+        let query = config.find_type("Query").unwrap();
+        let default_value = |method: &str| {
+            query
+                .fields
+                .iter()
+                .find(|(name, _)| name.ends_with(method))
+                .and_then(|(_, field)| field.args.values().next())
+                .and_then(|arg| arg.default_value.clone())
+        };
+
+        assert_eq!(
+            default_value("GetUsers"),
+            Some(serde_json::json!({
+                "limit": 10,
+                "sort": "name",
+                "verified": true,
+                "status": "ACTIVE",
+                "after": "0",
+            }))
+        );
+        assert_eq!(default_value("GetUser"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_extensions() -> Result<()> {
+        let set = compile_protobuf(&[protobuf::EXTENSIONS])?;
+        let config = from_proto(
+            &[set.clone()],
+            "Query",
+            "http://localhost:50051",
+            &FromProtoOptions::default(),
+        )?;
+
+        let product = config.find_type("GEN__extensions__Product").unwrap();
+        let vendor = product.fields.get("vendor").unwrap();
+        assert_eq!(vendor.type_of.name(), "String");
+        assert!(vendor.type_of.is_nullable());
+
+        let rating = product.fields.get("rating").unwrap();
+        assert_eq!(rating.type_of.name(), "Int");
+
+        // the extension fields are part of the response schema
+        let mut extensions = Extensions::default();
+        extensions.add_proto(ProtoMetadata {
+            descriptor_set: set,
+            path: protobuf::EXTENSIONS.to_string(),
+        });
+        Blueprint::try_from(&ConfigModule::new(config, extensions))?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_enums() -> Result<()> {
+        let set = compile_protobuf(&[protobuf::NESTED_ENUMS])?;
+        let config = from_proto(
+            &[set],
+            "Query",
+            "http://localhost:50051",
+            &FromProtoOptions::default(),
+        )?;
+
+        // every nested enum is qualified by the full chain of its parent messages
+        let expected = [
+            ("GEN__nested_enums__Order__Status", "SHIPPED"),
+            ("GEN__nested_enums__Payment__Status", "PAID"),
+            ("GEN__nested_enums__Payment__Refund__Status", "REFUNDED"),
+        ];
+
+        for (enum_name, variant) in expected {
+            let enum_ = config.find_enum(enum_name).unwrap();
+            assert!(enum_.variants.iter().any(|v| v.name == variant));
+        }
+
+        let order = config.find_type("GEN__nested_enums__Order").unwrap();
+        assert_eq!(
+            order.fields["status"].type_of.name(),
+            "GEN__nested_enums__Order__Status"
+        );
+
+        let payment = config.find_type("GEN__nested_enums__Payment").unwrap();
+        assert_eq!(
+            payment.fields["status"].type_of.name(),
+            "GEN__nested_enums__Payment__Status"
+        );
+
+        let refund = config
+            .find_type("GEN__nested_enums__Payment__Refund")
+            .unwrap();
+        assert_eq!(
+            refund.fields["status"].type_of.name(),
+            "GEN__nested_enums__Payment__Refund__Status"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_service_grouping_conflict() -> Result<()> {
+        let set = compile_protobuf(&[protobuf::GREETINGS_A, protobuf::GREETINGS_B])?;
+        let url = "http://localhost:50051";
+        let generate = |grouping: ServiceGrouping| {
+            let options = FromProtoOptions::default().service_grouping(grouping);
+            from_proto(&[set.clone()], "Query", url, &options)
+        };
+
+        let expected = "Service Greeter of package greetings_b.c conflicts with a service of the same name in another package, use the flat service grouping instead";
+        let actual = generate(ServiceGrouping::Prefixed).unwrap_err().to_string();
+        assert_eq!(actual, expected);
+        let actual = generate(ServiceGrouping::PerService)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(actual, expected);
+
+        let config = generate(ServiceGrouping::Flat)?;
+        let query = config.find_type("Query").unwrap();
+        assert!(query
+            .fields
+            .contains_key("GEN__greetings_a__b__Greeter__SayHello"));
+        assert!(query
+            .fields
+            .contains_key("GEN__greetings_b__c__Greeter__SayHello"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_service_grouping() -> Result<()> {
+        let set = compile_protobuf(&[protobuf::SERVICE_GROUPING])?;
+        let url = "http://localhost:50051";
+        let generate = |grouping: ServiceGrouping| {
+            let options = FromProtoOptions::default().service_grouping(grouping);
+            from_proto(&[set.clone()], "Query", url, &options)
+        };
+
+        let config = generate(ServiceGrouping::Flat)?;
+        let query = config.find_type("Query").unwrap();
+        assert!(query
+            .fields
+            .contains_key("GEN__service_grouping__UserService__Get"));
+        assert!(query
+            .fields
+            .contains_key("GEN__service_grouping__OrderService__Get"));
+
+        let config = generate(ServiceGrouping::Prefixed)?;
+        let query = config.find_type("Query").unwrap();
+        assert_eq!(
+            query.fields["user_service_get"].type_of.name(),
+            "GEN__service_grouping__User"
+        );
+        assert_eq!(
+            query.fields["order_service_get"].type_of.name(),
+            "GEN__service_grouping__Order"
+        );
+
+        let config = generate(ServiceGrouping::PerService)?;
+        let query = config.find_type("Query").unwrap();
+        let user_service = "GEN__service_grouping__UserService__Service";
+        let order_service = "GEN__service_grouping__OrderService__Service";
+        assert_eq!(query.fields["userService"].type_of.name(), user_service);
+        assert_eq!(query.fields["orderService"].type_of.name(), order_service);
+        assert_eq!(
+            config.find_type(user_service).unwrap().fields["get"]
+                .type_of
+                .name(),
+            "GEN__service_grouping__User"
+        );
+        assert_eq!(
+            config.find_type(order_service).unwrap().fields["get"]
+                .type_of
+                .name(),
+            "GEN__service_grouping__Order"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_key() -> Result<()> {
+        let set = compile_protobuf(&[protobuf::BATCH])?;
+        let url = "http://localhost:50051";
+        let batch_key = |config: &Config, method: &str| {
+            let query = config.find_type("Query").unwrap();
+            match query.fields[method].resolvers.first() {
+                Some(Resolver::Grpc(grpc)) => grpc.batch_key.clone(),
+                _ => panic!("Expected gRPC resolver for {method}"),
+            }
+        };
+
+        let config = from_proto(&[set.clone()], "Query", url, &FromProtoOptions::default())?;
+        assert!(batch_key(&config, "GEN__batch__UserService__BatchGetUsers").is_empty());
+
+        let options = FromProtoOptions::default().batch_method_prefix("Batch");
+        let config = from_proto(&[set.clone()], "Query", url, &options)?;
+        assert_eq!(
+            batch_key(&config, "GEN__batch__UserService__BatchGetUsers"),
+            vec!["users", "id"]
+        );
+        assert!(batch_key(&config, "GEN__batch__UserService__GetUser").is_empty());
+
+        // the batched method resolves a single item by a single key
+        let field =
+            &config.find_type("Query").unwrap().fields["GEN__batch__UserService__BatchGetUsers"];
+        assert_eq!(field.type_of.name(), "GEN__batch__User");
+        assert!(!field.type_of.is_nullable());
+        assert_eq!(
+            field.args["getUserRequest"].type_of.name(),
+            "GEN__batch__GetUserRequest"
+        );
+
+        // the generated batching passes the blueprint validation
+        let mut extensions = Extensions::default();
+        extensions
+            .add_proto(ProtoMetadata { descriptor_set: set, path: protobuf::BATCH.to_string() });
+        Blueprint::try_from(&ConfigModule::new(config, extensions))?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_headers() -> Result<()> {
+        let set = compile_protobuf(&[protobuf::SERVICE_GROUPING])?;
+        let header = KeyValue {
+            key: "authorization".to_string(),
+            value: "Bearer {{.env.TOKEN}}".to_string(),
+        };
+        let options = FromProtoOptions::default().headers(vec![header.clone()]);
+        let config = from_proto(&[set], "Query", "http://localhost:50051", &options)?;
+
+        let query = config.find_type("Query").unwrap();
+        assert_eq!(query.fields.len(), 2);
+        for field in query.fields.values() {
+            match field.resolvers.first() {
+                Some(Resolver::Grpc(grpc)) => assert_eq!(grpc.headers, vec![header.clone()]),
+                _ => panic!("Expected gRPC resolver"),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unused_enums_and_unions() -> Result<()> {
+        let set = compile_protobuf(&[protobuf::UNUSED_ENUM])?;
+        let config = from_proto(
+            &[set],
+            "Query",
+            "http://localhost:50051",
+            &FromProtoOptions::default(),
+        )?;
+
+        assert!(config.find_enum("GEN__unused_enum__Status").is_some());
+        assert!(config.find_enum("GEN__unused_enum__Legacy").is_none());
+        assert!(config.find_union("GEN__unused_enum__Orphan").is_none());
+        assert!(config
+            .find_type("GEN__unused_enum__Orphan__Interface")
+            .is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_flatten_input_oneofs() -> Result<()> {
+        let set = compile_protobuf(&[protobuf::ONEOF_INPUT])?;
+        let options = FromProtoOptions::default().flatten_input_oneofs(true);
+        let config = from_proto(&[set], "Query", "http://localhost:50051", &options)?;
+
+        let query = config.find_type("Query").unwrap();
+        let send = &query.fields["GEN__oneof_input__Notifier__Send"];
+        assert_eq!(
+            send.args["sendRequest"].type_of.name(),
+            "GEN__oneof_input__SendRequestInput"
+        );
+
+        // message with regular fields can't be validated as a whole
+        let send_request = config
+            .find_type("GEN__oneof_input__SendRequestInput")
+            .unwrap();
+        let fields = send_request.fields.keys().collect::<Vec<_>>();
+        assert_eq!(fields, ["email", "id", "phone"]);
+        assert!(send_request
+            .fields
+            .values()
+            .all(|field| field.type_of.is_nullable()));
+        assert!(send_request.directives.is_empty());
+
+        let payload = config.find_type("GEN__oneof_input__PayloadInput").unwrap();
+        let fields = payload.fields.keys().collect::<Vec<_>>();
+        assert_eq!(fields, ["number", "text"]);
+        assert_eq!(payload.directives.len(), 1);
+        assert_eq!(payload.directives[0].name, "oneOf");
+
+        // none of the oneof variants are generated for the inputs
+        assert!(config.unions.is_empty());
+        assert!(config
+            .find_type("GEN__oneof_input__SendRequest__Var0")
+            .is_none());
+
+        Ok(())
+    }
 }
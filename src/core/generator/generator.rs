@@ -7,7 +7,7 @@ use serde_json::Value;
 use tailcall_valid::Validator;
 use url::Url;
 
-use super::from_proto::from_proto;
+use super::from_proto::{from_proto, FromProtoOptions};
 use super::proto::connect_rpc::ConnectRPC;
 use super::{FromJsonGenerator, NameGenerator, RequestSample, PREFIX};
 use crate::core::config::{self, Config, ConfigModule, Link, LinkType};
@@ -44,6 +44,7 @@ pub enum Input {
         url: String,
         metadata: ProtoMetadata,
         connect_rpc: Option<bool>,
+        options: FromProtoOptions,
     },
     Config {
         schema: String,
@@ -90,9 +91,10 @@ impl Generator {
         metadata: &ProtoMetadata,
         operation_name: &str,
         url: &str,
+        options: &FromProtoOptions,
     ) -> anyhow::Result<Config> {
         let descriptor_set = resolve_file_descriptor_set(metadata.descriptor_set.clone())?;
-        let mut config = from_proto(&[descriptor_set], operation_name, url)?;
+        let mut config = from_proto(&[descriptor_set], operation_name, url, options)?;
         config.links.push(Link {
             id: None,
             src: metadata.path.to_owned(),
@@ -100,6 +102,8 @@ impl Generator {
             headers: None,
             meta: None,
             proto_paths: None,
+            refresh_interval: None,
+            refresh_grace_period: None,
         });
         Ok(config)
     }
@@ -136,8 +140,9 @@ impl Generator {
                     config = config
                         .merge_right(self.generate_from_json(&type_name_generator, &[req_sample])?);
                 }
-                Input::Proto { metadata, url, connect_rpc } => {
-                    let proto_config = self.generate_from_proto(metadata, &self.query, url)?;
+                Input::Proto { metadata, url, connect_rpc, options } => {
+                    let proto_config =
+                        self.generate_from_proto(metadata, &self.query, url, options)?;
                     let proto_config = if connect_rpc == &Some(true) {
                         ConnectRPC.transform(proto_config).to_result()?
                     } else {
@@ -273,6 +278,7 @@ pub mod test {
                 },
                 url: "http://localhost:50051".to_string(),
                 connect_rpc: None,
+                options: Default::default(),
             }])
             .generate(false)?;
 
@@ -327,6 +333,7 @@ pub mod test {
             },
             url: "http://localhost:50051".to_string(),
             connect_rpc: None,
+            options: Default::default(),
         };
 
         // Config input
@@ -25,6 +25,8 @@ pub struct GraphQLType<A>(A);
 pub struct Parsed {
     namespace: Namespace,
     name: String,
+    /// The name as it was found in the source, before normalization.
+    original: String,
     entity: Entity,
 }
 
@@ -100,7 +102,12 @@ impl GraphQLType<Unparsed> {
 
         let name = normalize_name(&unparsed.head);
 
-        GraphQLType(Parsed { name, namespace: Namespace(path), entity })
+        GraphQLType(Parsed {
+            name,
+            original: unparsed.head,
+            namespace: Namespace(path),
+            entity,
+        })
     }
 
     pub fn into_enum(self) -> GraphQLType<Parsed> {
@@ -133,6 +140,25 @@ impl GraphQLType<Parsed> {
             self.0.name.clone()
         }
     }
+
+    /// The name of the entity as it was found in the proto file.
+    pub fn original_name(&self) -> &str {
+        &self.0.original
+    }
+
+    /// Reconstructs the fully qualified proto reference of the entity. The
+    /// namespace is expected to be the package followed by the enclosing
+    /// messages, i.e. the message for a field and the enum for an enum
+    /// variant. Since proto scopes the enum values as the siblings of their
+    /// enum, the enum itself is dropped from the reference of a variant.
+    pub fn to_proto_ref(&self) -> String {
+        let mut path = self.0.namespace.0.clone();
+        if self.0.entity == Entity::EnumVariant {
+            path.pop();
+        }
+        path.push(self.0.original.clone());
+        path.join(PACKAGE_SEPARATOR)
+    }
 }
 
 /// Used to convert proto type names to GraphQL formatted names.
@@ -291,6 +317,7 @@ mod tests {
         let actual = GraphQLType::new("foo").into_enum();
         let expected = GraphQLType(Parsed {
             name: "foo".to_string(),
+            original: "foo".to_string(),
             namespace: Namespace::default(),
             entity: Entity::Enum,
         });
@@ -302,12 +329,45 @@ mod tests {
         let actual = GraphQLType::new("foo").push("bar").push("baz").into_enum();
         let expected = GraphQLType(Parsed {
             name: "foo".to_string(),
+            original: "foo".to_string(),
             namespace: Namespace::new(&["bar", "baz"]),
             entity: Entity::Enum,
         });
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_to_proto_ref_message() {
+        let actual = GraphQLType::new("NewsList")
+            .push("news.v1")
+            .into_object_type();
+
+        assert_eq!(actual.to_string(), format!("{PREFIX}news__v1__NewsList"));
+        assert_eq!(actual.original_name(), "NewsList");
+        assert_eq!(actual.to_proto_ref(), "news.v1.NewsList");
+    }
+
+    #[test]
+    fn test_to_proto_ref_nested_enum_variant() {
+        let actual = GraphQLType::new("STATUS_ACTIVE")
+            .extend(&["news", "News", "Status"])
+            .into_enum_variant();
+
+        assert_eq!(actual.to_string(), "STATUS_ACTIVE");
+        assert_eq!(actual.to_proto_ref(), "news.News.STATUS_ACTIVE");
+    }
+
+    #[test]
+    fn test_to_proto_ref_field() {
+        let actual = GraphQLType::new("post_id")
+            .extend(&["news", "Post"])
+            .into_field();
+
+        assert_eq!(actual.to_string(), "postId");
+        assert_eq!(actual.original_name(), "post_id");
+        assert_eq!(actual.to_proto_ref(), "news.Post.post_id");
+    }
+
     fn assert_type_names(input: Vec<TestParams>) {
         for ((entity, namespaces, name), expected) in input {
             let prefix = match entity {
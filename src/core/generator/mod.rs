@@ -1,11 +1,18 @@
+mod from_avro;
+mod from_graphql;
 mod from_json;
+mod from_json_schema;
 mod from_proto;
 mod generator;
 mod graphql_type;
 mod json;
 mod proto;
 
+pub use from_avro::from_avro;
+pub use from_graphql::from_graphql_sdl;
 pub use from_json::{FromJsonGenerator, RequestSample};
+pub use from_json_schema::from_json_schema;
+pub use from_proto::{FromProtoOptions, OneofStrategy, ServiceGrouping};
 pub use generator::{Generator, Input};
 
 use crate::core::counter::{Count, Counter};
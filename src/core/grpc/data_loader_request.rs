@@ -75,6 +75,8 @@ mod tests {
             headers: None,
             meta: None,
             proto_paths: None,
+            refresh_interval: None,
+            refresh_grace_period: None,
         }]);
         let method = GrpcMethod {
             package: "greetings".to_string(),
@@ -2,6 +2,7 @@ use std::fmt::Debug;
 
 use anyhow::{anyhow, bail, Context, Result};
 use async_graphql::Value;
+use convert_case::{Case, Casing};
 use prost::bytes::BufMut;
 use prost::Message;
 use prost_reflect::prost_types::FileDescriptorSet;
@@ -27,6 +28,53 @@ fn to_message(descriptor: &MessageDescriptor, input: &str) -> Result<DynamicMess
     Ok(message)
 }
 
+/// Splits the body into the messages of the Length-Prefixed Message Framing,
+/// each one being a compression flag, the length of the message as 4 bytes
+/// big-endian and the message itself.
+fn to_frames(mut bytes: &[u8]) -> Result<Vec<&[u8]>> {
+    let mut frames = vec![];
+    while !bytes.is_empty() {
+        if bytes.len() < 5 {
+            bail!("Incomplete message frame");
+        }
+        let len = u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]) as usize;
+        let end = 5 + len;
+        if bytes.len() < end {
+            bail!("Incomplete message frame");
+        }
+        frames.push(&bytes[5..end]);
+        bytes = &bytes[end..];
+    }
+
+    Ok(frames)
+}
+
+/// Renames the extension fields, serialized by prost as `[<full name>]`, to
+/// their camelCase name used by the generated GraphQL fields.
+fn rename_extensions(json: &mut serde_json::Value) {
+    match json {
+        serde_json::Value::Object(map) => {
+            let extensions = map
+                .keys()
+                .filter(|key| key.starts_with('[') && key.ends_with(']'))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            for key in extensions {
+                if let Some(value) = map.remove(&key) {
+                    let full_name = &key[1..key.len() - 1];
+                    let name = full_name.rsplit('.').next().unwrap_or(full_name);
+                    map.insert(name.to_case(Case::Camel), value);
+                }
+            }
+
+            map.values_mut().for_each(rename_extensions);
+        }
+        serde_json::Value::Array(arr) => arr.iter_mut().for_each(rename_extensions),
+        _ => {}
+    }
+}
+
 fn message_to_bytes(message: DynamicMessage) -> Result<Vec<u8>> {
     let mut buf: Vec<u8> = Vec::with_capacity(message.encoded_len() + 5);
     // set compression flag
@@ -210,16 +258,35 @@ impl ProtobufOperation {
         message_to_bytes(message).map(|result| (result, ids))
     }
 
+    pub fn is_server_streaming(&self) -> bool {
+        self.method.is_server_streaming()
+    }
+
     pub fn convert_output<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T> {
         if bytes.len() < 5 {
             bail!("Empty response");
         }
+
+        if self.is_server_streaming() {
+            // every message sent by a server streaming method is an item of the list
+            let messages = to_frames(bytes)?
+                .into_iter()
+                .map(|frame| self.decode_output(frame))
+                .collect::<Result<Vec<_>>>()?;
+
+            return Ok(serde_json::from_value(serde_json::Value::Array(messages))?);
+        }
+
         // ignore 5 first bytes as they are part of Length-Prefixed Message Framing
         // see https://www.oreilly.com/library/view/grpc-up-and/9781492058328/ch04.html#:~:text=Length%2DPrefixed%20Message%20Framing
         // 1st byte - compression flag
         // 2-4th bytes - length of the message
+        Ok(serde_json::from_value(self.decode_output(&bytes[5..])?)?)
+    }
+
+    fn decode_output(&self, bytes: &[u8]) -> Result<serde_json::Value> {
         let message =
-            DynamicMessage::decode(self.output_type.clone(), &bytes[5..]).with_context(|| {
+            DynamicMessage::decode(self.output_type.clone(), bytes).with_context(|| {
                 format!(
                     "Failed to parse response for type {}",
                     self.output_type.full_name()
@@ -228,7 +295,8 @@ impl ProtobufOperation {
 
         let mut serializer = serde_json::Serializer::new(vec![]);
         message.serialize_with_options(&mut serializer, &self.serialize_options)?;
-        let json = serde_json::from_slice::<T>(serializer.into_inner().as_ref())?;
+        let mut json = serde_json::from_slice(serializer.into_inner().as_ref())?;
+        rename_extensions(&mut json);
         Ok(json)
     }
 
@@ -281,6 +349,8 @@ pub mod tests {
             headers: None,
             meta: None,
             proto_paths: link_config.proto_paths,
+            refresh_interval: None,
+            refresh_grace_period: None,
         }]);
 
         let method = GrpcMethod { package: id, service: "a".to_owned(), name: "b".to_owned() };
@@ -382,6 +452,65 @@ pub mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn server_streaming_proto_file() -> Result<()> {
+        let grpc_method = GrpcMethod::try_from("streaming.PriceService.StreamPrices").unwrap();
+        let file = ProtobufSet::from_proto_file(get_proto_file(protobuf::STREAMING).await?)?;
+        let service = file.find_service(&grpc_method)?;
+        let operation = service.find_operation(&grpc_method)?;
+
+        let output = b"\0\0\0\0\x0f\n\x04AAPL\x11\0\0\0\0\0\0\xf8?\0\0\0\0\x0f\n\x04MSFT\x11\0\0\0\0\0\0\x04@";
+
+        let parsed = operation.convert_output::<serde_json::Value>(output)?;
+
+        assert_eq!(
+            serde_json::to_value(parsed)?,
+            json!([
+              { "symbol": "AAPL", "value": 1.5 },
+              { "symbol": "MSFT", "value": 2.5 }
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn server_streaming_incomplete_frame() -> Result<()> {
+        let grpc_method = GrpcMethod::try_from("streaming.PriceService.StreamPrices").unwrap();
+        let file = ProtobufSet::from_proto_file(get_proto_file(protobuf::STREAMING).await?)?;
+        let service = file.find_service(&grpc_method)?;
+        let operation = service.find_operation(&grpc_method)?;
+
+        let output = b"\0\0\0\0\x0f\n\x04AAPL";
+
+        let error = operation
+            .convert_output::<serde_json::Value>(output)
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "Incomplete message frame");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn extensions_proto_file() -> Result<()> {
+        let grpc_method = GrpcMethod::try_from("extensions.ProductService.GetProduct").unwrap();
+        let file = ProtobufSet::from_proto_file(get_proto_file(protobuf::EXTENSIONS).await?)?;
+        let service = file.find_service(&grpc_method)?;
+        let operation = service.find_operation(&grpc_method)?;
+
+        let output = b"\0\0\0\0\x0c\x08\x01\xa2\x06\x04acme\xa8\x06\x05";
+
+        let parsed = operation.convert_output::<serde_json::Value>(output)?;
+
+        assert_eq!(parsed["id"], json!(1));
+        assert_eq!(parsed["vendor"], json!("acme"));
+        assert_eq!(parsed["rating"], json!(5));
+        assert!(parsed.get("[extensions.vendor]").is_none());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn news_proto_file() -> Result<()> {
         let grpc_method = GrpcMethod::try_from("news.NewsService.GetNews").unwrap();
@@ -1,6 +1,6 @@
 use std::hash::{Hash, Hasher};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use derive_setters::Setters;
 use http::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
 use tailcall_hasher::TailcallHasher;
@@ -12,6 +12,7 @@ use crate::core::grpc::protobuf::ProtobufOperation;
 use crate::core::has_headers::HasHeaders;
 use crate::core::helpers::headers::MustacheHeaders;
 use crate::core::ir::model::{CacheKey, IoId};
+use crate::core::json::JsonLike;
 use crate::core::mustache::Mustache;
 use crate::core::path::PathString;
 
@@ -24,6 +25,16 @@ pub struct RequestTemplate {
     pub body: Option<RequestBody>,
     pub operation: ProtobufOperation,
     pub operation_type: GraphQLOperationType,
+    pub page_token: Option<PageToken>,
+}
+
+/// Cursor-based pagination of the responses. The token of the next page is
+/// read from the response at `next_token` and sent in the `token` field of
+/// the request body to fetch that page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageToken {
+    pub token: String,
+    pub next_token: Vec<String>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Setters)]
@@ -105,6 +116,42 @@ impl RequestTemplate {
 
         req_headers
     }
+
+    /// Builds the request of the page following the `response` to the
+    /// `rendered` request. Returns `None` when the pagination isn't configured
+    /// or the response doesn't have the token of the next page, i.e. it's the
+    /// last one.
+    pub fn next_page<'json, Json: JsonLike<'json>>(
+        &self,
+        rendered: &RenderedRequestTemplate,
+        response: &'json Json,
+    ) -> Result<Option<RenderedRequestTemplate>> {
+        let Some(page_token) = &self.page_token else {
+            return Ok(None);
+        };
+
+        let token = match response
+            .get_path(&page_token.next_token)
+            .and_then(JsonLike::as_str)
+        {
+            Some(token) if !token.is_empty() => token,
+            _ => return Ok(None),
+        };
+
+        let mut body: serde_json::Value = serde_json::from_str(&rendered.body)?;
+        let Some(body_map) = body.as_object_mut() else {
+            bail!("Page token can only be set on the object request body");
+        };
+        body_map.insert(
+            page_token.token.clone(),
+            serde_json::Value::String(token.to_string()),
+        );
+
+        Ok(Some(RenderedRequestTemplate {
+            body: body.to_string(),
+            ..rendered.clone()
+        }))
+    }
 }
 
 impl RenderedRequestTemplate {
@@ -137,7 +184,7 @@ mod tests {
     use pretty_assertions::assert_eq;
     use tailcall_fixtures::protobuf;
 
-    use super::{RequestBody, RequestTemplate};
+    use super::{PageToken, RequestBody, RequestTemplate};
     use crate::core::blueprint::GrpcMethod;
     use crate::core::config::reader::ConfigReader;
     use crate::core::config::{
@@ -161,6 +208,8 @@ mod tests {
             headers: None,
             meta: None,
             proto_paths: None,
+            refresh_interval: None,
+            refresh_grace_period: None,
         }]);
         let method = GrpcMethod {
             package: id.to_string(),
@@ -227,6 +276,7 @@ mod tests {
             operation: get_protobuf_op().await,
             body: None,
             operation_type: GraphQLOperationType::Query,
+            page_token: None,
         };
         let ctx = Context::default();
         let rendered = tmpl.render(&ctx).unwrap();
@@ -264,6 +314,7 @@ mod tests {
                 value: Default::default(),
             }),
             operation_type: GraphQLOperationType::Query,
+            page_token: None,
         };
         let ctx = Context::default();
         let rendered = tmpl.render(&ctx).unwrap();
@@ -284,9 +335,32 @@ mod tests {
                 value: Default::default(),
             }),
             operation_type: GraphQLOperationType::Query,
+            page_token: None,
         }
     }
 
+    #[tokio::test]
+    async fn test_next_page() {
+        let tmpl = request_template_with_body(r#"{ "name": "test" }"#)
+            .await
+            .page_token(Some(PageToken {
+                token: "name".to_string(),
+                next_token: vec!["nextPageToken".to_string()],
+            }));
+        let ctx = Context::default();
+        let rendered = tmpl.render(&ctx).unwrap();
+
+        let response = serde_json::json!({ "message": "Hello", "nextPageToken": "page-2" });
+        let next = tmpl.next_page(&rendered, &response).unwrap().unwrap();
+
+        assert_eq!(next.url, rendered.url);
+        assert_eq!(next.body, r#"{"name":"page-2"}"#);
+
+        // the last page doesn't have the token of the next one
+        let response = serde_json::json!({ "message": "Hello" });
+        assert_eq!(tmpl.next_page(&rendered, &response).unwrap(), None);
+    }
+
     #[tokio::test]
     async fn test_grpc_cache_key_collision() {
         let arr = [
@@ -0,0 +1,75 @@
+use std::io::{Read, Write};
+
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
+
+/// Compresses the bytes as per the value of the `Content-Encoding` header.
+/// Returns `None` for the `identity` encoding, when the bytes are sent as is.
+pub fn compress(encoding: &str, bytes: &[u8]) -> anyhow::Result<Option<Vec<u8>>> {
+    let compressed = match encoding.trim().to_ascii_lowercase().as_str() {
+        "" | "identity" => return Ok(None),
+        "gzip" => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(bytes)?;
+            encoder.finish()?
+        }
+        "deflate" => {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(bytes)?;
+            encoder.finish()?
+        }
+        "br" => {
+            let mut compressed = Vec::new();
+            {
+                let mut encoder = brotli::CompressorWriter::new(&mut compressed, 4096, 11, 22);
+                encoder.write_all(bytes)?;
+            }
+            compressed
+        }
+        encoding => anyhow::bail!("Unsupported content encoding: {}", encoding),
+    };
+
+    Ok(Some(compressed))
+}
+
+/// Decompresses the bytes as per the value of the `Content-Encoding` header.
+/// Returns `None` for the `identity` encoding and for the bytes that don't
+/// start with the header of the encoding, i.e. were already decoded. Fails
+/// when the decompressed bytes exceed `max_size`.
+pub fn decompress(
+    encoding: &str,
+    bytes: &[u8],
+    max_size: usize,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    let decoder: Box<dyn Read + '_> = match encoding.trim().to_ascii_lowercase().as_str() {
+        "" | "identity" => return Ok(None),
+        "gzip" if !bytes.starts_with(&GZIP_MAGIC) => return Ok(None),
+        "gzip" => Box::new(GzDecoder::new(bytes)),
+        "deflate" if !is_zlib(bytes) => return Ok(None),
+        "deflate" => Box::new(ZlibDecoder::new(bytes)),
+        // brotli has no header to tell the encoded bytes apart
+        "br" => Box::new(brotli::Decompressor::new(bytes, 4096)),
+        encoding => anyhow::bail!("Unsupported content encoding: {}", encoding),
+    };
+
+    let mut decompressed = Vec::new();
+    decoder
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut decompressed)?;
+    if decompressed.len() > max_size {
+        anyhow::bail!("Decompressed body exceeds the limit of {} bytes", max_size);
+    }
+
+    Ok(Some(decompressed))
+}
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Checks the two bytes header of the zlib stream, see RFC 1950.
+fn is_zlib(bytes: &[u8]) -> bool {
+    match bytes {
+        [cmf, flg, ..] => cmf & 0x0f == 8 && (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0,
+        _ => false,
+    }
+}
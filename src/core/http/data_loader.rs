@@ -12,6 +12,7 @@ use crate::core::config::group_by::GroupBy;
 use crate::core::config::Batch;
 use crate::core::data_loader::{DataLoader, Loader};
 use crate::core::http::{DataLoaderRequest, Response};
+use crate::core::ir::JqExpr;
 use crate::core::json::JsonLike;
 use crate::core::runtime::TargetRuntime;
 use crate::core::transform::TransformerOps;
@@ -40,10 +41,17 @@ pub struct HttpDataLoader {
     pub runtime: TargetRuntime,
     pub group_by: Option<GroupBy>,
     is_list: bool,
+    /// Applied to the batched response before it's grouped by the batch key.
+    response_transform: Option<JqExpr>,
 }
 impl HttpDataLoader {
-    pub fn new(runtime: TargetRuntime, group_by: Option<GroupBy>, is_list: bool) -> Self {
-        HttpDataLoader { runtime, group_by, is_list }
+    pub fn new(
+        runtime: TargetRuntime,
+        group_by: Option<GroupBy>,
+        is_list: bool,
+        response_transform: Option<JqExpr>,
+    ) -> Self {
+        HttpDataLoader { runtime, group_by, is_list, response_transform }
     }
 
     pub fn to_data_loader(self, batch: Batch) -> DataLoader<DataLoaderRequest, HttpDataLoader> {
@@ -88,13 +96,17 @@ impl Loader<DataLoaderRequest> for HttpDataLoader {
                 };
 
                 // Dispatch request
-                let res = self
+                let mut res = self
                     .runtime
                     .http
                     .execute(base_request)
                     .await?
                     .to_json::<ConstValue>()?;
 
+                if let Some(transform) = &self.response_transform {
+                    res.body = transform.eval(std::mem::take(&mut res.body));
+                }
+
                 // Create a response HashMap
                 #[allow(clippy::mutable_key_type)]
                 let mut hashmap = HashMap::with_capacity(dl_requests.len());
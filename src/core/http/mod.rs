@@ -10,6 +10,7 @@ pub use request_template::RequestTemplate;
 pub use response::*;
 
 mod cache;
+mod compression;
 mod data_loader;
 mod data_loader_request;
 mod method;
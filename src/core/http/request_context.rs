@@ -4,16 +4,18 @@ use std::sync::{Arc, Mutex};
 
 use async_graphql_value::ConstValue;
 use cache_control::{Cachability, CacheControl};
+use dashmap::DashMap;
 use derive_setters::Setters;
 use http::header::{HeaderMap, HeaderName, HeaderValue};
 
 use crate::core::app_context::AppContext;
+use crate::core::auth::jwt::jwks_refresh::RefreshableJwks;
 use crate::core::blueprint::{Server, Upstream};
 use crate::core::data_loader::{DataLoader, DedupeResult};
 use crate::core::graphql::GraphqlDataLoader;
 use crate::core::grpc::data_loader::GrpcDataLoader;
 use crate::core::http::{DataLoaderRequest, HttpDataLoader};
-use crate::core::ir::model::IoId;
+use crate::core::ir::model::{IoId, IoResult};
 use crate::core::ir::Error;
 use crate::core::runtime::TargetRuntime;
 use crate::core::{cache, grpc};
@@ -23,6 +25,9 @@ pub struct RequestContext {
     pub server: Server,
     pub upstream: Upstream,
     pub x_response_headers: Arc<Mutex<HeaderMap>>,
+    // Headers captured from the upstream responses with `@http(captureHeaders)`,
+    // readable by the fields resolved afterwards. The last response wins.
+    pub captured_response_headers: Arc<Mutex<HeaderMap>>,
     pub cookie_headers: Option<Arc<Mutex<HeaderMap>>>,
     // A subset of all the headers received in the GraphQL Request that will be sent to the
     // upstream.
@@ -33,8 +38,11 @@ pub struct RequestContext {
     pub min_max_age: Arc<Mutex<Option<i32>>>,
     pub cache_public: Arc<Mutex<Option<bool>>>,
     pub runtime: TargetRuntime,
-    pub cache: DedupeResult<IoId, ConstValue, Error>,
-    pub dedupe_handler: Arc<DedupeResult<IoId, ConstValue, Error>>,
+    pub cache: DedupeResult<IoId, IoResult, Error>,
+    pub dedupe_handler: Arc<DedupeResult<IoId, IoResult, Error>>,
+    // JWKS that are fetched again periodically, shared across the requests and
+    // keyed by their URL.
+    pub jwks: Arc<DashMap<String, Arc<RefreshableJwks>>>,
 }
 
 impl RequestContext {
@@ -43,6 +51,7 @@ impl RequestContext {
             server: Default::default(),
             upstream: Default::default(),
             x_response_headers: Arc::new(Mutex::new(HeaderMap::new())),
+            captured_response_headers: Arc::new(Mutex::new(HeaderMap::new())),
             cookie_headers: None,
             http_data_loaders: Arc::new(vec![]),
             gql_data_loaders: Arc::new(vec![]),
@@ -53,6 +62,7 @@ impl RequestContext {
             cache: DedupeResult::new(true),
             dedupe_handler: Arc::new(DedupeResult::new(false)),
             allowed_headers: HeaderMap::new(),
+            jwks: Arc::new(DashMap::new()),
         }
     }
     fn set_min_max_age_conc(&self, min_max_age: i32) {
@@ -169,6 +179,26 @@ impl RequestContext {
         }
     }
 
+    /// Makes the captured headers of an upstream response readable by the
+    /// fields resolved afterwards, replacing the previously captured values.
+    pub fn add_captured_response_headers(&self, headers: &HeaderMap) {
+        let mut captured = self.captured_response_headers.lock().unwrap();
+        for name in headers.keys() {
+            captured.remove(name);
+        }
+        for (name, value) in headers.iter() {
+            captured.append(name, value.clone());
+        }
+    }
+
+    /// Returns the value of a header captured from the upstream responses.
+    pub fn captured_response_header(&self, name: &str) -> Option<String> {
+        let captured = self.captured_response_headers.lock().unwrap();
+        let value = captured.get(name)?;
+
+        value.to_str().ok().map(|value| value.to_owned())
+    }
+
     /// Modifies existing headers to include the experimental headers
     pub fn extend_x_headers(&self, headers: &mut HeaderMap) {
         if self.has_experimental_headers() {
@@ -191,6 +221,7 @@ impl From<&AppContext> for RequestContext {
             server: app_ctx.blueprint.server.clone(),
             upstream: app_ctx.blueprint.upstream.clone(),
             x_response_headers: Arc::new(Mutex::new(HeaderMap::new())),
+            captured_response_headers: Arc::new(Mutex::new(HeaderMap::new())),
             cookie_headers,
             allowed_headers: HeaderMap::new(),
             http_data_loaders: app_ctx.http_data_loaders.clone(),
@@ -201,6 +232,7 @@ impl From<&AppContext> for RequestContext {
             runtime: app_ctx.runtime.clone(),
             cache: DedupeResult::new(true),
             dedupe_handler: app_ctx.dedupe_handler.clone(),
+            jwks: app_ctx.jwks.clone(),
         }
     }
 }
@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use async_graphql::ServerError;
+use hyper::body::{Bytes, HttpBody};
 use hyper::header::{self, HeaderValue, CONTENT_TYPE};
 use hyper::http::request::Parts;
 use hyper::http::Method;
@@ -54,6 +55,43 @@ fn not_found() -> Result<Response<Body>> {
         .body(Body::empty())?)
 }
 
+fn payload_too_large() -> Result<Response<Body>> {
+    Ok(Response::builder()
+        .status(StatusCode::PAYLOAD_TOO_LARGE)
+        .body(Body::empty())?)
+}
+
+/// Buffers the request body, or returns `None` as soon as it's known to exceed
+/// the limit, so oversized bodies are never fully loaded into memory.
+async fn to_bytes_limited(
+    headers: &HeaderMap,
+    mut body: Body,
+    limit: Option<usize>,
+) -> Result<Option<Bytes>> {
+    let Some(limit) = limit else {
+        return Ok(Some(hyper::body::to_bytes(body).await?));
+    };
+
+    let content_length = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+    if content_length.is_some_and(|content_length| content_length > limit) {
+        return Ok(None);
+    }
+
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+        if bytes.len() + chunk.len() > limit {
+            return Ok(None);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(Some(Bytes::from(bytes)))
+}
+
 fn create_request_context(req: &Request<Body>, app_ctx: &AppContext) -> RequestContext {
     let allowed_headers =
         create_allowed_headers(req.headers(), &app_ctx.blueprint.upstream.allowed_headers);
@@ -90,7 +128,10 @@ pub async fn graphql_request<T: DeserializeOwned + GraphQLRequestLike>(
     req_counter.set_http_route("/graphql");
     let req_ctx = Arc::new(create_request_context(&req, app_ctx));
     let (req, body) = req.into_parts();
-    let bytes = hyper::body::to_bytes(body).await?;
+    let limit = app_ctx.blueprint.server.get_max_request_body_bytes();
+    let Some(bytes) = to_bytes_limited(&req.headers, body, limit).await? else {
+        return payload_too_large();
+    };
     let bytes = if req.headers.get("content-type")
         == Some(&HeaderValue::from_str("application/graphql")?)
     {
@@ -264,6 +305,12 @@ async fn handle_rest_apis(
             { HTTP_ROUTE } = http_route
         );
         return async {
+            let (parts, body) = request.into_parts();
+            let limit = app_ctx.blueprint.server.get_max_request_body_bytes();
+            let Some(bytes) = to_bytes_limited(&parts.headers, body, limit).await? else {
+                return payload_too_large();
+            };
+            let request = Request::from_parts(parts, Body::from(bytes));
             let graphql_request = p_request.into_request(request).await?;
             let mut response = graphql_request
                 .data(req_ctx.clone())
@@ -386,6 +433,61 @@ mod test {
     use crate::core::rest::EndpointSet;
     use crate::core::runtime::test::init;
 
+    #[tokio::test]
+    async fn test_max_request_body_bytes() -> anyhow::Result<()> {
+        let sdl = tokio::fs::read_to_string(tailcall_fixtures::configs::JSONPLACEHOLDER).await?;
+        let config = Config::from_sdl(&sdl).to_result()?;
+        let mut blueprint = Blueprint::try_from(&ConfigModule::from(config))?;
+        blueprint.server.max_request_body_bytes = Some(16);
+        let app_ctx = Arc::new(AppContext::new(
+            blueprint,
+            init(None),
+            EndpointSet::default(),
+        ));
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("http://localhost:8000/graphql".to_string())
+            .body(Body::from(r#"{"query": "{ users { id name } }"}"#))?;
+
+        let resp = handle_request::<GraphQLRequest>(req, app_ctx).await?;
+
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_max_request_body_bytes_rest() -> anyhow::Result<()> {
+        let config = Config::from_sdl(
+            r#"
+            schema { query: Query }
+
+            type Query {
+                echo(input: JSON): JSON @expr(body: "{{.args.input}}")
+            }
+            "#,
+        )
+        .to_result()?;
+        let mut blueprint = Blueprint::try_from(&ConfigModule::from(config))?;
+        blueprint.server.max_request_body_bytes = Some(16);
+        let endpoints = EndpointSet::try_new(
+            r#"query ($input: JSON) @rest(method: POST, path: "/echo", body: "$input") { echo(input: $input) }"#,
+        )?
+        .into_checked(&blueprint, init(None))
+        .await?;
+        let app_ctx = Arc::new(AppContext::new(blueprint, init(None), endpoints));
+
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("http://localhost:8000/api/echo".to_string())
+            .body(Body::from(r#"{"name": "Leanne Graham"}"#))?;
+
+        let resp = handle_request::<GraphQLRequest>(req, app_ctx).await?;
+
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_health_endpoint() -> anyhow::Result<()> {
         let sdl = tokio::fs::read_to_string(tailcall_fixtures::configs::JSONPLACEHOLDER).await?;
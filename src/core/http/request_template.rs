@@ -6,6 +6,7 @@ use http::header::{HeaderMap, HeaderValue};
 use tailcall_hasher::TailcallHasher;
 use url::Url;
 
+use super::compression;
 use super::query_encoder::QueryEncoder;
 use crate::core::config::Encoding;
 use crate::core::endpoint::Endpoint;
@@ -152,9 +153,29 @@ impl RequestTemplate {
         } else {
             None
         };
+        Self::compress_body(&mut req)?;
         Ok(DynamicRequest::new(req).with_batching_value(batching_value))
     }
 
+    /// Compresses the body as per the `Content-Encoding` header of the request
+    fn compress_body(req: &mut reqwest::Request) -> anyhow::Result<()> {
+        let Some(encoding) = req.headers().get(reqwest::header::CONTENT_ENCODING) else {
+            return Ok(());
+        };
+        let encoding = encoding.to_str()?.to_owned();
+
+        let compressed = match req.body().and_then(|body| body.as_bytes()) {
+            Some(bytes) => compression::compress(&encoding, bytes)?,
+            None => None,
+        };
+
+        if let Some(compressed) = compressed {
+            req.body_mut().replace(compressed.into());
+        }
+
+        Ok(())
+    }
+
     /// Sets the headers for the request
     fn set_headers<C: PathString + HasHeaders>(
         &self,
@@ -693,6 +714,33 @@ mod tests {
         assert_eq!(body, "baz");
     }
 
+    #[test]
+    fn test_body_gzip_compression() {
+        use std::io::Read;
+
+        let tmpl = RequestTemplate::new("http://localhost:3000")
+            .unwrap()
+            .method(reqwest::Method::POST)
+            .headers(vec![(
+                HeaderName::from_static("content-encoding"),
+                Mustache::parse("gzip"),
+            )])
+            .body_path(Some(Mustache::parse("{{foo.bar}}")));
+        let ctx = Context::default().value(json!({
+          "foo": {
+            "bar": "baz"
+          }
+        }));
+        let req = tmpl.to_request(&ctx).unwrap().into_request();
+        let body = req.body().and_then(|a| a.as_bytes()).unwrap();
+
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(body)
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, "baz");
+    }
+
     #[test]
     fn test_body_encoding_application_json() {
         let tmpl = RequestTemplate::new("http://localhost:3000")
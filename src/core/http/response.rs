@@ -8,6 +8,7 @@ use prost::Message;
 use tonic::Status;
 use tonic_types::Status as GrpcStatus;
 
+use super::compression;
 use crate::core::grpc::protobuf::ProtobufOperation;
 use crate::core::ir::Error;
 
@@ -63,6 +64,25 @@ impl Response<Bytes> {
         Ok(Response { status, headers, body })
     }
 
+    /// Decompresses the body as per the `Content-Encoding` header, so that the
+    /// body of the response can be parsed as is. Fails for unsupported
+    /// encodings instead of leaving the body compressed, and for bodies that
+    /// decompress to more than `max_size` bytes. Only needed for the clients
+    /// that don't decode the body themselves, e.g. `fetch` already does.
+    pub fn decompress(mut self, max_size: usize) -> Result<Self> {
+        let Some(encoding) = self.headers.get(reqwest::header::CONTENT_ENCODING) else {
+            return Ok(self);
+        };
+
+        if let Some(body) = compression::decompress(encoding.to_str()?, &self.body, max_size)? {
+            self.body = body.into();
+            self.headers.remove(reqwest::header::CONTENT_ENCODING);
+            self.headers.remove(reqwest::header::CONTENT_LENGTH);
+        }
+
+        Ok(self)
+    }
+
     pub fn empty() -> Self {
         Response {
             status: reqwest::StatusCode::OK,
@@ -164,3 +184,68 @@ impl From<Response<Bytes>> for http::Response<Body> {
         response
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use hyper::body::Bytes;
+
+    use super::Response;
+
+    fn response(encoding: &str, body: Vec<u8>) -> Response<Bytes> {
+        let mut headers = headers::HeaderMap::new();
+        headers.insert("content-encoding", encoding.parse().unwrap());
+
+        Response::empty().headers(headers).body(body.into())
+    }
+
+    fn gzip(body: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_gzip_response() {
+        let actual = response("gzip", gzip(br#"{"id":1}"#))
+            .decompress(1024)
+            .unwrap();
+
+        assert_eq!(actual.body, Bytes::from_static(br#"{"id":1}"#));
+        assert!(!actual.headers.contains_key("content-encoding"));
+    }
+
+    #[test]
+    fn test_decoded_response() {
+        // e.g. `fetch` decodes the body but keeps the header
+        let actual = response("gzip", br#"{"id":1}"#.to_vec())
+            .decompress(1024)
+            .unwrap();
+
+        assert_eq!(actual.body, Bytes::from_static(br#"{"id":1}"#));
+    }
+
+    #[test]
+    fn test_decompressed_size_limit() {
+        let error = response("gzip", gzip(&[b'a'; 2048]))
+            .decompress(1024)
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Decompressed body exceeds the limit of 1024 bytes"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_encoding() {
+        let error = response("zstd", b"...".to_vec())
+            .decompress(1024)
+            .unwrap_err();
+
+        assert_eq!(error.to_string(), "Unsupported content encoding: zstd");
+    }
+}
@@ -1,10 +1,14 @@
 use std::collections::HashMap;
 use std::future::Future;
 use std::ops::Deref;
+use std::sync::Arc;
 
-use async_graphql_value::ConstValue;
+use async_graphql_value::{ConstValue, Name};
+use futures_timer::Delay;
 use futures_util::future::join_all;
+use http::header::{HeaderMap, HeaderName, HeaderValue};
 use indexmap::IndexMap;
+use tracing::Instrument;
 
 use super::eval_io::eval_io;
 use super::model::{Cache, CacheKey, Map, IR};
@@ -44,25 +48,75 @@ impl IR {
                     expr.eval(ctx).await
                 }
                 IR::IO(io) => eval_io(io, ctx).await,
-                IR::Cache(Cache { max_age, io }) => {
+                IR::Cache(cache) => {
+                    let Cache { max_age, io, .. } = cache;
                     let io = io.deref();
-                    let key = io.cache_key(ctx);
+                    let key = cache.cache_key(ctx);
                     if let Some(key) = key {
-                        if let Some(val) = ctx.request_ctx.runtime.cache.get(&key).await? {
-                            Ok(val)
-                        } else {
-                            let val = eval_io(io, ctx).await?;
-                            ctx.request_ctx
-                                .runtime
-                                .cache
-                                .set(key, val.clone(), max_age.to_owned())
-                                .await?;
-                            Ok(val)
+                        // failures of the cache are not fatal, the IO is evaluated as if
+                        // the value wasn't cached
+                        match ctx.request_ctx.runtime.cache.get(&key).await {
+                            Ok(Some(cached)) => {
+                                if let Some((val, headers)) = from_cached(cached) {
+                                    ctx.set_response_headers(headers);
+                                    return Ok(val);
+                                }
+                            }
+                            Ok(None) => {}
+                            Err(e) => tracing::warn!("Failed to read from the cache: {}", e),
                         }
+
+                        let val = eval_io(io, ctx).await?;
+                        let cached = to_cached(val.clone(), ctx.response_headers().as_deref());
+                        if let Err(e) = ctx
+                            .request_ctx
+                            .runtime
+                            .cache
+                            .set(key, cached, max_age.to_owned())
+                            .await
+                        {
+                            tracing::warn!("Failed to write to the cache: {}", e);
+                        }
+                        Ok(val)
                     } else {
                         eval_io(io, ctx).await
                     }
                 }
+                IR::Retry(retry) => {
+                    let mut attempt = 1;
+                    loop {
+                        match retry.ir.eval(ctx).await {
+                            // only failures of the upstream calls are retried
+                            Err(Error::IO(_)) if attempt < retry.max_attempts => {
+                                Delay::new(retry.delay(attempt)).await;
+                                attempt += 1;
+                            }
+                            result => break result,
+                        }
+                    }
+                }
+                IR::Cond { predicate, then, otherwise } => {
+                    // only the taken branch is evaluated, so the IO of the other
+                    // branch is never executed
+                    let predicate = predicate.eval(&mut ctx.clone()).await?;
+                    match predicate {
+                        ConstValue::Null | ConstValue::Boolean(false) => otherwise.eval(ctx).await,
+                        _ => then.eval(ctx).await,
+                    }
+                }
+                IR::Debug { label, ir } => {
+                    let span = tracing::debug_span!("debug", otel.name = %label);
+
+                    async move {
+                        tracing::debug!(input = ?ctx.path_arg::<&str>(&[]), "{label}");
+                        let value = ir.eval(ctx).await;
+                        tracing::debug!(output = ?value, "{label}");
+
+                        value
+                    }
+                    .instrument(span)
+                    .await
+                }
                 IR::Map(Map { input, map }) => {
                     fn recursive_map_enum(
                         val: Result<ConstValue, Error>,
@@ -92,10 +146,25 @@ impl IR {
                     recursive_map_enum(input.eval(ctx).await, map)
                 }
                 IR::Pipe(first, second) => {
-                    let args = first.eval(&mut ctx.clone()).await?;
+                    let mut first_ctx = ctx.clone();
+                    let args = first.eval(&mut first_ctx).await?;
                     let ctx = &mut ctx.with_args(args);
+                    // the headers captured by the first step are readable by the second
+                    ctx.set_response_headers(first_ctx.response_headers());
                     second.eval(ctx).await
                 }
+                IR::Parallel(vec) => {
+                    let results = join_all(vec.iter().map(|ir| {
+                        let mut ctx = ctx.clone();
+
+                        async move { ir.eval(&mut ctx).await }
+                    }))
+                    .await
+                    .into_iter()
+                    .collect::<Result<_, _>>()?;
+
+                    Ok(ConstValue::List(results))
+                }
                 IR::Merge(vec) => {
                     let results: Vec<_> = join_all(vec.iter().map(|ir| {
                         let mut ctx = ctx.clone();
@@ -135,9 +204,23 @@ impl IR {
                             "expected __typename to be the part of the representation".to_string(),
                         ))?;
 
-                        let ir = map.get(type_name).ok_or(Error::Entity(format!(
-                            "Cannot find a resolver for type: `{type_name}`"
-                        )))?;
+                        // prefer the resolver with the most specific key among the
+                        // ones whose fields are present in the representation
+                        let ir = map
+                            .get(type_name)
+                            .into_iter()
+                            .flatten()
+                            .filter(|(key, _)| key.matches(repr))
+                            .max_by(|(a, _), (b, _)| {
+                                a.fields
+                                    .len()
+                                    .cmp(&b.fields.len())
+                                    .then(b.fields.cmp(&a.fields))
+                            })
+                            .map(|(_, ir)| ir)
+                            .ok_or(Error::Entity(format!(
+                                "Cannot find a resolver for type: `{type_name}`"
+                            )))?;
 
                         // pass the input for current representation as value in context
                         // TODO: can we drop clone?
@@ -170,6 +253,53 @@ impl IR {
     }
 }
 
+/// Stores the captured response headers along with the value of the IO, so
+/// a cache hit exposes them the same as the upstream response.
+fn to_cached(value: ConstValue, headers: Option<&HeaderMap>) -> ConstValue {
+    let headers = headers
+        .into_iter()
+        .flat_map(|headers| headers.iter())
+        .filter_map(|(name, value)| {
+            Some(ConstValue::List(vec![
+                ConstValue::String(name.to_string()),
+                ConstValue::String(value.to_str().ok()?.to_owned()),
+            ]))
+        })
+        .collect();
+
+    ConstValue::Object(IndexMap::from([
+        (Name::new("value"), value),
+        (Name::new("headers"), ConstValue::List(headers)),
+    ]))
+}
+
+/// Reverts [to_cached], entries of a different shape are treated as a miss.
+fn from_cached(cached: ConstValue) -> Option<(ConstValue, Option<Arc<HeaderMap>>)> {
+    let ConstValue::Object(mut cached) = cached else {
+        return None;
+    };
+    let Some(ConstValue::List(entries)) = cached.swap_remove("headers") else {
+        return None;
+    };
+    let value = cached.swap_remove("value")?;
+
+    let mut headers = HeaderMap::new();
+    for entry in entries {
+        let ConstValue::List(entry) = entry else {
+            return None;
+        };
+        let [ConstValue::String(name), ConstValue::String(value)] = entry.as_slice() else {
+            return None;
+        };
+        headers.append(
+            HeaderName::from_bytes(name.as_bytes()).ok()?,
+            HeaderValue::from_str(value).ok()?,
+        );
+    }
+
+    Some((value, (!headers.is_empty()).then(|| Arc::new(headers))))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,4 +367,93 @@ mod tests {
             assert_eq!(actual, expected);
         }
     }
+
+    mod cache {
+        use std::num::NonZeroU64;
+        use std::sync::{Arc, Mutex};
+
+        use super::*;
+        use crate::core::cache;
+        use crate::core::http::RequestContext;
+        use crate::core::ir::model::{IoId, IO};
+        use crate::core::ir::EmptyResolverContext;
+
+        #[derive(Default)]
+        struct MockStore {
+            ttl: Mutex<Option<NonZeroU64>>,
+            unavailable: bool,
+        }
+
+        impl MockStore {
+            fn check(&self) -> Result<(), cache::Error> {
+                if self.unavailable {
+                    Err(cache::Error::Kv("connection refused".to_string()))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl crate::core::Cache for MockStore {
+            type Key = IoId;
+            type Value = ConstValue;
+
+            async fn set<'a>(
+                &'a self,
+                _: IoId,
+                _: ConstValue,
+                ttl: NonZeroU64,
+            ) -> Result<(), cache::Error> {
+                self.check()?;
+                *self.ttl.lock().unwrap() = Some(ttl);
+                Ok(())
+            }
+
+            async fn get<'a>(&'a self, _: &'a IoId) -> Result<Option<ConstValue>, cache::Error> {
+                self.check()?;
+                Ok(None)
+            }
+
+            fn hit_rate(&self) -> Option<f64> {
+                None
+            }
+        }
+
+        #[tokio::test]
+        async fn test_max_age_is_ttl() {
+            let store = Arc::new(MockStore::default());
+            let mut runtime = crate::core::runtime::test::init(None);
+            runtime.cache = store.clone();
+
+            let max_age = NonZeroU64::new(300).unwrap();
+            let io = Box::new(IO::Js { name: "foo".to_string(), dedupe: false });
+            let ir = IR::Cache(Cache { max_age, io, key_args: vec![] });
+
+            let req_ctx = RequestContext::new(runtime);
+            let res_ctx = EmptyResolverContext {};
+            let mut eval_ctx = EvalContext::new(&req_ctx, &res_ctx);
+            ir.eval(&mut eval_ctx).await.unwrap();
+
+            assert_eq!(*store.ttl.lock().unwrap(), Some(max_age));
+        }
+
+        #[tokio::test]
+        async fn test_unavailable_cache_evaluates_io() {
+            let store = Arc::new(MockStore { unavailable: true, ..Default::default() });
+            let mut runtime = crate::core::runtime::test::init(None);
+            runtime.cache = store.clone();
+
+            let max_age = NonZeroU64::new(300).unwrap();
+            let io = Box::new(IO::Js { name: "foo".to_string(), dedupe: false });
+            let ir = IR::Cache(Cache { max_age, io, key_args: vec![] });
+
+            let req_ctx = RequestContext::new(runtime);
+            let res_ctx = EmptyResolverContext {};
+            let mut eval_ctx = EvalContext::new(&req_ctx, &res_ctx);
+
+            assert!(ir.eval(&mut eval_ctx).await.is_ok());
+            assert_eq!(*store.ttl.lock().unwrap(), None);
+        }
+    }
 }
@@ -24,6 +24,10 @@ pub struct EvalContext<'a, Ctx: ResolverContextLike> {
 
     // Overridden Arguments for Async GraphQL Context
     graphql_ctx_args: Option<Arc<Value>>,
+
+    // Captured headers of the upstream response of the last IO evaluated by
+    // the resolver
+    response_headers: Option<Arc<HeaderMap>>,
 }
 
 impl<'a, Ctx: ResolverContextLike> EvalContext<'a, Ctx> {
@@ -49,6 +53,7 @@ impl<'a, Ctx: ResolverContextLike> EvalContext<'a, Ctx> {
             graphql_ctx,
             graphql_ctx_value: None,
             graphql_ctx_args: None,
+            response_headers: None,
         }
     }
 
@@ -89,6 +94,33 @@ impl<'a, Ctx: ResolverContextLike> EvalContext<'a, Ctx> {
         value.to_str().ok()
     }
 
+    /// Returns the header of the response of the resolver's own IO, or else
+    /// the one captured by the fields resolved before, e.g. the parent.
+    pub fn response_header(&self, key: &str) -> Option<Cow<'_, str>> {
+        match self
+            .response_headers
+            .as_ref()
+            .and_then(|headers| headers.get(key))
+        {
+            Some(value) => value.to_str().ok().map(Cow::Borrowed),
+            None => self
+                .request_ctx
+                .captured_response_header(key)
+                .map(Cow::Owned),
+        }
+    }
+
+    pub fn set_response_headers(&mut self, response_headers: Option<Arc<HeaderMap>>) {
+        if let Some(headers) = &response_headers {
+            self.request_ctx.add_captured_response_headers(headers);
+        }
+        self.response_headers = response_headers;
+    }
+
+    pub(super) fn response_headers(&self) -> Option<Arc<HeaderMap>> {
+        self.response_headers.clone()
+    }
+
     pub fn env_var(&self, key: &str) -> Option<Cow<'_, str>> {
         self.request_ctx.runtime.env.get(key)
     }
@@ -1,4 +1,7 @@
+use std::sync::Arc;
+
 use async_graphql_value::ConstValue;
+use http::header::{HeaderMap, HeaderName};
 
 use super::eval_http::{
     execute_grpc_request_with_dl, execute_raw_grpc_request, execute_raw_request,
@@ -26,15 +29,23 @@ where
         return eval_io_inner(io, ctx).await;
     }
     if let Some(key) = io.cache_key(ctx) {
-        ctx.request_ctx
+        // the captured headers are shared with the deduplicated calls
+        let (value, response_headers) = ctx
+            .request_ctx
             .cache
             .dedupe(&key, || async {
                 ctx.request_ctx
                     .dedupe_handler
-                    .dedupe(&key, || eval_io_inner(io, ctx))
+                    .dedupe(&key, || async {
+                        let value = eval_io_inner(io, ctx).await?;
+                        Ok((value, ctx.response_headers()))
+                    })
                     .await
             })
-            .await
+            .await?;
+        ctx.set_response_headers(response_headers);
+
+        Ok(value)
     } else {
         eval_io_inner(io, ctx).await
     }
@@ -44,8 +55,18 @@ async fn eval_io_inner<Ctx>(io: &IO, ctx: &mut EvalContext<'_, Ctx>) -> Result<C
 where
     Ctx: ResolverContextLike + Sync,
 {
+    ctx.set_response_headers(None);
+
     match io {
-        IO::Http { req_template, dl_id, hook, .. } => {
+        IO::Http {
+            req_template,
+            group_by,
+            dl_id,
+            hook,
+            response_transform,
+            capture_headers,
+            ..
+        } => {
             let event_worker = &ctx.request_ctx.runtime.cmd_worker;
             let js_worker = &ctx.request_ctx.runtime.worker;
             let eval_http = EvalHttp::new(ctx, req_template, dl_id);
@@ -57,8 +78,15 @@ where
                 }
                 _ => eval_http.execute(request).await?,
             };
+            ctx.set_response_headers(capture_response_headers(&response.headers, capture_headers));
 
-            Ok(response.body)
+            // the batched responses are transformed by the data loader before
+            // they are grouped by the batch key
+            let is_grouped = group_by.is_some() && ctx.request_ctx.is_batching_enabled();
+            match response_transform {
+                Some(transform) if !is_grouped => Ok(transform.eval(response.body)),
+                _ => Ok(response.body),
+            }
         }
         IO::GraphQL { req_template, field_name, dl_id, .. } => {
             let req = req_template.to_request(ctx)?;
@@ -100,7 +128,7 @@ where
 
             Ok(res.body)
         }
-        IO::Js { name } => {
+        IO::Js { name, .. } => {
             if let Some((worker, value)) = ctx
                 .request_ctx
                 .runtime
@@ -116,3 +144,15 @@ where
         }
     }
 }
+
+/// Keeps only the `names` headers of the upstream response.
+fn capture_response_headers(headers: &HeaderMap, names: &[HeaderName]) -> Option<Arc<HeaderMap>> {
+    let mut captured = HeaderMap::new();
+    for name in names {
+        for value in headers.get_all(name) {
+            captured.append(name.clone(), value.clone());
+        }
+    }
+
+    (!captured.is_empty()).then(|| Arc::new(captured))
+}
@@ -0,0 +1,113 @@
+use anyhow::{bail, Result};
+use async_graphql_value::ConstValue;
+
+/// JQ-style expression that reshapes the upstream response before it's mapped
+/// to the GraphQL type. Supports the identity `.`, the field access, e.g.
+/// `.data.items`, and the iteration over the lists, e.g. `.users[].name`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JqExpr(Vec<Segment>);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Iterate,
+}
+
+impl JqExpr {
+    pub fn parse(expr: &str) -> Result<Self> {
+        let Some(path) = expr.trim().strip_prefix('.') else {
+            bail!("JQ expression must start with `.`: {expr}");
+        };
+
+        let mut segments = Vec::new();
+        if path.is_empty() {
+            return Ok(Self(segments));
+        }
+
+        for part in path.split('.') {
+            let (key, iterate) = match part.strip_suffix("[]") {
+                Some(key) => (key, true),
+                None => (part, false),
+            };
+
+            if key.contains(['[', ']']) || (key.is_empty() && !iterate) {
+                bail!("Invalid segment `{part}` in JQ expression: {expr}");
+            }
+
+            if !key.is_empty() {
+                segments.push(Segment::Key(key.to_string()));
+            }
+            if iterate {
+                segments.push(Segment::Iterate);
+            }
+        }
+
+        Ok(Self(segments))
+    }
+
+    /// Applies the expression to the value. Missing fields resolve to `null`.
+    pub fn eval(&self, value: ConstValue) -> ConstValue {
+        eval_segments(&self.0, value)
+    }
+}
+
+fn eval_segments(segments: &[Segment], value: ConstValue) -> ConstValue {
+    let Some((segment, rest)) = segments.split_first() else {
+        return value;
+    };
+
+    match (segment, value) {
+        (Segment::Key(key), ConstValue::Object(mut map)) => match map.shift_remove(key.as_str()) {
+            Some(value) => eval_segments(rest, value),
+            None => ConstValue::Null,
+        },
+        (Segment::Iterate, ConstValue::List(list)) => ConstValue::List(
+            list.into_iter()
+                .map(|value| eval_segments(rest, value))
+                .collect(),
+        ),
+        _ => ConstValue::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql_value::ConstValue;
+    use serde_json::json;
+
+    use super::JqExpr;
+
+    fn eval(expr: &str, value: serde_json::Value) -> serde_json::Value {
+        let value = ConstValue::from_json(value).unwrap();
+        JqExpr::parse(expr)
+            .unwrap()
+            .eval(value)
+            .into_json()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_nested_list() {
+        let response = json!({
+            "data": {
+                "items": [{"id": 1}, {"id": 2}],
+                "total": 2
+            }
+        });
+
+        assert_eq!(
+            eval(".data.items", response.clone()),
+            json!([{"id": 1}, {"id": 2}])
+        );
+        assert_eq!(eval(".data.items[].id", response.clone()), json!([1, 2]));
+        assert_eq!(eval(".", response.clone()), response);
+        assert_eq!(eval(".data.missing", response), json!(null));
+    }
+
+    #[test]
+    fn test_invalid_expr() {
+        assert!(JqExpr::parse("data").is_err());
+        assert!(JqExpr::parse(".data..items").is_err());
+        assert!(JqExpr::parse(".items[0]").is_err());
+    }
+}
@@ -4,6 +4,7 @@ mod eval;
 mod eval_context;
 mod eval_http;
 mod eval_io;
+mod jq_expr;
 mod request;
 mod resolver_context_like;
 
@@ -14,6 +15,7 @@ use std::ops::Deref;
 pub use discriminator::*;
 pub use error::*;
 pub use eval_context::EvalContext;
+pub use jq_expr::JqExpr;
 pub(crate) use request::DynamicRequest;
 pub use resolver_context_like::{
     EmptyResolverContext, ResolverContext, ResolverContextLike, SelectionField,
@@ -1,15 +1,22 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
 use std::num::NonZeroU64;
+use std::sync::Arc;
+use std::time::Duration;
 
+use ::http::{HeaderMap, HeaderName};
 use async_graphql::Value;
 use strum_macros::Display;
+use tailcall_hasher::TailcallHasher;
 
 use super::discriminator::Discriminator;
-use super::{EvalContext, ResolverContextLike};
+use super::{EvalContext, JqExpr, ResolverContextLike};
 use crate::core::blueprint::{Auth, DynamicValue};
 use crate::core::config::group_by::GroupBy;
 use crate::core::graphql::{self};
+use crate::core::json::JsonLike;
 use crate::core::worker_hooks::WorkerHooks;
 use crate::core::{grpc, http};
 
@@ -19,6 +26,7 @@ pub enum IR {
     #[strum(to_string = "{0}")]
     IO(IO),
     Cache(Cache),
+    Retry(Retry),
     // TODO: Path can be implement using Pipe
     Path(Box<IR>, Vec<String>),
     ContextPath(Vec<String>),
@@ -27,13 +35,72 @@ pub enum IR {
     Pipe(Box<IR>, Box<IR>),
     /// Merges the result of multiple IRs together
     Merge(Vec<IR>),
+    /// Evaluates the IRs concurrently and returns the list of their results
+    Parallel(Vec<IR>),
     Discriminate(Discriminator, Box<IR>),
-    /// Apollo Federation _entities resolver
-    Entity(HashMap<String, IR>),
+    /// Evaluates `then` if the result of `predicate` is truthy, i.e. neither
+    /// `null` nor `false`, and `otherwise` in other cases
+    Cond {
+        predicate: Box<IR>,
+        then: Box<IR>,
+        otherwise: Box<IR>,
+    },
+    /// Logs the input and the output of `ir` under the span named by `label`
+    /// and passes the output through unchanged
+    Debug {
+        label: String,
+        ir: Box<IR>,
+    },
+    /// Apollo Federation _entities resolver. The resolvers are grouped by the
+    /// entity type and then keyed by the fields of the `@key`.
+    Entity(HashMap<String, HashMap<EntityKey, IR>>),
     /// Apollo Federation _service resolver
     Service(String),
 }
 
+/// Identifies the resolver of Apollo Federation entity by the fields of the
+/// `@key` the resolver relies on.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EntityKey {
+    /// Paths to the leaf fields of the key
+    pub fields: Vec<Vec<String>>,
+}
+
+impl EntityKey {
+    /// Creates the key from the `fields` argument of the `@key` directive,
+    /// e.g. `id organization { id }`.
+    pub fn new(fields: &str) -> Self {
+        let fields = fields.replace('{', " { ").replace('}', " } ");
+        let mut paths: Vec<Vec<String>> = Vec::new();
+        let mut parent: Vec<String> = Vec::new();
+
+        for token in fields.split_whitespace() {
+            match token {
+                // the previous field has selection, so it's not a leaf
+                "{" => parent = paths.pop().unwrap_or_default(),
+                "}" => {
+                    parent.pop();
+                }
+                name => {
+                    let mut path = parent.clone();
+                    path.push(name.to_string());
+                    paths.push(path);
+                }
+            }
+        }
+
+        Self { fields: paths }
+    }
+
+    /// Checks if the entity representation contains all the fields of the
+    /// key.
+    pub fn matches<'a, Json: JsonLike<'a>>(&self, repr: &'a Json) -> bool {
+        self.fields
+            .iter()
+            .all(|path| repr.get_path(path).is_some_and(|value| !value.is_null()))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Map {
     pub input: Box<IR>,
@@ -41,6 +108,9 @@ pub struct Map {
     pub map: HashMap<String, String>,
 }
 
+/// Value of the IO along with the captured headers of the upstream response.
+pub type IoResult = (Value, Option<Arc<HeaderMap>>);
+
 #[derive(Clone, Debug, strum_macros::Display)]
 pub enum IO {
     Http {
@@ -50,6 +120,11 @@ pub enum IO {
         is_list: bool,
         dedupe: bool,
         hook: Option<WorkerHooks>,
+        response_transform: Option<JqExpr>,
+        /// Headers of the upstream response captured along with the result, so
+        /// the following steps of the same resolver, e.g. the `select`, can
+        /// read them as `{{.response.headers.<name>}}`.
+        capture_headers: Vec<HeaderName>,
     },
     GraphQL {
         req_template: graphql::RequestTemplate,
@@ -67,6 +142,7 @@ pub enum IO {
     },
     Js {
         name: String,
+        dedupe: bool,
     },
 }
 
@@ -76,7 +152,7 @@ impl IO {
             IO::Http { dedupe, .. } => *dedupe,
             IO::GraphQL { dedupe, .. } => *dedupe,
             IO::Grpc { dedupe, .. } => *dedupe,
-            IO::Js { .. } => false,
+            IO::Js { dedupe, .. } => *dedupe,
         }
     }
 }
@@ -115,6 +191,8 @@ pub trait CacheKey<Ctx> {
 pub struct Cache {
     pub max_age: NonZeroU64,
     pub io: Box<IO>,
+    /// Arguments that form the cache key, all of them if empty
+    pub key_args: Vec<String>,
 }
 
 impl Cache {
@@ -122,33 +200,106 @@ impl Cache {
     /// Wraps an expression with the cache primitive.
     /// Performance DFS on the cache on the expression and identifies all the IO
     /// nodes. Then wraps each IO node with the cache primitive.
-    pub fn wrap(max_age: NonZeroU64, expr: IR) -> IR {
+    pub fn wrap(max_age: NonZeroU64, key_args: Vec<String>, expr: IR) -> IR {
         expr.modify(&mut move |expr| match expr {
-            IR::IO(io) => Some(IR::Cache(Cache { max_age, io: Box::new(io.to_owned()) })),
+            IR::IO(io) => Some(IR::Cache(Cache {
+                max_age,
+                io: Box::new(io.to_owned()),
+                key_args: key_args.clone(),
+            })),
             _ => None,
         })
     }
 }
 
+impl<'a, Ctx: ResolverContextLike + Sync> CacheKey<EvalContext<'a, Ctx>> for Cache {
+    fn cache_key(&self, ctx: &EvalContext<'a, Ctx>) -> Option<IoId> {
+        match self.io.as_ref() {
+            IO::Http { req_template, .. } if !self.key_args.is_empty() => {
+                // the request is rendered without the arguments that are not
+                // part of the key
+                let args = match ctx.path_arg::<&str>(&[]).map(Cow::into_owned) {
+                    Some(Value::Object(args)) => Value::Object(
+                        args.into_iter()
+                            .filter(|(name, _)| {
+                                self.key_args.iter().any(|arg| arg == name.as_str())
+                            })
+                            .collect(),
+                    ),
+                    _ => Value::Null,
+                };
+
+                req_template.cache_key(&ctx.with_args(args))
+            }
+            io => io.cache_key(ctx),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Retry {
+    /// Total number of attempts including the first one
+    pub max_attempts: usize,
+    /// Delay between the attempts that doubles after every failure
+    pub backoff_ms: u64,
+    pub ir: Box<IR>,
+}
+
+impl Retry {
+    ///
+    /// Wraps an expression with the retry primitive.
+    /// Performs DFS on the expression and wraps every IO node, cached or not,
+    /// with the retry primitive. Nodes that are already retried are left as is.
+    pub fn wrap(max_attempts: usize, backoff_ms: u64, expr: IR) -> IR {
+        expr.modify(&mut move |expr| match expr {
+            IR::IO(_) | IR::Cache(_) => Some(IR::Retry(Retry {
+                max_attempts,
+                backoff_ms,
+                ir: Box::new(expr.to_owned()),
+            })),
+            IR::Retry(_) => Some(expr.to_owned()),
+            _ => None,
+        })
+    }
+
+    /// Delay before the given retry, starting from `1`.
+    pub fn delay(&self, retry: usize) -> Duration {
+        let factor = 2u64.saturating_pow(retry.saturating_sub(1) as u32);
+        Duration::from_millis(self.backoff_ms.saturating_mul(factor))
+    }
+}
+
 impl IR {
     // allows to modify the IO node in the IR tree
     pub fn modify_io(&mut self, io_modifier: &mut dyn FnMut(&mut IO)) {
         match self {
             IR::IO(io) => io_modifier(io),
             IR::Cache(cache) => io_modifier(&mut cache.io),
-            IR::Discriminate(_, ir) | IR::Protect(_, ir) | IR::Path(ir, _) => {
-                ir.modify_io(io_modifier)
-            }
+            IR::Retry(retry) => retry.ir.modify_io(io_modifier),
+            IR::Discriminate(_, ir)
+            | IR::Protect(_, ir)
+            | IR::Path(ir, _)
+            | IR::Debug { ir, .. } => ir.modify_io(io_modifier),
             IR::Pipe(ir1, ir2) => {
                 ir1.modify_io(io_modifier);
                 ir2.modify_io(io_modifier);
             }
             IR::Entity(hash_map) => {
-                for ir in hash_map.values_mut() {
+                for ir in hash_map.values_mut().flat_map(|irs| irs.values_mut()) {
                     ir.modify_io(io_modifier);
                 }
             }
             IR::Map(map) => map.input.modify_io(io_modifier),
+            IR::Parallel(vec) => {
+                for ir in vec {
+                    ir.modify_io(io_modifier);
+                }
+            }
+            IR::Cond { predicate, then, otherwise } => {
+                predicate.modify_io(io_modifier);
+                then.modify_io(io_modifier);
+                otherwise.modify_io(io_modifier);
+            }
             _ => {}
         }
     }
@@ -178,13 +329,16 @@ impl IR {
                     IR::ContextPath(path) => IR::ContextPath(path),
                     IR::Dynamic(_) => expr,
                     IR::IO(_) => expr,
-                    IR::Cache(Cache { io, max_age }) => {
+                    IR::Cache(Cache { io, max_age, key_args }) => {
                         let expr = *IR::IO(*io).modify_box(modifier);
                         match expr {
-                            IR::IO(io) => IR::Cache(Cache { io: Box::new(io), max_age }),
+                            IR::IO(io) => IR::Cache(Cache { io: Box::new(io), max_age, key_args }),
                             expr => expr,
                         }
                     }
+                    IR::Retry(Retry { max_attempts, backoff_ms, ir }) => {
+                        IR::Retry(Retry { max_attempts, backoff_ms, ir: ir.modify_box(modifier) })
+                    }
                     IR::Path(expr, path) => IR::Path(expr.modify_box(modifier), path),
                     IR::Protect(auth, expr) => IR::Protect(auth, expr.modify_box(modifier)),
                     IR::Map(Map { input, map }) => {
@@ -193,15 +347,30 @@ impl IR {
                     IR::Discriminate(discriminator, expr) => {
                         IR::Discriminate(discriminator, expr.modify_box(modifier))
                     }
+                    IR::Cond { predicate, then, otherwise } => IR::Cond {
+                        predicate: predicate.modify_box(modifier),
+                        then: then.modify_box(modifier),
+                        otherwise: otherwise.modify_box(modifier),
+                    },
+                    IR::Debug { label, ir } => IR::Debug { label, ir: ir.modify_box(modifier) },
                     IR::Entity(map) => IR::Entity(
                         map.into_iter()
-                            .map(|(k, v)| (k, v.modify(modifier)))
+                            .map(|(type_name, irs)| {
+                                let irs = irs
+                                    .into_iter()
+                                    .map(|(k, v)| (k, v.modify(modifier)))
+                                    .collect();
+                                (type_name, irs)
+                            })
                             .collect(),
                     ),
                     IR::Service(sdl) => IR::Service(sdl),
                     IR::Merge(vec) => {
                         IR::Merge(vec.into_iter().map(|ir| ir.modify(modifier)).collect())
                     }
+                    IR::Parallel(vec) => {
+                        IR::Parallel(vec.into_iter().map(|ir| ir.modify(modifier)).collect())
+                    }
                 }
             }
         }
@@ -214,7 +383,256 @@ impl<'a, Ctx: ResolverContextLike + Sync> CacheKey<EvalContext<'a, Ctx>> for IO
             IO::Http { req_template, .. } => req_template.cache_key(ctx),
             IO::Grpc { req_template, .. } => req_template.cache_key(ctx),
             IO::GraphQL { req_template, .. } => req_template.cache_key(ctx),
-            IO::Js { .. } => None,
+            IO::Js { name, .. } => {
+                let mut hasher = TailcallHasher::default();
+                let state = &mut hasher;
+
+                name.hash(state);
+                ctx.value().map(|value| value.to_string()).hash(state);
+                ctx.path_arg::<&str>(&[])
+                    .map(|args| args.to_string())
+                    .hash(state);
+
+                Some(IoId::new(hasher.finish()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use async_graphql::Value;
+    use serde_json::json;
+
+    use super::{Cache, EntityKey, Retry, IO, IR};
+
+    #[test]
+    fn test_entity_key_fields() {
+        let key = EntityKey::new("id organization { id address { city } } email");
+
+        assert_eq!(
+            key.fields,
+            vec![
+                vec!["id".to_string()],
+                vec!["organization".to_string(), "id".to_string()],
+                vec![
+                    "organization".to_string(),
+                    "address".to_string(),
+                    "city".to_string()
+                ],
+                vec!["email".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entity_key_matches() {
+        let by_id = EntityKey::new("id");
+        let by_sku = EntityKey::new("sku");
+        let repr = Value::from_json(json!({ "__typename": "Product", "sku": "a-1" })).unwrap();
+
+        assert!(!by_id.matches(&repr));
+        assert!(by_sku.matches(&repr));
+    }
+
+    /// Collects the number of retry wrappers around every IO node.
+    fn retries(ir: &IR, depth: usize, found: &mut Vec<usize>) {
+        match ir {
+            IR::IO(_) => found.push(depth),
+            IR::Retry(retry) => retries(&retry.ir, depth + 1, found),
+            IR::Path(ir, _) => retries(ir, depth, found),
+            IR::Pipe(first, second) => {
+                retries(first, depth, found);
+                retries(second, depth, found);
+            }
+            _ => {}
         }
     }
+
+    #[test]
+    fn test_retry_wrap() {
+        let js = |name: &str| IR::IO(IO::Js { name: name.to_string(), dedupe: false });
+        let ir = IR::Pipe(
+            Box::new(js("first")),
+            Box::new(IR::Path(Box::new(js("second")), vec!["data".to_string()])),
+        );
+
+        let ir = Retry::wrap(3, 100, ir);
+        let mut found = vec![];
+        retries(&ir, 0, &mut found);
+        assert_eq!(found, vec![1, 1]);
+
+        // wrapping again doesn't retry the same node twice
+        let ir = Retry::wrap(3, 100, ir);
+        let mut found = vec![];
+        retries(&ir, 0, &mut found);
+        assert_eq!(found, vec![1, 1]);
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let retry = Retry {
+            max_attempts: 3,
+            backoff_ms: 100,
+            ir: Box::new(IR::ContextPath(vec![])),
+        };
+
+        assert_eq!(retry.delay(1).as_millis(), 100);
+        assert_eq!(retry.delay(2).as_millis(), 200);
+        assert_eq!(retry.delay(3).as_millis(), 400);
+    }
+
+    #[test]
+    fn test_cond_modify() {
+        let js = |name: &str| IR::IO(IO::Js { name: name.to_string(), dedupe: false });
+        let ir = IR::Cond {
+            predicate: Box::new(js("predicate")),
+            then: Box::new(js("then")),
+            otherwise: Box::new(js("otherwise")),
+        };
+
+        let mut visited = vec![];
+        ir.modify(&mut |ir| {
+            if let IR::IO(IO::Js { name, .. }) = ir {
+                visited.push(name.clone());
+            }
+            None
+        });
+
+        assert_eq!(visited, vec!["predicate", "then", "otherwise"]);
+    }
+
+    #[test]
+    fn test_debug_modify() {
+        let js = |name: &str| IR::IO(IO::Js { name: name.to_string(), dedupe: false });
+        let ir = IR::Debug {
+            label: "users".to_string(),
+            ir: Box::new(IR::Pipe(Box::new(js("first")), Box::new(js("second")))),
+        };
+
+        let mut visited = vec![];
+        let ir = ir.modify(&mut |ir| {
+            if let IR::IO(IO::Js { name, .. }) = ir {
+                visited.push(name.clone());
+            }
+            None
+        });
+        assert_eq!(visited, vec!["first", "second"]);
+
+        // the wrapper is kept as is around the wrapped IR
+        match ir {
+            IR::Debug { label, ir } => {
+                assert_eq!(label, "users");
+                assert!(matches!(*ir, IR::Pipe(_, _)));
+            }
+            ir => panic!("Expected IR::Debug, got {ir:?}"),
+        }
+    }
+
+    #[test]
+    fn test_js_cache_key() {
+        use crate::core::blueprint::Blueprint;
+        use crate::core::http::RequestContext;
+        use crate::core::ir::model::CacheKey;
+        use crate::core::ir::{EmptyResolverContext, EvalContext};
+
+        let runtime = crate::cli::runtime::init(&Blueprint::default());
+        let req_ctx = RequestContext::new(runtime);
+        let res_ctx = EmptyResolverContext {};
+        let ctx = EvalContext::new(&req_ctx, &res_ctx);
+
+        let js = |name: &str| IO::Js { name: name.to_string(), dedupe: true };
+
+        let first = js("foo").cache_key(&ctx).unwrap();
+        let second = js("foo").cache_key(&ctx).unwrap();
+        let other = js("bar").cache_key(&ctx).unwrap();
+
+        assert!(js("foo").dedupe());
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_cache_key_args() {
+        use crate::core::blueprint::Blueprint;
+        use crate::core::http::{RequestContext, RequestTemplate};
+        use crate::core::ir::model::CacheKey;
+        use crate::core::ir::{EmptyResolverContext, EvalContext};
+
+        let runtime = crate::cli::runtime::init(&Blueprint::default());
+        let req_ctx = RequestContext::new(runtime);
+        let res_ctx = EmptyResolverContext {};
+        let ctx = EvalContext::new(&req_ctx, &res_ctx);
+
+        let req_template = RequestTemplate::new(
+            "http://localhost/users/{{.args.userId}}?requestId={{.args.requestId}}",
+        )
+        .unwrap();
+        let cache = |key_args: Vec<&str>| Cache {
+            max_age: NonZeroU64::new(100).unwrap(),
+            io: Box::new(IO::Http {
+                req_template: req_template.clone(),
+                group_by: None,
+                dl_id: None,
+                is_list: false,
+                dedupe: false,
+                hook: None,
+                response_transform: None,
+                capture_headers: vec![],
+            }),
+            key_args: key_args.into_iter().map(String::from).collect(),
+        };
+        let args = |user_id: u64, request_id: &str| {
+            ctx.with_args(
+                Value::from_json(json!({"userId": user_id, "requestId": request_id})).unwrap(),
+            )
+        };
+
+        let by_user = cache(vec!["userId"]);
+        let first = by_user.cache_key(&args(1, "a")).unwrap();
+        let second = by_user.cache_key(&args(1, "b")).unwrap();
+        let other = by_user.cache_key(&args(2, "a")).unwrap();
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+
+        // all the arguments form the key by default
+        let by_all = cache(vec![]);
+        assert_ne!(
+            by_all.cache_key(&args(1, "a")).unwrap(),
+            by_all.cache_key(&args(1, "b")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parallel_modify() {
+        let js = |name: &str| IR::IO(IO::Js { name: name.to_string(), dedupe: false });
+        let ir = IR::Parallel(vec![
+            js("first"),
+            IR::Path(Box::new(js("second")), vec!["data".to_string()]),
+            IR::Parallel(vec![js("third")]),
+        ]);
+
+        let mut visited = vec![];
+        let ir = ir.modify(&mut |ir| {
+            if let IR::IO(IO::Js { name, .. }) = ir {
+                visited.push(name.clone());
+            }
+            None
+        });
+        assert_eq!(visited, vec!["first", "second", "third"]);
+
+        let mut cached = vec![];
+        Cache::wrap(NonZeroU64::new(100).unwrap(), vec![], ir).modify(&mut |ir| {
+            if let IR::Cache(Cache { io, .. }) = ir {
+                if let IO::Js { name, .. } = io.as_ref() {
+                    cached.push(name.clone());
+                }
+            }
+            None
+        });
+        assert_eq!(cached, vec!["first", "second", "third"]);
+    }
 }
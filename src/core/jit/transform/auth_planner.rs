@@ -61,10 +61,14 @@ pub fn update_ir(ir: &mut IR, vec: &mut Vec<Auth>) {
         | IR::ContextPath(_)
         | IR::Map(_)
         | IR::Entity(_)
+        | IR::Cond { .. }
         | IR::Service(_) => {}
         IR::Path(ir, _) => {
             update_ir(ir, vec);
         }
+        IR::Retry(retry) => {
+            update_ir(&mut retry.ir, vec);
+        }
         IR::Protect(auth, ir_0) => {
             vec.push(auth.clone());
 
@@ -75,10 +79,10 @@ pub fn update_ir(ir: &mut IR, vec: &mut Vec<Auth>) {
             update_ir(ir1, vec);
             update_ir(ir2, vec);
         }
-        IR::Discriminate(_, ir) => {
+        IR::Discriminate(_, ir) | IR::Debug { ir, .. } => {
             update_ir(ir, vec);
         }
-        IR::Merge(irs) => {
+        IR::Merge(irs) | IR::Parallel(irs) => {
             irs.iter_mut().for_each(|ir| update_ir(ir, vec));
         }
     }
@@ -21,15 +21,31 @@ fn check_cache(ir: &IR) -> Option<NonZeroU64> {
     match ir {
         IR::IO(_) => None,
         IR::Cache(cache) => Some(cache.max_age),
+        IR::Retry(retry) => check_cache(&retry.ir),
         IR::Path(ir, _) => check_cache(ir),
         IR::Protect(_, ir) => check_cache(ir),
         IR::Pipe(ir, ir1) => match (check_cache(ir), check_cache(ir1)) {
             (Some(age1), Some(age2)) => Some(age1.min(age2)),
             _ => None,
         },
-        IR::Merge(vec) => vec.iter().map(check_cache).min().unwrap_or_default(),
-        IR::Discriminate(_, ir) => check_cache(ir),
-        IR::Entity(hash_map) => hash_map.values().map(check_cache).min().unwrap_or_default(),
+        IR::Merge(vec) | IR::Parallel(vec) => vec.iter().map(check_cache).min().unwrap_or_default(),
+        IR::Discriminate(_, ir) | IR::Debug { ir, .. } => check_cache(ir),
+        IR::Cond { predicate, then, otherwise } => {
+            match (
+                check_cache(predicate),
+                check_cache(then),
+                check_cache(otherwise),
+            ) {
+                (Some(age1), Some(age2), Some(age3)) => Some(age1.min(age2).min(age3)),
+                _ => None,
+            }
+        }
+        IR::Entity(hash_map) => hash_map
+            .values()
+            .flat_map(|irs| irs.values())
+            .map(check_cache)
+            .min()
+            .unwrap_or_default(),
         IR::Dynamic(_) | IR::ContextPath(_) | IR::Map(_) | IR::Service(_) => None,
     }
 }
@@ -20,14 +20,18 @@ pub fn is_const(ir: &IR) -> bool {
         IR::Dynamic(dynamic_value) => dynamic_value.is_const(),
         IR::IO(_) => false,
         IR::Cache(_) => false,
+        IR::Retry(retry) => is_const(&retry.ir),
         IR::Path(ir, _) => is_const(ir),
         IR::ContextPath(_) => false,
         IR::Protect(_, ir) => is_const(ir),
         IR::Map(map) => is_const(&map.input),
         IR::Pipe(ir, ir1) => is_const(ir) && is_const(ir1),
-        IR::Merge(vec) => vec.iter().all(is_const),
-        IR::Discriminate(_, ir) => is_const(ir),
-        IR::Entity(hash_map) => hash_map.values().all(is_const),
+        IR::Merge(vec) | IR::Parallel(vec) => vec.iter().all(is_const),
+        IR::Discriminate(_, ir) | IR::Debug { ir, .. } => is_const(ir),
+        IR::Cond { predicate, then, otherwise } => {
+            is_const(predicate) && is_const(then) && is_const(otherwise)
+        }
+        IR::Entity(hash_map) => hash_map.values().flat_map(|irs| irs.values()).all(is_const),
         IR::Service(_) => true,
     }
 }
@@ -18,12 +18,19 @@ fn check_dedupe(ir: &IR) -> bool {
     match ir {
         IR::IO(io) => io.dedupe(),
         IR::Cache(cache) => cache.io.dedupe(),
+        IR::Retry(retry) => check_dedupe(&retry.ir),
         IR::Path(ir, _) => check_dedupe(ir),
         IR::Protect(_, ir) => check_dedupe(ir),
         IR::Pipe(ir, ir1) => check_dedupe(ir) && check_dedupe(ir1),
-        IR::Merge(vec) => vec.iter().all(check_dedupe),
-        IR::Discriminate(_, ir) => check_dedupe(ir),
-        IR::Entity(hash_map) => hash_map.values().all(check_dedupe),
+        IR::Merge(vec) | IR::Parallel(vec) => vec.iter().all(check_dedupe),
+        IR::Discriminate(_, ir) | IR::Debug { ir, .. } => check_dedupe(ir),
+        IR::Cond { predicate, then, otherwise } => {
+            check_dedupe(predicate) && check_dedupe(then) && check_dedupe(otherwise)
+        }
+        IR::Entity(hash_map) => hash_map
+            .values()
+            .flat_map(|irs| irs.values())
+            .all(check_dedupe),
         IR::Dynamic(_) => true,
         IR::ContextPath(_) => true,
         IR::Map(_) => true,
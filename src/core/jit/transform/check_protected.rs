@@ -20,14 +20,22 @@ pub fn is_protected(ir: &IR) -> bool {
         IR::Dynamic(_) => false,
         IR::IO(_) => false,
         IR::Cache(_) => false,
+        IR::Retry(retry) => is_protected(&retry.ir),
         IR::Path(ir, _) => is_protected(ir),
         IR::ContextPath(_) => false,
         IR::Protect(_, _) => true,
         IR::Map(map) => is_protected(&map.input),
         IR::Pipe(ir, ir1) => is_protected(ir) || is_protected(ir1),
         IR::Merge(vec) => vec.iter().all(is_protected),
-        IR::Discriminate(_, ir) => is_protected(ir),
-        IR::Entity(hash_map) => hash_map.values().any(is_protected),
+        IR::Parallel(vec) => vec.iter().any(is_protected),
+        IR::Discriminate(_, ir) | IR::Debug { ir, .. } => is_protected(ir),
+        IR::Cond { predicate, then, otherwise } => {
+            is_protected(predicate) || is_protected(then) || is_protected(otherwise)
+        }
+        IR::Entity(hash_map) => hash_map
+            .values()
+            .flat_map(|irs| irs.values())
+            .any(is_protected),
         IR::Service(_) => false,
     }
 }
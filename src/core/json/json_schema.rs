@@ -2,7 +2,9 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Display;
 
 use convert_case::{Case, Casing};
-use prost_reflect::{EnumDescriptor, FieldDescriptor, Kind, MessageDescriptor};
+use prost_reflect::{
+    Cardinality, EnumDescriptor, ExtensionDescriptor, FieldDescriptor, Kind, MessageDescriptor,
+};
 use serde::{Deserialize, Serialize};
 use tailcall_valid::{Valid, Validator};
 
@@ -260,6 +262,13 @@ impl TryFrom<&MessageDescriptor> for JsonSchema {
             map.insert(field.name().to_case(Case::Camel), field_schema);
         }
 
+        // the extension fields are renamed to their camelCase name when the
+        // response is decoded, see `ProtobufOperation::convert_output`
+        for extension in value.extensions() {
+            let field_schema = JsonSchema::try_from(&extension)?;
+            map.insert(extension.name().to_case(Case::Camel), field_schema);
+        }
+
         if map.is_empty() {
             Ok(JsonSchema::Empty)
         } else {
@@ -284,44 +293,57 @@ impl TryFrom<&FieldDescriptor> for JsonSchema {
     type Error = tailcall_valid::ValidationError<String>;
 
     fn try_from(value: &FieldDescriptor) -> Result<Self, Self::Error> {
-        let field_schema = match value.kind() {
-            Kind::Double => JsonSchema::Num,
-            Kind::Float => JsonSchema::Num,
-            Kind::Int32 => JsonSchema::Num,
-            Kind::Int64 => JsonSchema::Num,
-            Kind::Uint32 => JsonSchema::Num,
-            Kind::Uint64 => JsonSchema::Num,
-            Kind::Sint32 => JsonSchema::Num,
-            Kind::Sint64 => JsonSchema::Num,
-            Kind::Fixed32 => JsonSchema::Num,
-            Kind::Fixed64 => JsonSchema::Num,
-            Kind::Sfixed32 => JsonSchema::Num,
-            Kind::Sfixed64 => JsonSchema::Num,
-            Kind::Bool => JsonSchema::Bool,
-            Kind::String => JsonSchema::Str,
-            Kind::Bytes => JsonSchema::Str,
-            Kind::Message(msg) => JsonSchema::try_from(&msg)?,
-            Kind::Enum(enm) => JsonSchema::try_from(&enm)?,
-        };
-        let field_schema = if value
-            .cardinality()
-            .eq(&prost_reflect::Cardinality::Optional)
-        {
-            JsonSchema::Opt(Box::new(field_schema))
-        } else {
-            field_schema
-        };
-        let field_schema = if value.is_list() {
-            // if value is of type list then we treat it as optional.
-            JsonSchema::Opt(Box::new(JsonSchema::Arr(Box::new(field_schema))))
-        } else {
-            field_schema
-        };
+        field_schema(value.kind(), value.cardinality(), value.is_list())
+    }
+}
+
+impl TryFrom<&ExtensionDescriptor> for JsonSchema {
+    type Error = tailcall_valid::ValidationError<String>;
 
-        Ok(field_schema)
+    fn try_from(value: &ExtensionDescriptor) -> Result<Self, Self::Error> {
+        field_schema(value.kind(), value.cardinality(), value.is_list())
     }
 }
 
+fn field_schema(
+    kind: Kind,
+    cardinality: Cardinality,
+    is_list: bool,
+) -> Result<JsonSchema, tailcall_valid::ValidationError<String>> {
+    let field_schema = match kind {
+        Kind::Double => JsonSchema::Num,
+        Kind::Float => JsonSchema::Num,
+        Kind::Int32 => JsonSchema::Num,
+        Kind::Int64 => JsonSchema::Num,
+        Kind::Uint32 => JsonSchema::Num,
+        Kind::Uint64 => JsonSchema::Num,
+        Kind::Sint32 => JsonSchema::Num,
+        Kind::Sint64 => JsonSchema::Num,
+        Kind::Fixed32 => JsonSchema::Num,
+        Kind::Fixed64 => JsonSchema::Num,
+        Kind::Sfixed32 => JsonSchema::Num,
+        Kind::Sfixed64 => JsonSchema::Num,
+        Kind::Bool => JsonSchema::Bool,
+        Kind::String => JsonSchema::Str,
+        Kind::Bytes => JsonSchema::Str,
+        Kind::Message(msg) => JsonSchema::try_from(&msg)?,
+        Kind::Enum(enm) => JsonSchema::try_from(&enm)?,
+    };
+    let field_schema = if cardinality.eq(&Cardinality::Optional) {
+        JsonSchema::Opt(Box::new(field_schema))
+    } else {
+        field_schema
+    };
+    let field_schema = if is_list {
+        // if value is of type list then we treat it as optional.
+        JsonSchema::Opt(Box::new(JsonSchema::Arr(Box::new(field_schema))))
+    } else {
+        field_schema
+    };
+
+    Ok(field_schema)
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::{BTreeMap, BTreeSet};
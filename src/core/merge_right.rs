@@ -30,7 +30,10 @@ where
     V: Ord,
 {
     fn merge_right(mut self, other: Self) -> Self {
-        self.extend(other);
+        // equal values are replaced so the right side wins
+        for value in other {
+            self.replace(value);
+        }
         self
     }
 }
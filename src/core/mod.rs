@@ -53,6 +53,7 @@ use async_graphql::{Pos, Positioned};
 use async_graphql_value::ConstValue;
 pub use errata::Errata;
 pub use error::{Error, Result};
+use futures_util::stream::{BoxStream, StreamExt};
 use http::Response;
 use ir::model::IoId;
 pub use mustache::Mustache;
@@ -69,12 +70,23 @@ pub trait EnvIO: Send + Sync + 'static {
     fn get(&self, key: &str) -> Option<Cow<'_, str>>;
 }
 
+/// Chunks of the response body yielded as they are received.
+pub type ByteStream = BoxStream<'static, anyhow::Result<hyper::body::Bytes>>;
+
 #[async_trait::async_trait]
 pub trait HttpIO: Sync + Send + 'static {
     async fn execute(
         &self,
         request: reqwest::Request,
     ) -> anyhow::Result<Response<hyper::body::Bytes>>;
+
+    /// Executes the request and yields the response body in chunks, so large
+    /// bodies can be processed incrementally. Falls back to buffering the
+    /// whole body into a single chunk by default.
+    async fn execute_stream(&self, request: reqwest::Request) -> anyhow::Result<ByteStream> {
+        let response = self.execute(request).await?;
+        Ok(futures_util::stream::once(async move { Ok(response.body) }).boxed())
+    }
 }
 
 #[async_trait::async_trait]
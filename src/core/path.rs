@@ -94,6 +94,12 @@ impl<Ctx: ResolverContextLike> EvalContext<'_, Ctx> {
                     ctx.var(tail[0].as_ref())?,
                 ))),
                 "env" => Some(ValueString::String(ctx.env_var(tail[0].as_ref())?)),
+                "response" => match tail {
+                    [kind, name] if kind.as_ref() == "headers" => {
+                        Some(ValueString::String(ctx.response_header(name.as_ref())?))
+                    }
+                    _ => None,
+                },
                 _ => None,
             })
     }
@@ -248,8 +254,28 @@ mod tests {
             req_ctx
         });
 
-        static EVAL_CTX: Lazy<EvalContext<'static, MockGraphqlContext>> =
-            Lazy::new(|| EvalContext::new(&REQ_CTX, &MockGraphqlContext));
+        static EVAL_CTX: Lazy<EvalContext<'static, MockGraphqlContext>> = Lazy::new(|| {
+            let mut ctx = EvalContext::new(&REQ_CTX, &MockGraphqlContext);
+
+            let mut response_headers = HeaderMap::new();
+            response_headers.insert("etag", HeaderValue::from_static("W/\"123\""));
+            ctx.set_response_headers(Some(Arc::new(response_headers)));
+
+            ctx
+        });
+
+        #[test]
+        fn path_to_response_headers() {
+            assert_eq!(
+                EVAL_CTX.path_string(&["response", "headers", "etag"]),
+                Some(Cow::Borrowed("W/\"123\""))
+            );
+            assert_eq!(
+                EVAL_CTX.path_string(&["response", "headers", "missing"]),
+                None
+            );
+            assert_eq!(EVAL_CTX.path_string(&["response", "etag"]), None);
+        }
 
         #[test]
         fn path_to_value() {
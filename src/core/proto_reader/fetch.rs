@@ -167,6 +167,7 @@ impl GrpcReflection {
             body: body_,
             operation: operation.clone(),
             operation_type: Default::default(),
+            page_token: None,
         };
 
         let ctx = ConfigReaderContext::new(&self.target_runtime);
@@ -143,6 +143,21 @@ impl Scalar {
             Scalar::UInt32 => eval_unsigned(value, u32::try_from),
         }
     }
+    /// URL of the specification the values of the scalar conform to
+    pub fn specified_by_url(&self) -> Option<&'static str> {
+        match self {
+            Scalar::Email => {
+                Some("https://html.spec.whatwg.org/multipage/input.html#valid-e-mail-address")
+            }
+            Scalar::PhoneNumber => Some("https://en.wikipedia.org/wiki/E.164"),
+            Scalar::Date | Scalar::DateTime => {
+                Some("https://datatracker.ietf.org/doc/html/rfc3339")
+            }
+            Scalar::Url => Some("https://datatracker.ietf.org/doc/html/rfc3986"),
+            Scalar::JSON => Some("https://datatracker.ietf.org/doc/html/rfc8259"),
+            _ => None,
+        }
+    }
     pub fn find(name: &str) -> Option<&Scalar> {
         CUSTOM_SCALARS.get(name)
     }
@@ -104,8 +104,9 @@ pub mod http {
     use hyper::body::Bytes;
     use reqwest::Client;
     use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
+    use tailcall::cli::runtime::body_stream;
     use tailcall::core::http::Response;
-    use tailcall::core::HttpIO;
+    use tailcall::core::{ByteStream, HttpIO};
 
     use super::cacache_manager::CaCacheManager;
 
@@ -138,6 +139,15 @@ pub mod http {
             )
             .await?)
         }
+
+        async fn execute_stream(&self, request: reqwest::Request) -> Result<ByteStream> {
+            let response = self.client.execute(request).await;
+            Ok(body_stream(
+                response?
+                    .error_for_status()
+                    .map_err(|err| err.without_url())?,
+            ))
+        }
     }
 }
 pub mod env {
@@ -234,8 +244,9 @@ pub mod test {
         use hyper::body::Bytes;
         use reqwest::Client;
         use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
+        use tailcall::cli::runtime::body_stream;
         use tailcall::core::http::Response;
-        use tailcall::core::HttpIO;
+        use tailcall::core::{ByteStream, HttpIO};
 
         use super::cacache_manager::CaCacheManager;
 
@@ -268,6 +279,15 @@ pub mod test {
                 )
                 .await?)
             }
+
+            async fn execute_stream(&self, request: reqwest::Request) -> Result<ByteStream> {
+                let response = self.client.execute(request).await;
+                Ok(body_stream(
+                    response?
+                        .error_for_status()
+                        .map_err(|err| err.without_url())?,
+                ))
+            }
         }
     }
 